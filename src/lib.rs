@@ -8,9 +8,13 @@ pub mod types;
 pub mod utils;
 
 pub use services::{CacheManager, GraphBuilder, OpenApiParser};
-pub use tools::{CodeStyle, GenerateInput, GenerateOutput, GenerateTarget};
+pub use tools::{CodeStyle, GenerateInput, GenerateOutput, GenerateTarget, GeneratedFile};
 pub use tools::{DepsDirection, DepsInput, DepsOutput};
 pub use tools::{DiffInput, DiffOutput};
+pub use tools::{GraphInput, GraphOutput};
+pub use tools::{LintInput, LintOutput};
 pub use tools::{ParseFormat, ParseInput, ParseOutput};
 pub use tools::{StatusInput, StatusOutput};
-pub use tools::{diff_specs, generate_code, get_status, parse_spec, query_deps};
+pub use tools::{
+    diff_specs, export_graph, generate_code, get_status, lint_spec, parse_spec, query_deps,
+};
@@ -16,7 +16,9 @@ use std::io::{BufRead, Write};
 use tracing::{Level, debug, error, info};
 use tracing_subscriber::FmtSubscriber;
 
-use crate::tools::{diff_specs, generate_code, get_status, parse_spec, query_deps};
+use crate::tools::{
+    diff_specs, export_graph, generate_code, get_status, lint_spec, parse_spec, query_deps,
+};
 
 // ===== JSON-RPC Types =====
 
@@ -150,8 +152,8 @@ impl McpServer {
                             },
                             "format": {
                                 "type": "string",
-                                "enum": ["summary", "endpoints-list", "schemas-list", "endpoints", "schemas", "full"],
-                                "description": "Output format. summary=metadata only (default), endpoints-list/schemas-list=names only, endpoints/schemas=paginated details, full=paginated both"
+                                "enum": ["summary", "endpoints-list", "schemas-list", "endpoints", "schemas", "webhooks", "full"],
+                                "description": "Output format. summary=metadata only (default), endpoints-list/schemas-list=names only, endpoints/schemas=paginated details, webhooks=paginated webhooks and callbacks, full=paginated both"
                             },
                             "limit": {
                                 "type": "integer",
@@ -187,7 +189,7 @@ impl McpServer {
                 },
                 {
                     "name": "oas_deps",
-                    "description": "Query dependency graph - find affected paths when schema changes. Essential for tracking impact of schema modifications.",
+                    "description": "Query dependency graph - find affected paths when schema changes. Each result comes with the reference chain (e.g. GET:/posts/{id} -> Post.author -> User) explaining it and its depth (hops from the target); traversal goes transitively up or down from schemas and paths, e.g. to find other endpoints sharing schemas with an endpoint. Schema reference cycles can be listed too. Essential for tracking impact of schema modifications.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
@@ -206,7 +208,59 @@ impl McpServer {
                             "direction": {
                                 "type": "string",
                                 "enum": ["upstream", "downstream", "both"],
-                                "description": "Direction (default: downstream)"
+                                "description": "upstream: schemas the target depends on; downstream: schemas and paths depending on a schema, or other paths sharing schemas with a path (default: downstream for schemas, upstream for paths)"
+                            },
+                            "max_depth": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "description": "Stop this many hops from the target; each result reports its depth"
+                            },
+                            "project_dir": {
+                                "type": "string",
+                                "description": "Project directory for caching"
+                            },
+                            "use_cache": {
+                                "type": "boolean",
+                                "description": "Use cached spec if available (default: true)"
+                            },
+                            "all_chains": {
+                                "type": "boolean",
+                                "description": "List every reference chain instead of the shortest one per result (default: false)"
+                            },
+                            "cycles": {
+                                "type": "boolean",
+                                "description": "Report schema reference cycles involving the target, or all cycles when neither schema nor path is given, with whether each is broken by an optional or array reference (default: false)"
+                            }
+                        },
+                        "required": ["source"]
+                    }
+                },
+                {
+                    "name": "oas_graph",
+                    "description": "Export the dependency graph (paths using schemas, schemas referencing schemas by property) as Graphviz DOT, a Mermaid flowchart or a node/edge JSON document. Optionally limited to what a schema, path or tag is connected to.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "source": {
+                                "type": "string",
+                                "description": "URL or file path to OpenAPI spec"
+                            },
+                            "format": {
+                                "type": "string",
+                                "enum": ["dot", "mermaid", "json"],
+                                "description": "Output format (default: mermaid)"
+                            },
+                            "schema": {
+                                "type": "string",
+                                "description": "Only the schema, its dependencies, dependents and the paths using it"
+                            },
+                            "path": {
+                                "type": "string",
+                                "description": "Only the path (e.g. get:/pets) and the schemas it uses"
+                            },
+                            "tag": {
+                                "type": "string",
+                                "description": "Only operations with this tag and the schemas they use"
                             },
                             "project_dir": {
                                 "type": "string",
@@ -222,7 +276,7 @@ impl McpServer {
                 },
                 {
                     "name": "oas_diff",
-                    "description": "Compare two OpenAPI spec versions. Shows added, modified, removed endpoints and schemas, with breaking change detection.",
+                    "description": "Compare two OpenAPI spec versions. Shows added, modified, removed, moved and renamed endpoints and schemas, new deprecations with their x-deprecated-at/x-sunset dates, breaking change detection (removing deprecated items is reported separately) and a semantic version bump recommendation checked against info.version.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
@@ -249,11 +303,66 @@ impl McpServer {
                             "use_cache": {
                                 "type": "boolean",
                                 "description": "Use cached spec if available (default: true)"
+                            },
+                            "rules": {
+                                "type": "object",
+                                "description": "Breaking-change rule overrides by rule id, e.g. {\"enum-value-added\": {\"enabled\": false}, \"response-header-removed\": {\"severity\": \"error\"}}. Layered over the diff.rules section of .openapi-sync.json",
+                                "additionalProperties": {
+                                    "type": "object",
+                                    "properties": {
+                                        "enabled": { "type": "boolean" },
+                                        "severity": { "type": "string", "enum": ["error", "warning", "info"] }
+                                    }
+                                }
+                            },
+                            "changelog_format": {
+                                "type": "string",
+                                "enum": ["markdown", "html"],
+                                "description": "Also render a changelog grouped by tag and by breaking/non-breaking"
+                            },
+                            "changelog_template": {
+                                "type": "string",
+                                "description": "Handlebars template file replacing the built-in changelog template"
+                            },
+                            "docs_url": {
+                                "type": "string",
+                                "description": "API docs URL prefixed to changelog links (#operation/{id}, #schema/{name})"
+                            },
+                            "reports": {
+                                "type": "array",
+                                "items": { "type": "string", "enum": ["sarif", "junit", "json-patch"] },
+                                "description": "Machine-readable reports: SARIF located in the new spec file, JUnit XML with one test case per breaking change, RFC 6902 JSON Patch between the documents"
                             }
                         },
                         "required": ["old_source", "new_source"]
                     }
                 },
+                {
+                    "name": "oas_lint",
+                    "description": "Find dead components: schemas no operation or webhook reaches (directly or through other schemas) and references to schemas that do not exist. Optionally returns the spec with unused schemas pruned.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "source": {
+                                "type": "string",
+                                "description": "URL or file path to OpenAPI spec"
+                            },
+                            "prune": {
+                                "type": "boolean",
+                                "description": "Also return the spec document without unused schemas (default: false)"
+                            },
+                            "project_dir": {
+                                "type": "string",
+                                "description": "Project directory for caching"
+                            },
+                            "use_cache": {
+                                "type": "boolean",
+                                "description": "Use cached spec if available (default: true)"
+                            }
+                        },
+                        "required": ["source"]
+                    }
+                },
                 {
                     "name": "oas_status",
                     "description": "Get cached status without fetching spec. Shows metadata, coverage, and optionally checks if remote has updates.",
@@ -357,7 +466,9 @@ impl McpServer {
         let result = match name {
             "oas_parse" => self.call_oas_parse(&args).await,
             "oas_deps" => self.call_oas_deps(&args).await,
+            "oas_graph" => self.call_oas_graph(&args).await,
             "oas_diff" => self.call_oas_diff(&args).await,
+            "oas_lint" => self.call_oas_lint(&args).await,
             "oas_status" => self.call_oas_status(&args).await,
             "oas_generate" => self.call_oas_generate(&args).await,
             _ => return Err((-32602, format!("Unknown tool: {name}"))),
@@ -415,6 +526,7 @@ impl McpServer {
                 Some("schemas-list") => tools::ParseFormat::SchemasList,
                 Some("endpoints") => tools::ParseFormat::Endpoints,
                 Some("schemas") => tools::ParseFormat::Schemas,
+                Some("webhooks") => tools::ParseFormat::Webhooks,
                 Some("full") => tools::ParseFormat::Full,
                 _ => tools::ParseFormat::Summary, // Default to summary (minimal)
             },
@@ -452,18 +564,34 @@ impl McpServer {
             .get("use_cache")
             .and_then(|v| v.as_bool())
             .unwrap_or(true);
+        let all_chains = args
+            .get("all_chains")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let cycles = args
+            .get("cycles")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let max_depth = args
+            .get("max_depth")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize);
 
         let input = tools::DepsInput {
             source,
             schema,
             path,
             direction: match direction {
-                Some("upstream") => tools::DepsDirection::Upstream,
-                Some("both") => tools::DepsDirection::Both,
-                _ => tools::DepsDirection::Downstream,
+                Some("upstream") => Some(tools::DepsDirection::Upstream),
+                Some("downstream") => Some(tools::DepsDirection::Downstream),
+                Some("both") => Some(tools::DepsDirection::Both),
+                _ => None,
             },
+            max_depth,
             project_dir,
             use_cache,
+            all_chains,
+            cycles,
         };
 
         let result = query_deps(input).await;
@@ -503,6 +631,44 @@ impl McpServer {
             .and_then(|v| v.as_bool())
             .unwrap_or(true);
 
+        let rules = match args.get("rules") {
+            Some(rules) => serde_json::from_value(rules.clone())
+                .map_err(|e| format!("Invalid parameter: rules ({e})"))?,
+            None => Default::default(),
+        };
+
+        let changelog_format = match args.get("changelog_format").and_then(|v| v.as_str()) {
+            Some("markdown") => Some(services::ChangelogFormat::Markdown),
+            Some("html") => Some(services::ChangelogFormat::Html),
+            Some(format) => return Err(format!("Unknown changelog format: {format}")),
+            None => None,
+        };
+
+        let changelog_template = args
+            .get("changelog_template")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let docs_url = args
+            .get("docs_url")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let mut reports = Vec::new();
+        for format in args
+            .get("reports")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            reports.push(match format.as_str() {
+                Some("sarif") => services::ReportFormat::Sarif,
+                Some("junit") => services::ReportFormat::Junit,
+                Some("json-patch") => services::ReportFormat::JsonPatch,
+                _ => return Err(format!("Unknown report format: {format}")),
+            });
+        }
+
         let input = tools::DiffInput {
             old_source,
             new_source,
@@ -510,12 +676,85 @@ impl McpServer {
             breaking_only,
             project_dir,
             use_cache,
+            rules,
+            changelog_format,
+            changelog_template,
+            docs_url,
+            reports,
         };
 
         let result = diff_specs(input).await;
         serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
     }
 
+    async fn call_oas_graph(&self, args: &Value) -> Result<String, String> {
+        let source = args
+            .get("source")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing required parameter: source")?
+            .to_string();
+        let format = match args.get("format").and_then(|v| v.as_str()) {
+            Some("dot") => services::GraphFormat::Dot,
+            Some("mermaid") | None => services::GraphFormat::Mermaid,
+            Some("json") => services::GraphFormat::Json,
+            Some(format) => return Err(format!("Unknown graph format: {format}")),
+        };
+        let schema = args
+            .get("schema")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let path = args.get("path").and_then(|v| v.as_str()).map(String::from);
+        let tag = args.get("tag").and_then(|v| v.as_str()).map(String::from);
+        let project_dir = args
+            .get("project_dir")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let use_cache = args
+            .get("use_cache")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let input = tools::GraphInput {
+            source,
+            format,
+            schema,
+            path,
+            tag,
+            project_dir,
+            use_cache,
+        };
+
+        let result = export_graph(input).await;
+        serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+    }
+
+    async fn call_oas_lint(&self, args: &Value) -> Result<String, String> {
+        let source = args
+            .get("source")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing required parameter: source")?
+            .to_string();
+        let prune = args.get("prune").and_then(|v| v.as_bool()).unwrap_or(false);
+        let project_dir = args
+            .get("project_dir")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let use_cache = args
+            .get("use_cache")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let input = tools::LintInput {
+            source,
+            prune,
+            project_dir,
+            use_cache,
+        };
+
+        let result = lint_spec(input).await;
+        serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+    }
+
     async fn call_oas_status(&self, args: &Value) -> Result<String, String> {
         let project_dir = args
             .get("project_dir")
@@ -708,6 +947,8 @@ TOOLS:
     oas_parse    Parse and validate OpenAPI spec
     oas_deps     Query dependency graph
     oas_diff     Compare two spec versions
+    oas_graph    Export the dependency graph (DOT, Mermaid, JSON)
+    oas_lint     Find unused schemas and dangling references
     oas_status   Get cached status
     oas_generate Generate code from OpenAPI spec
 
@@ -25,6 +25,29 @@ impl CacheManager {
         Path::new(&self.project_dir).join(".openapi-sync.cache.json")
     }
 
+    /// Get config file path
+    fn config_path(&self) -> std::path::PathBuf {
+        Path::new(&self.project_dir).join(".openapi-sync.json")
+    }
+
+    /// Load the `diff` section of the project config; a missing file or
+    /// section yields the default rule set
+    pub fn load_diff_config(&self) -> OasResult<DiffConfig> {
+        let path = self.config_path();
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Ok(DiffConfig::default());
+        };
+
+        // Read only the `diff` section so partial configs are accepted
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| OasError::InvalidConfig(format!("{}: {e}", path.display())))?;
+        match value.get("diff") {
+            Some(section) => serde_json::from_value(section.clone())
+                .map_err(|e| OasError::InvalidConfig(format!("diff: {e}"))),
+            None => Ok(DiffConfig::default()),
+        }
+    }
+
     /// Get state file path
     #[allow(dead_code)]
     fn state_path(&self) -> std::path::PathBuf {
@@ -105,18 +128,15 @@ impl CacheManager {
         ttl_seconds: Option<u64>,
         http_headers: Option<&super::parser::HttpHeaders>,
     ) -> OasCache {
-        // For local files, extract mtime for cache validation
+        // For local files, extract mtimes (root and bundled files) for cache validation
         let local_cache = if !source.starts_with("http") {
-            if let Ok(metadata) = std::fs::metadata(source) {
-                if let Ok(modified) = metadata.modified() {
-                    LocalCacheInfo {
-                        mtime: Some(DateTime::<Utc>::from(modified).to_rfc3339()),
-                    }
-                } else {
-                    LocalCacheInfo::default()
-                }
-            } else {
-                LocalCacheInfo::default()
+            LocalCacheInfo {
+                mtime: file_mtime(source),
+                external_mtimes: spec
+                    .external_files
+                    .iter()
+                    .filter_map(|path| Some((path.clone(), file_mtime(path)?)))
+                    .collect(),
             }
         } else {
             LocalCacheInfo::default()
@@ -243,13 +263,13 @@ impl CacheManager {
                 .await
                 .map_err(|e| OasError::ConnectionFailed(e.to_string()))?;
 
-            let spec = super::parser::OpenApiParser::parse_content(&content, source)?;
+            let spec = super::parser::OpenApiParser::parse_content(&content, source).await?;
             Ok((spec, headers))
         } else {
             // Local file read
             let content =
                 std::fs::read_to_string(source).map_err(|e| OasError::ReadError(e.to_string()))?;
-            let spec = super::parser::OpenApiParser::parse_content(&content, source)?;
+            let spec = super::parser::OpenApiParser::parse_content(&content, source).await?;
             Ok((spec, super::parser::HttpHeaders::default()))
         }
     }
@@ -310,25 +330,39 @@ impl CacheManager {
         true
     }
 
-    /// Check if local file cache is valid (mtime + TTL)
+    /// Check if local file cache is valid (TTL + mtime of the root and
+    /// every file bundled into it)
     pub fn check_local_cache(&self, path: &str, cache: &OasCache) -> bool {
         // First check TTL
         if self.is_cache_expired(cache) {
             return false;
         }
 
-        let metadata = match std::fs::metadata(path) {
-            Ok(m) => m,
-            Err(_) => return false,
+        let Some(cached_mtime) = &cache.local_cache.mtime else {
+            return false;
         };
-
-        if let Ok(modified) = metadata.modified() {
-            let mtime = chrono::DateTime::<Utc>::from(modified).to_rfc3339();
-            if let Some(cached_mtime) = &cache.local_cache.mtime {
-                return &mtime == cached_mtime;
-            }
+        if file_mtime(path).as_ref() != Some(cached_mtime) {
+            return false;
         }
 
-        false
+        // The cached spec names the files it was bundled from
+        let external_files = cache
+            .parsed_spec
+            .as_ref()
+            .map(|spec| spec.external_files.as_slice())
+            .unwrap_or_default();
+        external_files.iter().all(|file| {
+            cache
+                .local_cache
+                .external_mtimes
+                .get(file)
+                .is_some_and(|cached| file_mtime(file).as_ref() == Some(cached))
+        })
     }
 }
+
+/// Modification time of a local file, RFC 3339
+fn file_mtime(path: &str) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(DateTime::<Utc>::from(modified).to_rfc3339())
+}
@@ -0,0 +1,508 @@
+//! Human-readable changelog rendering for spec diffs
+
+use crate::services::{BreakingChange, EndpointChange, SpecDiff};
+use crate::types::{OasError, OasResult, Severity, SpecMetadata};
+use handlebars::Handlebars;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Changelog output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogFormat {
+    Markdown,
+    Html,
+}
+
+const MARKDOWN_TEMPLATE: &str = r#"# {{title}} changelog
+
+{{old_version}} → {{new_version}}: {{breaking_count}} breaking, {{change_count}} other change(s)
+{{#each groups}}
+
+## {{name}}
+{{#if breaking}}
+
+### Breaking changes
+
+{{#each breaking}}
+- **{{change}}** [{{title}}]({{link}})
+{{#each details}}
+  - {{this}}
+{{/each}}
+{{/each}}
+{{/if}}
+{{#if changes}}
+
+### Changes
+
+{{#each changes}}
+- **{{change}}** [{{title}}]({{link}})
+{{#each details}}
+  - {{this}}
+{{/each}}
+{{/each}}
+{{/if}}
+{{/each}}
+"#;
+
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{title}} changelog</title>
+</head>
+<body>
+<h1>{{title}} changelog</h1>
+<p>{{old_version}} → {{new_version}}: {{breaking_count}} breaking, {{change_count}} other change(s)</p>
+{{#each groups}}
+<section>
+<h2>{{name}}</h2>
+{{#if breaking}}
+<h3>Breaking changes</h3>
+<ul class="breaking">
+{{#each breaking}}
+<li><strong>{{change}}</strong> <a href="{{link}}">{{title}}</a>
+<ul>{{#each details}}<li>{{this}}</li>{{/each}}</ul></li>
+{{/each}}
+</ul>
+{{/if}}
+{{#if changes}}
+<h3>Changes</h3>
+<ul>
+{{#each changes}}
+<li><strong>{{change}}</strong> <a href="{{link}}">{{title}}</a>
+<ul>{{#each details}}<li>{{this}}</li>{{/each}}</ul></li>
+{{/each}}
+</ul>
+{{/if}}
+</section>
+{{/each}}
+</body>
+</html>
+"#;
+
+/// Data available to changelog templates
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangelogContext {
+    pub title: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub breaking_count: usize,
+    pub change_count: usize,
+    /// Tags (sorted), then untagged operations, webhooks and schemas
+    pub groups: Vec<ChangelogGroup>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangelogGroup {
+    pub name: String,
+    pub breaking: Vec<ChangelogEntry>,
+    pub changes: Vec<ChangelogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangelogEntry {
+    /// added, modified, removed, moved or renamed
+    pub change: &'static str,
+    /// `GET /pets/{id}` or a schema name
+    pub title: String,
+    /// Operation or schema anchor, prefixed with the docs URL if given
+    pub link: String,
+    /// Breaking change messages first, then what else changed
+    pub details: Vec<String>,
+}
+
+/// Group name for operations without tags
+const UNTAGGED: &str = "Other operations";
+
+/// Changelog renderer
+pub struct ChangelogRenderer;
+
+impl ChangelogRenderer {
+    /// Render `diff` with the built-in template for `format`, or `template`
+    /// (Handlebars source receiving a `ChangelogContext`). HTML output is escaped
+    pub fn render(
+        diff: &SpecDiff,
+        old: &SpecMetadata,
+        new: &SpecMetadata,
+        format: ChangelogFormat,
+        template: Option<&str>,
+        docs_url: Option<&str>,
+    ) -> OasResult<String> {
+        let mut handlebars = Handlebars::new();
+        handlebars.set_strict_mode(true);
+        if format == ChangelogFormat::Markdown {
+            handlebars.register_escape_fn(handlebars::no_escape);
+        }
+
+        let template = template.unwrap_or(match format {
+            ChangelogFormat::Markdown => MARKDOWN_TEMPLATE,
+            ChangelogFormat::Html => HTML_TEMPLATE,
+        });
+        handlebars
+            .register_template_string("changelog", template)
+            .map_err(|e| OasError::TemplateError(e.to_string()))?;
+
+        let context = Self::context(diff, old, new, docs_url.unwrap_or_default());
+        handlebars
+            .render("changelog", &context)
+            .map_err(|e| OasError::TemplateError(e.to_string()))
+    }
+
+    /// Group changes by tag and by breaking/non-breaking. A change is breaking
+    /// when a breaking change of warning or error severity belongs to it
+    pub fn context(
+        diff: &SpecDiff,
+        old: &SpecMetadata,
+        new: &SpecMetadata,
+        docs_url: &str,
+    ) -> ChangelogContext {
+        let breaking: Vec<&BreakingChange> = diff
+            .breaking_changes
+            .iter()
+            .filter(|c| c.severity >= Severity::Warning)
+            .collect();
+        let schema_breaking = |name: &str| -> Vec<String> {
+            let location = format!("#/components/schemas/{name}");
+            breaking
+                .iter()
+                .filter(|c| {
+                    c.location == location || c.location.starts_with(&format!("{location}/"))
+                })
+                .map(|c| c.message.clone())
+                .collect()
+        };
+        let named_breaking = |key: &str| -> Vec<String> {
+            breaking
+                .iter()
+                .filter(|c| c.key.as_deref() == Some(key))
+                .map(|c| c.message.clone())
+                .collect()
+        };
+        // Change lists repeat the breaking messages already listed first
+        let append_new = |details: &mut Vec<String>, changes: &[String]| {
+            for change in changes {
+                if !details.contains(change) {
+                    details.push(change.clone());
+                }
+            }
+        };
+
+        let mut groups: BTreeMap<(u8, String), ChangelogGroup> = BTreeMap::new();
+        let mut push = |order: u8, group: &str, entry: ChangelogEntry, is_breaking: bool| {
+            let group =
+                groups
+                    .entry((order, group.to_string()))
+                    .or_insert_with(|| ChangelogGroup {
+                        name: group.to_string(),
+                        breaking: vec![],
+                        changes: vec![],
+                    });
+            if is_breaking {
+                group.breaking.push(entry);
+            } else {
+                group.changes.push(entry);
+            }
+        };
+
+        let operation = |change: &EndpointChange| {
+            let anchor = change
+                .operation_id
+                .clone()
+                .unwrap_or_else(|| format!("{}-{}", change.method, change.path).to_lowercase());
+            format!("{docs_url}#operation/{anchor}")
+        };
+        let endpoint_group = |change: &EndpointChange| match change.tags.first() {
+            Some(tag) => (0, tag.clone()),
+            None => (1, UNTAGGED.to_string()),
+        };
+        let title = |change: &EndpointChange| format!("{} {}", change.method, change.path);
+
+        for (kind, changes) in [
+            ("added", &diff.added_endpoints),
+            ("modified", &diff.modified_endpoints),
+            ("removed", &diff.removed_endpoints),
+        ] {
+            for change in changes {
+                let mut details = match kind {
+                    "removed" => named_breaking(&change.key),
+                    _ => {
+                        let prefix = format!("{} {} ", change.method, change.path);
+                        breaking
+                            .iter()
+                            .filter(|c| c.location.starts_with(&prefix))
+                            .map(|c| c.message.clone())
+                            .collect()
+                    }
+                };
+                let is_breaking = !details.is_empty()
+                    || change
+                        .affected_by_schemas
+                        .iter()
+                        .any(|s| !schema_breaking(s).is_empty());
+                if kind == "modified" {
+                    append_new(&mut details, &change.changes);
+                }
+
+                let (order, group) = endpoint_group(change);
+                push(
+                    order,
+                    &group,
+                    ChangelogEntry {
+                        change: kind,
+                        title: title(change),
+                        link: operation(change),
+                        details,
+                    },
+                    is_breaking,
+                );
+            }
+        }
+
+        for moved in &diff.moved_endpoints {
+            let mut details = named_breaking(&moved.old_key);
+            let is_breaking = !details.is_empty();
+            details.push(format!(
+                "Moved from {} (confidence {:.2})",
+                moved.old_path, moved.confidence
+            ));
+
+            let anchor = moved
+                .operation_id
+                .clone()
+                .unwrap_or_else(|| format!("{}-{}", moved.method, moved.new_path).to_lowercase());
+            let (order, group) = match moved.tags.first() {
+                Some(tag) => (0, tag.clone()),
+                None => (1, UNTAGGED.to_string()),
+            };
+            push(
+                order,
+                &group,
+                ChangelogEntry {
+                    change: "moved",
+                    title: format!("{} {}", moved.method, moved.new_path),
+                    link: format!("{docs_url}#operation/{anchor}"),
+                    details,
+                },
+                is_breaking,
+            );
+        }
+
+        for (kind, changes) in [
+            ("added", &diff.added_webhooks),
+            ("modified", &diff.modified_webhooks),
+            ("removed", &diff.removed_webhooks),
+        ] {
+            for change in changes {
+                let mut details = named_breaking(&change.key);
+                let is_breaking = !details.is_empty();
+                if kind == "modified" {
+                    append_new(&mut details, &change.changes);
+                }
+                push(
+                    2,
+                    "Webhooks",
+                    ChangelogEntry {
+                        change: kind,
+                        title: format!("{} {}", change.method, change.name),
+                        link: format!("{docs_url}#webhook/{}", change.name),
+                        details,
+                    },
+                    is_breaking,
+                );
+            }
+        }
+
+        for (kind, changes) in [
+            ("added", &diff.added_schemas),
+            ("modified", &diff.modified_schemas),
+            ("removed", &diff.removed_schemas),
+        ] {
+            for change in changes {
+                let mut details = schema_breaking(&change.name);
+                let is_breaking = !details.is_empty();
+                if kind == "modified" {
+                    append_new(&mut details, &change.changes);
+                }
+                push(
+                    3,
+                    "Schemas",
+                    ChangelogEntry {
+                        change: kind,
+                        title: change.name.clone(),
+                        link: format!("{docs_url}#schema/{}", change.name),
+                        details,
+                    },
+                    is_breaking,
+                );
+            }
+        }
+
+        for rename in &diff.renamed_schemas {
+            let mut details = schema_breaking(&rename.old_name);
+            let is_breaking = !details.is_empty();
+            details.push(format!(
+                "Renamed from {} (confidence {:.2})",
+                rename.old_name, rename.confidence
+            ));
+            push(
+                3,
+                "Schemas",
+                ChangelogEntry {
+                    change: "renamed",
+                    title: rename.new_name.clone(),
+                    link: format!("{docs_url}#schema/{}", rename.new_name),
+                    details,
+                },
+                is_breaking,
+            );
+        }
+
+        let mut groups: Vec<ChangelogGroup> = groups.into_values().collect();
+        for group in &mut groups {
+            group.breaking.sort_by(|a, b| a.title.cmp(&b.title));
+            group.changes.sort_by(|a, b| a.title.cmp(&b.title));
+        }
+
+        ChangelogContext {
+            title: new.title.clone(),
+            old_version: old.version.clone(),
+            new_version: new.version.clone(),
+            breaking_count: groups.iter().map(|g| g.breaking.len()).sum(),
+            change_count: groups.iter().map(|g| g.changes.len()).sum(),
+            groups,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::DiffEngine;
+    use crate::services::diff::tests::spec;
+    use crate::types::ParsedSpec;
+
+    fn versioned(version: &str, paths: serde_json::Value, pet: serde_json::Value) -> ParsedSpec {
+        let mut spec = spec(paths, serde_json::json!({ "Pet": pet }));
+        spec.metadata.version = version.to_string();
+        spec
+    }
+
+    fn operation(operation_id: &str, tag: &str) -> serde_json::Value {
+        serde_json::json!({
+            "operationId": operation_id,
+            "tags": [tag],
+            "responses": { "200": { "description": "ok" } }
+        })
+    }
+
+    #[test]
+    fn test_markdown_and_html_changelog() {
+        let old = versioned(
+            "1.0.0",
+            serde_json::json!({
+                "/pets": { "get": operation("listPets", "pets") },
+                "/pets/{id}": { "delete": operation("deletePet", "pets") }
+            }),
+            serde_json::json!({ "type": "object", "properties": { "name": { "type": "string" } } }),
+        );
+        let new = versioned(
+            "2.0.0",
+            serde_json::json!({
+                "/pets": { "get": operation("listPets", "pets") },
+                "/owners": { "get": operation("listOwners", "owners") }
+            }),
+            serde_json::json!({ "type": "object", "properties": {
+                "name": { "type": "string" },
+                "tag": { "type": "string" }
+            } }),
+        );
+        let diff = DiffEngine::diff(&old, &new, None);
+
+        let markdown = ChangelogRenderer::render(
+            &diff,
+            &old.metadata,
+            &new.metadata,
+            ChangelogFormat::Markdown,
+            None,
+            Some("https://docs.example.com/"),
+        )
+        .unwrap();
+        assert!(
+            markdown
+                .starts_with("# Pets changelog\n\n1.0.0 → 2.0.0: 1 breaking, 2 other change(s)\n")
+        );
+        assert!(markdown.contains(
+            "## owners\n\n### Changes\n\n- **added** [GET /owners](https://docs.example.com/#operation/listOwners)\n"
+        ));
+        assert!(markdown.contains(
+            "## pets\n\n### Breaking changes\n\n- **removed** [DELETE /pets/{id}](https://docs.example.com/#operation/deletePet)\n  - Endpoint 'delete:/pets/{id}' was removed\n"
+        ));
+        assert!(markdown.contains("- **modified** [Pet](https://docs.example.com/#schema/Pet)\n"));
+
+        let html = ChangelogRenderer::render(
+            &diff,
+            &old.metadata,
+            &new.metadata,
+            ChangelogFormat::Html,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(html.contains(r##"<a href="#operation/deletePet">DELETE /pets/{id}</a>"##));
+        assert!(html.contains("Endpoint &#x27;delete:/pets/{id}&#x27; was removed"));
+
+        let custom = ChangelogRenderer::render(
+            &diff,
+            &old.metadata,
+            &new.metadata,
+            ChangelogFormat::Markdown,
+            Some("{{#each groups}}{{name}};{{/each}}"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(custom, "owners;pets;Schemas;");
+
+        let invalid = ChangelogRenderer::render(
+            &diff,
+            &old.metadata,
+            &new.metadata,
+            ChangelogFormat::Markdown,
+            Some("{{#each groups}}"),
+            None,
+        );
+        assert!(matches!(invalid, Err(OasError::TemplateError(_))));
+    }
+
+    #[test]
+    fn test_breaking_details_listed_once() {
+        let list_pets = |required: bool| {
+            serde_json::json!({ "/pets": { "get": {
+                "parameters": [{
+                    "name": "limit", "in": "query", "required": required,
+                    "schema": { "type": "integer" }
+                }],
+                "responses": { "200": { "description": "ok" } }
+            } } })
+        };
+        let pet = |id_type: &str| serde_json::json!({ "type": "object", "properties": { "id": { "type": id_type } } });
+        let old = versioned("1.0.0", list_pets(false), pet("string"));
+        let new = versioned("2.0.0", list_pets(true), pet("integer"));
+        let diff = DiffEngine::diff(&old, &new, None);
+
+        let context = ChangelogRenderer::context(&diff, &old.metadata, &new.metadata, "");
+        for entry in context.groups.iter().flat_map(|g| &g.breaking) {
+            let mut unique = entry.details.clone();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), entry.details.len(), "{:?}", entry.details);
+        }
+        let get_pets = context
+            .groups
+            .iter()
+            .flat_map(|g| &g.breaking)
+            .find(|e| e.title == "GET /pets")
+            .unwrap();
+        assert!(get_pets.details.iter().any(|d| d.contains("limit")));
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::types::*;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Diff result between two specs
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,16 +10,108 @@ pub struct SpecDiff {
     pub added_endpoints: Vec<EndpointChange>,
     pub modified_endpoints: Vec<EndpointChange>,
     pub removed_endpoints: Vec<EndpointChange>,
+    /// Endpoints matched across a path change; their other changes are
+    /// reported under the new key
+    #[serde(default)]
+    pub moved_endpoints: Vec<EndpointMove>,
     pub unchanged_endpoints: usize,
 
+    pub added_webhooks: Vec<WebhookChange>,
+    pub modified_webhooks: Vec<WebhookChange>,
+    pub removed_webhooks: Vec<WebhookChange>,
+    pub unchanged_webhooks: usize,
+
     pub added_schemas: Vec<SchemaChange>,
     pub modified_schemas: Vec<SchemaChange>,
     pub removed_schemas: Vec<SchemaChange>,
+    /// Schemas matched across a rename; their other changes are reported
+    /// under the new name
+    #[serde(default)]
+    pub renamed_schemas: Vec<SchemaRename>,
     pub unchanged_schemas: usize,
 
+    /// Newly deprecated operations, parameters, schemas and properties, and
+    /// deprecated ones whose announced dates changed
+    #[serde(default)]
+    pub deprecations: Vec<Deprecation>,
+
     pub breaking_changes: Vec<BreakingChange>,
 }
 
+/// What a deprecation applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeprecationTarget {
+    Operation,
+    Parameter,
+    Schema,
+    Property,
+}
+
+/// Deprecation announced by the new spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub target: DeprecationTarget,
+    /// `METHOD /path`, `METHOD /path -> parameters/{in}/{name}` or a schema JSON pointer
+    pub location: String,
+    pub message: String,
+    /// `x-deprecated-at` / `x-sunset` of the new spec
+    #[serde(flatten)]
+    pub dates: DeprecationDates,
+}
+
+/// Why a removed item was paired with an added one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchEvidence {
+    /// Same definition under a different name or path
+    IdenticalDefinition,
+    /// Endpoints declaring the same `operationId`
+    SameOperationId,
+    /// Mostly the same properties, parameters or responses
+    StructuralSimilarity,
+}
+
+/// Endpoint that disappeared from one path and reappeared at another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointMove {
+    pub old_key: String,
+    pub new_key: String,
+    pub old_path: String,
+    pub new_path: String,
+    pub method: HttpMethod,
+    pub operation_id: Option<String>,
+    pub tags: Vec<String>,
+    /// 0.0 - 1.0
+    pub confidence: f64,
+    pub evidence: MatchEvidence,
+}
+
+/// Schema that disappeared under one name and reappeared under another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaRename {
+    pub old_name: String,
+    pub new_name: String,
+    /// 0.0 - 1.0
+    pub confidence: f64,
+    pub evidence: MatchEvidence,
+    /// Endpoints using the schema, keyed as in the new spec
+    pub affected_endpoints: Vec<String>,
+}
+
+/// Removed/added pair considered for a rename or move
+struct MatchCandidate {
+    old: String,
+    new: String,
+    confidence: f64,
+    evidence: MatchEvidence,
+}
+
+/// Minimum similarity for pairing items without stronger evidence
+const SIMILARITY_THRESHOLD: f64 = 0.6;
+/// Confidence of a structural match relative to its similarity
+const STRUCTURAL_CONFIDENCE: f64 = 0.9;
+
 /// Endpoint change details
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EndpointChange {
@@ -34,33 +126,281 @@ pub struct EndpointChange {
     pub affected_by_schemas: Vec<String>,
 }
 
+/// Webhook/callback change details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookChange {
+    pub key: String,
+    pub kind: WebhookKind,
+    pub name: String,
+    pub method: HttpMethod,
+    /// For modified: what changed
+    pub changes: Vec<String>,
+    /// Affected by schema changes (for modified)
+    pub affected_by_schemas: Vec<String>,
+}
+
+impl WebhookChange {
+    fn new(webhook: &Webhook, changes: Vec<String>, affected_by_schemas: Vec<String>) -> Self {
+        Self {
+            key: webhook.key(),
+            kind: webhook.kind,
+            name: webhook.name.clone(),
+            method: webhook.endpoint.method,
+            changes,
+            affected_by_schemas,
+        }
+    }
+}
+
 /// Schema change details
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaChange {
     pub name: String,
     /// For modified: what changed
     pub changes: Vec<String>,
+    /// For modified: structural changes by JSON pointer
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_changes: Vec<SchemaFieldChange>,
+    /// Whether the schema is reachable from requests, responses or both
+    #[serde(default, skip_serializing_if = "SchemaUsage::is_unused")]
+    pub usage: SchemaUsage,
     /// Endpoints affected by this schema change
     pub affected_endpoints: Vec<String>,
 }
 
+/// Structural change at one location inside a schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaFieldChange {
+    /// JSON pointer, e.g. `#/components/schemas/User/properties/email`
+    pub pointer: String,
+    pub kind: SchemaFieldChangeKind,
+    pub message: String,
+    pub breaking: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaFieldChangeKind {
+    PropertyAdded,
+    RequiredPropertyAdded,
+    PropertyRemoved,
+    BecameRequired,
+    BecameOptional,
+    TypeChanged,
+    FormatChanged,
+    EnumValueAdded,
+    EnumValueRemoved,
+    /// Removal of a property the old spec marked deprecated
+    DeprecatedPropertyRemoved,
+    /// `null` became an allowed value
+    BecameNullable,
+}
+
+impl SchemaFieldChangeKind {
+    /// Whether this change breaks consumers given where the schema is used.
+    /// Requests break when the server accepts less; responses break when
+    /// the consumer may receive something it did not expect or no longer
+    /// receives something it relied on
+    fn breaking_for(self, usage: SchemaUsage) -> bool {
+        match self {
+            Self::TypeChanged | Self::FormatChanged => true,
+            Self::RequiredPropertyAdded | Self::BecameRequired | Self::EnumValueRemoved => {
+                usage.request
+            }
+            Self::PropertyRemoved
+            | Self::DeprecatedPropertyRemoved
+            | Self::BecameOptional
+            | Self::BecameNullable
+            | Self::EnumValueAdded => usage.response,
+            Self::PropertyAdded => false,
+        }
+    }
+
+    /// Category reported when a change of this kind is breaking
+    fn category(self) -> BreakingChangeCategory {
+        match self {
+            Self::PropertyAdded | Self::RequiredPropertyAdded => {
+                BreakingChangeCategory::SchemaFieldAdded
+            }
+            Self::PropertyRemoved => BreakingChangeCategory::SchemaFieldRemoved,
+            Self::DeprecatedPropertyRemoved => BreakingChangeCategory::DeprecatedSchemaFieldRemoved,
+            Self::BecameRequired => BreakingChangeCategory::SchemaFieldRequired,
+            Self::BecameOptional => BreakingChangeCategory::SchemaFieldOptional,
+            Self::BecameNullable => BreakingChangeCategory::SchemaFieldNullable,
+            Self::TypeChanged | Self::FormatChanged => {
+                BreakingChangeCategory::SchemaFieldTypeChanged
+            }
+            Self::EnumValueAdded => BreakingChangeCategory::EnumValueAdded,
+            Self::EnumValueRemoved => BreakingChangeCategory::EnumValueRemoved,
+        }
+    }
+}
+
 /// Breaking change
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BreakingChange {
     pub category: BreakingChangeCategory,
+    /// Rule id the change was reported under (see `BreakingChangeCategory::rule_id`)
+    pub rule: String,
+    pub severity: Severity,
     pub message: String,
     pub location: String,
+    /// Key of the removed or moved endpoint or webhook the change is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl BreakingChange {
+    fn new(category: BreakingChangeCategory, message: String, location: String) -> Self {
+        Self {
+            category,
+            rule: category.rule_id().to_string(),
+            severity: category.default_severity(),
+            message,
+            location,
+            key: None,
+        }
+    }
+
+    fn with_key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum BreakingChangeCategory {
+    /// Removal of an endpoint that was not deprecated first
     EndpointRemoved,
+    DeprecatedEndpointRemoved,
+    EndpointMoved,
+    WebhookRemoved,
     ParameterAdded,
     ParameterTypeChanged,
+    ParameterFormatChanged,
+    ParameterBecameRequired,
+    ParameterStyleChanged,
+    ParameterLocationChanged,
+    /// Removal of a parameter that was not deprecated first
+    ParameterRemoved,
+    DeprecatedParameterRemoved,
     ResponseTypeChanged,
+    ResponseRemoved,
+    /// Removal of a schema that was not deprecated first
     SchemaRemoved,
+    DeprecatedSchemaRemoved,
+    SchemaRenamed,
+    SchemaFieldAdded,
+    /// Removal of a property that was not deprecated first
     SchemaFieldRemoved,
+    DeprecatedSchemaFieldRemoved,
     SchemaFieldTypeChanged,
+    SchemaFieldRequired,
+    SchemaFieldOptional,
+    SchemaFieldNullable,
+    EnumValueAdded,
+    EnumValueRemoved,
+    ConstraintTightened,
+    DiscriminatorMappingRemoved,
+    ResponseHeaderRemoved,
+    MediaTypeRemoved,
+}
+
+impl BreakingChangeCategory {
+    pub const ALL: [Self; 30] = [
+        Self::EndpointRemoved,
+        Self::DeprecatedEndpointRemoved,
+        Self::EndpointMoved,
+        Self::WebhookRemoved,
+        Self::ParameterAdded,
+        Self::ParameterTypeChanged,
+        Self::ParameterFormatChanged,
+        Self::ParameterBecameRequired,
+        Self::ParameterStyleChanged,
+        Self::ParameterLocationChanged,
+        Self::ParameterRemoved,
+        Self::DeprecatedParameterRemoved,
+        Self::ResponseTypeChanged,
+        Self::ResponseRemoved,
+        Self::SchemaRemoved,
+        Self::DeprecatedSchemaRemoved,
+        Self::SchemaRenamed,
+        Self::SchemaFieldAdded,
+        Self::SchemaFieldRemoved,
+        Self::DeprecatedSchemaFieldRemoved,
+        Self::SchemaFieldTypeChanged,
+        Self::SchemaFieldRequired,
+        Self::SchemaFieldOptional,
+        Self::SchemaFieldNullable,
+        Self::EnumValueAdded,
+        Self::EnumValueRemoved,
+        Self::ConstraintTightened,
+        Self::DiscriminatorMappingRemoved,
+        Self::ResponseHeaderRemoved,
+        Self::MediaTypeRemoved,
+    ];
+
+    /// Stable rule id used in the `diff.rules` configuration
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            Self::EndpointRemoved => "endpoint-removed",
+            Self::DeprecatedEndpointRemoved => "deprecated-endpoint-removed",
+            Self::EndpointMoved => "endpoint-moved",
+            Self::WebhookRemoved => "webhook-removed",
+            Self::ParameterAdded => "parameter-added",
+            Self::ParameterTypeChanged => "parameter-type-changed",
+            Self::ParameterFormatChanged => "parameter-format-changed",
+            Self::ParameterBecameRequired => "parameter-became-required",
+            Self::ParameterStyleChanged => "parameter-style-changed",
+            Self::ParameterLocationChanged => "parameter-location-changed",
+            Self::ParameterRemoved => "parameter-removed",
+            Self::DeprecatedParameterRemoved => "deprecated-parameter-removed",
+            Self::ResponseTypeChanged => "response-type-changed",
+            Self::ResponseRemoved => "response-removed",
+            Self::SchemaRemoved => "schema-removed",
+            Self::DeprecatedSchemaRemoved => "deprecated-schema-removed",
+            Self::SchemaRenamed => "schema-renamed",
+            Self::SchemaFieldAdded => "schema-field-added",
+            Self::SchemaFieldRemoved => "schema-field-removed",
+            Self::DeprecatedSchemaFieldRemoved => "deprecated-schema-field-removed",
+            Self::SchemaFieldTypeChanged => "schema-field-type-changed",
+            Self::SchemaFieldRequired => "schema-field-required",
+            Self::SchemaFieldOptional => "schema-field-optional",
+            Self::SchemaFieldNullable => "schema-field-nullable",
+            Self::EnumValueAdded => "enum-value-added",
+            Self::EnumValueRemoved => "enum-value-removed",
+            Self::ConstraintTightened => "constraint-tightened",
+            Self::DiscriminatorMappingRemoved => "discriminator-mapping-removed",
+            Self::ResponseHeaderRemoved => "response-header-removed",
+            Self::MediaTypeRemoved => "media-type-removed",
+        }
+    }
+
+    /// Severity when the rule is not overridden. Changes that only break
+    /// consumers relying on undocumented tolerance, or removals consumers
+    /// were warned about through deprecation, are warnings; a schema rename
+    /// only changes generated type names
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Self::SchemaRenamed => Severity::Info,
+            Self::DeprecatedEndpointRemoved
+            | Self::DeprecatedParameterRemoved
+            | Self::DeprecatedSchemaRemoved
+            | Self::DeprecatedSchemaFieldRemoved
+            | Self::SchemaFieldOptional
+            | Self::EnumValueAdded
+            | Self::ConstraintTightened
+            | Self::DiscriminatorMappingRemoved
+            | Self::ResponseHeaderRemoved => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    fn from_rule_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.rule_id() == id)
+    }
 }
 
 /// Diff engine
@@ -77,18 +417,96 @@ impl DiffEngine {
             added_endpoints: Vec::new(),
             modified_endpoints: Vec::new(),
             removed_endpoints: Vec::new(),
+            moved_endpoints: Vec::new(),
             unchanged_endpoints: 0,
 
+            added_webhooks: Vec::new(),
+            modified_webhooks: Vec::new(),
+            removed_webhooks: Vec::new(),
+            unchanged_webhooks: 0,
+
             added_schemas: Vec::new(),
             modified_schemas: Vec::new(),
             removed_schemas: Vec::new(),
+            renamed_schemas: Vec::new(),
             unchanged_schemas: 0,
 
+            deprecations: Vec::new(),
+
             breaking_changes: Vec::new(),
         };
 
+        // Pair removed items with added ones first, so a rename or move is
+        // diffed as one item rather than reported as a removal plus an addition
+        let schema_renames = Self::match_schema_renames(old_spec, new_spec);
+        let mut normalized =
+            (!schema_renames.is_empty()).then(|| Self::rename_schemas(old_spec, &schema_renames));
+        let endpoint_moves =
+            Self::match_endpoint_moves(normalized.as_ref().unwrap_or(old_spec), new_spec);
+        if !endpoint_moves.is_empty() {
+            let spec = normalized.get_or_insert_with(|| old_spec.clone());
+            Self::move_endpoints(spec, new_spec, &endpoint_moves);
+        }
+        if let Some(spec) = &mut normalized {
+            Self::sync_hashes(spec, new_spec);
+        }
+
+        for candidate in schema_renames {
+            let mut affected: Vec<String> = graph
+                .map(|g| g.get_affected_paths(&candidate.new).into_iter().collect())
+                .unwrap_or_default();
+            affected.sort();
+
+            diff.breaking_changes.push(BreakingChange::new(
+                BreakingChangeCategory::SchemaRenamed,
+                format!(
+                    "Schema '{}' was renamed to '{}'",
+                    candidate.old, candidate.new
+                ),
+                format!("#/components/schemas/{}", candidate.old),
+            ));
+            diff.renamed_schemas.push(SchemaRename {
+                old_name: candidate.old,
+                new_name: candidate.new,
+                confidence: candidate.confidence,
+                evidence: candidate.evidence,
+                affected_endpoints: affected,
+            });
+        }
+
+        for candidate in endpoint_moves {
+            let (old, new) = (
+                &old_spec.endpoints[&candidate.old],
+                &new_spec.endpoints[&candidate.new],
+            );
+            diff.breaking_changes.push(
+                BreakingChange::new(
+                    BreakingChangeCategory::EndpointMoved,
+                    format!(
+                        "Endpoint '{}' moved to '{} {}'",
+                        candidate.old, new.method, new.path
+                    ),
+                    format!("{} {}", new.method, new.path),
+                )
+                .with_key(&candidate.old),
+            );
+            diff.moved_endpoints.push(EndpointMove {
+                old_key: candidate.old,
+                new_key: candidate.new,
+                old_path: old.path.clone(),
+                new_path: new.path.clone(),
+                method: new.method,
+                operation_id: new.operation_id.clone(),
+                tags: new.tags.clone(),
+                confidence: candidate.confidence,
+                evidence: candidate.evidence,
+            });
+        }
+
+        let old_spec = normalized.as_ref().unwrap_or(old_spec);
+
         // Compare schemas first (to track affected endpoints)
-        let schema_changes = Self::compare_schemas(old_spec, new_spec);
+        let schema_changes = Self::compare_schemas(old_spec, new_spec, graph);
 
         for (name, change_type) in &schema_changes {
             match change_type {
@@ -96,32 +514,104 @@ impl DiffEngine {
                     diff.added_schemas.push(SchemaChange {
                         name: name.clone(),
                         changes: vec!["New schema".to_string()],
+                        field_changes: vec![],
+                        usage: SchemaUsage::default(),
                         affected_endpoints: vec![],
                     });
                 }
-                ChangeType::Modified(changes) => {
+                ChangeType::Modified(changes, field_changes) => {
+                    diff.deprecations.extend(Self::schema_deprecations(
+                        name,
+                        &old_spec.schemas[name].schema_type,
+                        &new_spec.schemas[name].schema_type,
+                    ));
+
                     let affected = graph
                         .map(|g| g.get_affected_paths(name).into_iter().collect::<Vec<_>>())
                         .unwrap_or_default();
 
+                    for change in field_changes.iter().filter(|c| c.breaking) {
+                        diff.breaking_changes.push(BreakingChange::new(
+                            change.kind.category(),
+                            change.message.clone(),
+                            change.pointer.clone(),
+                        ));
+                    }
+
+                    let usage = Self::schema_usage(graph, name);
                     diff.modified_schemas.push(SchemaChange {
                         name: name.clone(),
                         changes: changes.clone(),
+                        field_changes: field_changes.clone(),
+                        usage,
                         affected_endpoints: affected,
                     });
+
+                    // Stricter validation and fewer accepted tags only reject
+                    // what consumers send; responses are unaffected
+                    if !usage.request {
+                        continue;
+                    }
+
+                    let mut tightened = Vec::new();
+                    Self::tightened_constraints(
+                        &format!("#/components/schemas/{name}"),
+                        name,
+                        &old_spec.schemas[name].schema_type,
+                        &new_spec.schemas[name].schema_type,
+                        &mut tightened,
+                    );
+                    for (location, message) in tightened {
+                        diff.breaking_changes.push(BreakingChange::new(
+                            BreakingChangeCategory::ConstraintTightened,
+                            message,
+                            location,
+                        ));
+                    }
+
+                    for message in Self::removed_discriminator_mappings(
+                        name,
+                        &old_spec.schemas[name].schema_type,
+                        &new_spec.schemas[name].schema_type,
+                    ) {
+                        diff.breaking_changes.push(BreakingChange::new(
+                            BreakingChangeCategory::DiscriminatorMappingRemoved,
+                            message,
+                            format!("#/components/schemas/{name}/discriminator"),
+                        ));
+                    }
                 }
                 ChangeType::Removed => {
                     diff.removed_schemas.push(SchemaChange {
                         name: name.clone(),
                         changes: vec!["Schema removed".to_string()],
+                        field_changes: vec![],
+                        usage: SchemaUsage::default(),
                         affected_endpoints: vec![],
                     });
 
-                    diff.breaking_changes.push(BreakingChange {
-                        category: BreakingChangeCategory::SchemaRemoved,
-                        message: format!("Schema '{name}' was removed"),
-                        location: format!("#/components/schemas/{name}"),
-                    });
+                    let deprecation = old_spec.schemas[name]
+                        .schema_type
+                        .constraints()
+                        .filter(|c| c.deprecated);
+                    let (category, message) = match deprecation {
+                        Some(c) => (
+                            BreakingChangeCategory::DeprecatedSchemaRemoved,
+                            format!(
+                                "Deprecated schema '{name}' was removed{}",
+                                dates_suffix(&c.deprecation_dates)
+                            ),
+                        ),
+                        None => (
+                            BreakingChangeCategory::SchemaRemoved,
+                            format!("Schema '{name}' was removed"),
+                        ),
+                    };
+                    diff.breaking_changes.push(BreakingChange::new(
+                        category,
+                        message,
+                        format!("#/components/schemas/{name}"),
+                    ));
                 }
                 ChangeType::Unchanged => {
                     diff.unchanged_schemas += 1;
@@ -133,7 +623,7 @@ impl DiffEngine {
         let mut schema_affected_endpoints: HashSet<String> = HashSet::new();
         if let Some(g) = graph {
             for (name, change_type) in &schema_changes {
-                if matches!(change_type, ChangeType::Modified(_)) {
+                if matches!(change_type, ChangeType::Modified(..)) {
                     schema_affected_endpoints.extend(g.get_affected_paths(name));
                 }
             }
@@ -170,11 +660,22 @@ impl DiffEngine {
                 affected_by_schemas: vec![],
             });
 
-            diff.breaking_changes.push(BreakingChange {
-                category: BreakingChangeCategory::EndpointRemoved,
-                message: format!("Endpoint '{key}' was removed"),
-                location: endpoint.path.clone(),
-            });
+            let (category, message) = if endpoint.deprecated {
+                (
+                    BreakingChangeCategory::DeprecatedEndpointRemoved,
+                    format!(
+                        "Deprecated endpoint '{key}' was removed{}",
+                        dates_suffix(&endpoint.deprecation_dates)
+                    ),
+                )
+            } else {
+                (
+                    BreakingChangeCategory::EndpointRemoved,
+                    format!("Endpoint '{key}' was removed"),
+                )
+            };
+            diff.breaking_changes
+                .push(BreakingChange::new(category, message, endpoint.path.clone()).with_key(key));
         }
 
         // Modified or unchanged endpoints
@@ -184,6 +685,24 @@ impl DiffEngine {
 
             // Check if directly modified
             let direct_changes = Self::compare_endpoints(old_endpoint, new_endpoint);
+            if !direct_changes.is_empty() {
+                diff.deprecations
+                    .extend(Self::endpoint_deprecations(old_endpoint, new_endpoint));
+                for change in Self::parameter_changes(old_endpoint, new_endpoint) {
+                    if let Some(category) = change.category {
+                        diff.breaking_changes.push(BreakingChange::new(
+                            category,
+                            change.message,
+                            format!(
+                                "{} {} -> parameters/{}",
+                                old_endpoint.method, old_endpoint.path, change.location
+                            ),
+                        ));
+                    }
+                }
+                diff.breaking_changes
+                    .extend(Self::response_breaking_changes(old_endpoint, new_endpoint));
+            }
 
             // Check if affected by schema changes
             let affected_schemas: Vec<String> = new_endpoint
@@ -192,7 +711,7 @@ impl DiffEngine {
                 .filter(|s| {
                     schema_changes
                         .get(*s)
-                        .is_some_and(|c| matches!(c, ChangeType::Modified(_)))
+                        .is_some_and(|c| matches!(c, ChangeType::Modified(..)))
                 })
                 .cloned()
                 .collect();
@@ -217,13 +736,334 @@ impl DiffEngine {
             }
         }
 
+        // Compare webhooks and callbacks
+        let old_keys: HashSet<_> = old_spec.webhooks.keys().collect();
+        let new_keys: HashSet<_> = new_spec.webhooks.keys().collect();
+
+        for key in new_keys.difference(&old_keys) {
+            diff.added_webhooks.push(WebhookChange::new(
+                &new_spec.webhooks[*key],
+                vec!["New webhook".to_string()],
+                vec![],
+            ));
+        }
+
+        for key in old_keys.difference(&new_keys) {
+            let webhook = &old_spec.webhooks[*key];
+            diff.removed_webhooks.push(WebhookChange::new(
+                webhook,
+                vec!["Webhook removed".to_string()],
+                vec![],
+            ));
+
+            diff.breaking_changes.push(
+                BreakingChange::new(
+                    BreakingChangeCategory::WebhookRemoved,
+                    format!("Webhook '{key}' was removed"),
+                    webhook.endpoint.path.clone(),
+                )
+                .with_key(key),
+            );
+        }
+
+        for key in old_keys.intersection(&new_keys) {
+            let old_webhook = &old_spec.webhooks[*key];
+            let new_webhook = &new_spec.webhooks[*key];
+
+            let mut changes = Self::compare_endpoints(&old_webhook.endpoint, &new_webhook.endpoint);
+            if old_webhook.expression != new_webhook.expression {
+                changes.push("Callback URL expression changed".to_string());
+            }
+
+            let affected_schemas: Vec<String> = new_webhook
+                .endpoint
+                .schema_refs
+                .iter()
+                .filter(|s| {
+                    schema_changes
+                        .get(*s)
+                        .is_some_and(|c| matches!(c, ChangeType::Modified(..)))
+                })
+                .cloned()
+                .collect();
+
+            if changes.is_empty() && affected_schemas.is_empty() {
+                diff.unchanged_webhooks += 1;
+                continue;
+            }
+
+            for schema in &affected_schemas {
+                changes.push(format!("Affected by schema change: {schema}"));
+            }
+            diff.modified_webhooks
+                .push(WebhookChange::new(new_webhook, changes, affected_schemas));
+        }
+
         diff
     }
 
+    /// Pair removed schemas with added ones: identical definitions first,
+    /// then schemas sharing most of their properties or enum values. Matching
+    /// repeats with references rewritten through the pairs found so far, so a
+    /// schema that only differs by pointing at a renamed schema is found too
+    fn match_schema_renames(old_spec: &ParsedSpec, new_spec: &ParsedSpec) -> Vec<MatchCandidate> {
+        let mut removed: Vec<_> = old_spec
+            .schemas
+            .values()
+            .filter(|s| !new_spec.schemas.contains_key(&s.name))
+            .collect();
+        let mut added: Vec<_> = new_spec
+            .schemas
+            .values()
+            .filter(|s| !old_spec.schemas.contains_key(&s.name))
+            .collect();
+
+        let mut renames = HashMap::new();
+        let mut pairs = Vec::new();
+        loop {
+            let mut candidates = Vec::new();
+            for old in &removed {
+                let mut renamed = (*old).clone();
+                renamed.schema_type.rename_refs(&renames);
+                for reference in &mut renamed.refs {
+                    if let Some(new_name) = renames.get(reference) {
+                        *reference = new_name.clone();
+                    }
+                }
+
+                for new in &added {
+                    let (confidence, evidence) = if old.hash == new.hash {
+                        (1.0, MatchEvidence::IdenticalDefinition)
+                    } else if same_definition(&renamed, *new, &["name", "hash"]) {
+                        (0.95, MatchEvidence::IdenticalDefinition)
+                    } else {
+                        let similarity = similarity(
+                            &schema_features(&renamed.schema_type),
+                            &schema_features(&new.schema_type),
+                        );
+                        if similarity < SIMILARITY_THRESHOLD {
+                            continue;
+                        }
+                        (
+                            similarity * STRUCTURAL_CONFIDENCE,
+                            MatchEvidence::StructuralSimilarity,
+                        )
+                    };
+                    candidates.push(MatchCandidate {
+                        old: old.name.clone(),
+                        new: new.name.clone(),
+                        confidence,
+                        evidence,
+                    });
+                }
+            }
+
+            let found = Self::pair_candidates(candidates);
+            if found.is_empty() {
+                break;
+            }
+            for pair in &found {
+                renames.insert(pair.old.clone(), pair.new.clone());
+            }
+            removed.retain(|s| !renames.contains_key(&s.name));
+            added.retain(|s| !renames.values().any(|name| *name == s.name));
+            pairs.extend(found);
+        }
+
+        pairs.sort_by(|a, b| a.old.cmp(&b.old));
+        pairs
+    }
+
+    /// Pair removed endpoints with added ones: same `operationId`, then an
+    /// identical operation with the same method, then an operation with the
+    /// same method, last static path segment and mostly the same parameters
+    /// and responses
+    fn match_endpoint_moves(old_spec: &ParsedSpec, new_spec: &ParsedSpec) -> Vec<MatchCandidate> {
+        let removed: Vec<_> = old_spec
+            .endpoints
+            .iter()
+            .filter(|(key, _)| !new_spec.endpoints.contains_key(*key))
+            .collect();
+        let added: Vec<_> = new_spec
+            .endpoints
+            .iter()
+            .filter(|(key, _)| !old_spec.endpoints.contains_key(*key))
+            .collect();
+
+        let mut candidates = Vec::new();
+        for (old_key, old) in &removed {
+            for (new_key, new) in &added {
+                let identical = same_definition(*old, *new, &["path", "method", "hash"]);
+                let (confidence, evidence) = if old.operation_id.is_some()
+                    && old.operation_id == new.operation_id
+                {
+                    (
+                        if identical { 1.0 } else { 0.95 },
+                        MatchEvidence::SameOperationId,
+                    )
+                } else if old.method != new.method {
+                    continue;
+                } else if identical {
+                    (0.9, MatchEvidence::IdenticalDefinition)
+                } else {
+                    if last_static_segment(&old.path) != last_static_segment(&new.path) {
+                        continue;
+                    }
+                    let similarity = similarity(&endpoint_features(old), &endpoint_features(new));
+                    if similarity < SIMILARITY_THRESHOLD {
+                        continue;
+                    }
+                    (
+                        similarity * STRUCTURAL_CONFIDENCE,
+                        MatchEvidence::StructuralSimilarity,
+                    )
+                };
+                candidates.push(MatchCandidate {
+                    old: (*old_key).clone(),
+                    new: (*new_key).clone(),
+                    confidence,
+                    evidence,
+                });
+            }
+        }
+
+        Self::pair_candidates(candidates)
+    }
+
+    /// Greedily keep the most confident pairs, each item used at most once
+    fn pair_candidates(mut candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate> {
+        candidates.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then_with(|| a.old.cmp(&b.old))
+                .then_with(|| a.new.cmp(&b.new))
+        });
+
+        let mut used_old = HashSet::new();
+        let mut used_new = HashSet::new();
+        let mut pairs: Vec<_> = candidates
+            .into_iter()
+            .filter(|c| used_old.insert(c.old.clone()) && used_new.insert(c.new.clone()))
+            .map(|c| MatchCandidate {
+                confidence: (c.confidence * 100.0).round() / 100.0,
+                ..c
+            })
+            .collect();
+        pairs.sort_by(|a, b| a.old.cmp(&b.old));
+        pairs
+    }
+
+    /// Copy of `spec` with schemas renamed and every reference rewritten
+    fn rename_schemas(spec: &ParsedSpec, renames: &[MatchCandidate]) -> ParsedSpec {
+        let renames: HashMap<String, String> = renames
+            .iter()
+            .map(|c| (c.old.clone(), c.new.clone()))
+            .collect();
+
+        let mut spec = spec.clone();
+        spec.schemas = std::mem::take(&mut spec.schemas)
+            .into_values()
+            .map(|mut schema| {
+                if let Some(new_name) = renames.get(&schema.name) {
+                    schema.name = new_name.clone();
+                }
+                schema.schema_type.rename_refs(&renames);
+                for reference in &mut schema.refs {
+                    if let Some(new_name) = renames.get(reference) {
+                        *reference = new_name.clone();
+                    }
+                }
+                (schema.name.clone(), schema)
+            })
+            .collect();
+        for endpoint in spec.endpoints.values_mut() {
+            endpoint.rename_refs(&renames);
+        }
+        for webhook in spec.webhooks.values_mut() {
+            webhook.endpoint.rename_refs(&renames);
+        }
+        spec
+    }
+
+    /// Re-key moved endpoints, and callbacks declared by them, under their new keys
+    fn move_endpoints(spec: &mut ParsedSpec, new_spec: &ParsedSpec, moves: &[MatchCandidate]) {
+        let mut moved = HashMap::new();
+        for candidate in moves {
+            if let Some(mut endpoint) = spec.endpoints.remove(&candidate.old) {
+                let target = &new_spec.endpoints[&candidate.new];
+                endpoint.path = target.path.clone();
+                endpoint.method = target.method;
+                moved.insert(candidate.old.clone(), candidate.new.clone());
+                spec.endpoints.insert(candidate.new.clone(), endpoint);
+            }
+        }
+
+        spec.webhooks = std::mem::take(&mut spec.webhooks)
+            .into_values()
+            .map(|mut webhook| {
+                if let Some(parent) = &mut webhook.parent
+                    && let Some(new_key) = moved.get(parent)
+                {
+                    *parent = new_key.clone();
+                }
+                (webhook.key(), webhook)
+            })
+            .collect();
+    }
+
+    /// Rewritten references change definitions but not their hashes; items
+    /// that now match the new spec exactly take its hash so they count as unchanged
+    fn sync_hashes(spec: &mut ParsedSpec, new_spec: &ParsedSpec) {
+        for (name, schema) in &mut spec.schemas {
+            if let Some(new) = new_spec.schemas.get(name)
+                && same_definition(&*schema, new, &["hash"])
+            {
+                schema.hash = new.hash.clone();
+            }
+        }
+        for (key, endpoint) in &mut spec.endpoints {
+            if let Some(new) = new_spec.endpoints.get(key)
+                && same_definition(&*endpoint, new, &["hash"])
+            {
+                endpoint.hash = new.hash.clone();
+            }
+        }
+        for (key, webhook) in &mut spec.webhooks {
+            if let Some(new) = new_spec.webhooks.get(key)
+                && same_definition(&webhook.endpoint, &new.endpoint, &["hash"])
+            {
+                webhook.endpoint.hash = new.endpoint.hash.clone();
+            }
+        }
+    }
+
+    /// Apply a rule configuration: drop disabled rules and override severities.
+    /// Unknown rule ids are rejected so typos do not silently change nothing
+    pub fn apply_rules(diff: &mut SpecDiff, config: &DiffConfig) -> OasResult<()> {
+        let mut rules = HashMap::new();
+        for (id, rule) in &config.rules {
+            let category = BreakingChangeCategory::from_rule_id(id)
+                .ok_or_else(|| OasError::InvalidConfig(format!("unknown diff rule '{id}'")))?;
+            rules.insert(category.rule_id(), rule);
+        }
+
+        diff.breaking_changes.retain_mut(|change| {
+            let Some(rule) = rules.get(change.rule.as_str()) else {
+                return true;
+            };
+            if let Some(severity) = rule.severity {
+                change.severity = severity;
+            }
+            rule.enabled.unwrap_or(true)
+        });
+        Ok(())
+    }
+
     /// Compare schemas and return change type for each
     fn compare_schemas(
         old_spec: &ParsedSpec,
         new_spec: &ParsedSpec,
+        graph: Option<&DependencyGraph>,
     ) -> HashMap<String, ChangeType> {
         let mut changes = HashMap::new();
 
@@ -246,8 +1086,21 @@ impl DiffEngine {
             let new_schema = &new_spec.schemas[*name];
 
             if old_schema.hash != new_schema.hash {
-                let field_changes = Self::compare_schema_details(old_schema, new_schema);
-                changes.insert((*name).clone(), ChangeType::Modified(field_changes));
+                let usage = Self::schema_usage(graph, name);
+                let mut field_changes = Vec::new();
+                Self::structural_changes(
+                    &format!("#/components/schemas/{name}"),
+                    name,
+                    &old_schema.schema_type,
+                    &new_schema.schema_type,
+                    usage,
+                    &mut field_changes,
+                );
+                let details = Self::compare_schema_details(old_schema, new_schema, &field_changes);
+                changes.insert(
+                    (*name).clone(),
+                    ChangeType::Modified(details, field_changes),
+                );
             } else {
                 changes.insert((*name).clone(), ChangeType::Unchanged);
             }
@@ -256,9 +1109,43 @@ impl DiffEngine {
         changes
     }
 
+    /// Usage of a schema; without a graph, or for schemas no operation
+    /// reaches, assume both directions so nothing breaking is missed
+    fn schema_usage(graph: Option<&DependencyGraph>, name: &str) -> SchemaUsage {
+        graph
+            .map(|g| g.get_schema_usage(name))
+            .filter(|usage| !usage.is_unused())
+            .unwrap_or(SchemaUsage::BOTH)
+    }
+
     /// Compare two schemas in detail
-    fn compare_schema_details(old: &Schema, new: &Schema) -> Vec<String> {
-        let mut changes = Vec::new();
+    fn compare_schema_details(
+        old: &Schema,
+        new: &Schema,
+        field_changes: &[SchemaFieldChange],
+    ) -> Vec<String> {
+        let mut changes: Vec<String> = field_changes.iter().map(|c| c.message.clone()).collect();
+
+        // Compare validation constraints
+        let mut tightened = Vec::new();
+        Self::tightened_constraints(
+            "",
+            &old.name,
+            &old.schema_type,
+            &new.schema_type,
+            &mut tightened,
+        );
+        changes.extend(tightened.into_iter().map(|(_, message)| message));
+        changes.extend(Self::removed_discriminator_mappings(
+            &old.name,
+            &old.schema_type,
+            &new.schema_type,
+        ));
+        changes.extend(
+            Self::schema_deprecations(&old.name, &old.schema_type, &new.schema_type)
+                .into_iter()
+                .map(|d| d.message),
+        );
 
         // Compare refs
         let old_refs: HashSet<_> = old.refs.iter().collect();
@@ -280,6 +1167,420 @@ impl DiffEngine {
         changes
     }
 
+    /// Discriminator tag values that no longer select a variant
+    fn removed_discriminator_mappings(
+        name: &str,
+        old: &SchemaType,
+        new: &SchemaType,
+    ) -> Vec<String> {
+        let tags = |schema_type: &SchemaType| match schema_type {
+            SchemaType::OneOf {
+                variants,
+                discriminator: Some(discriminator),
+            }
+            | SchemaType::AnyOf {
+                variants,
+                discriminator: Some(discriminator),
+            } => discriminator.tags(variants),
+            _ => Vec::new(),
+        };
+
+        let new_tags: HashSet<String> = tags(new).into_iter().map(|(tag, _)| tag).collect();
+        tags(old)
+            .into_iter()
+            .filter(|(tag, _)| !new_tags.contains(tag))
+            .map(|(tag, schema)| {
+                format!("Discriminator mapping '{tag}' -> {schema} removed from '{name}'")
+            })
+            .collect()
+    }
+
+    /// Collect constraints that became stricter as (location, message) pairs,
+    /// recursing into object properties and array items
+    fn tightened_constraints(
+        location: &str,
+        path: &str,
+        old: &SchemaType,
+        new: &SchemaType,
+        out: &mut Vec<(String, String)>,
+    ) {
+        let (old, new) = match (old.base_shape(), new.base_shape()) {
+            (SchemaType::Nullable { inner: old }, SchemaType::Nullable { inner: new }) => {
+                (old.as_ref(), new.as_ref())
+            }
+            (SchemaType::Nullable { inner }, new) => {
+                out.push((
+                    location.to_string(),
+                    format!("'{path}' is no longer nullable"),
+                ));
+                (inner.as_ref(), new)
+            }
+            (old, SchemaType::Nullable { inner }) => (old, inner.as_ref()),
+            pair => pair,
+        };
+        let (old, new) = (old.base_shape(), new.base_shape());
+
+        if let (Some(old_c), Some(new_c)) = (old.constraints(), new.constraints()) {
+            let as_f64 = |v: Option<u64>| v.map(|v| v as f64);
+            let lower_bounds = [
+                ("minimum", old_c.minimum, new_c.minimum),
+                (
+                    "exclusiveMinimum",
+                    old_c.exclusive_minimum,
+                    new_c.exclusive_minimum,
+                ),
+                (
+                    "minLength",
+                    as_f64(old_c.min_length),
+                    as_f64(new_c.min_length),
+                ),
+                ("minItems", as_f64(old_c.min_items), as_f64(new_c.min_items)),
+            ];
+            let upper_bounds = [
+                ("maximum", old_c.maximum, new_c.maximum),
+                (
+                    "exclusiveMaximum",
+                    old_c.exclusive_maximum,
+                    new_c.exclusive_maximum,
+                ),
+                (
+                    "maxLength",
+                    as_f64(old_c.max_length),
+                    as_f64(new_c.max_length),
+                ),
+                ("maxItems", as_f64(old_c.max_items), as_f64(new_c.max_items)),
+            ];
+
+            let mut tightened = |keyword: &str, old_value: Option<String>, new_value: String| {
+                let message = match old_value {
+                    Some(old_value) => {
+                        format!(
+                            "Constraint tightened on '{path}': {keyword} {old_value} -> {new_value}"
+                        )
+                    }
+                    None => format!("Constraint added on '{path}': {keyword} {new_value}"),
+                };
+                out.push((location.to_string(), message));
+            };
+
+            for (keyword, old_value, new_value) in lower_bounds {
+                match (old_value, new_value) {
+                    (Some(o), Some(n)) if n > o => {
+                        tightened(keyword, Some(o.to_string()), n.to_string())
+                    }
+                    (None, Some(n)) => tightened(keyword, None, n.to_string()),
+                    _ => {}
+                }
+            }
+            for (keyword, old_value, new_value) in upper_bounds {
+                match (old_value, new_value) {
+                    (Some(o), Some(n)) if n < o => {
+                        tightened(keyword, Some(o.to_string()), n.to_string())
+                    }
+                    (None, Some(n)) => tightened(keyword, None, n.to_string()),
+                    _ => {}
+                }
+            }
+
+            if let Some(n) = new_c.multiple_of
+                && old_c.multiple_of != Some(n)
+            {
+                tightened(
+                    "multipleOf",
+                    old_c.multiple_of.map(|o| o.to_string()),
+                    n.to_string(),
+                );
+            }
+            if let Some(n) = &new_c.pattern
+                && old_c.pattern.as_ref() != Some(n)
+            {
+                tightened("pattern", old_c.pattern.clone(), n.clone());
+            }
+            if new_c.unique_items && !old_c.unique_items {
+                tightened("uniqueItems", None, "true".to_string());
+            }
+        }
+
+        match (old, new) {
+            (
+                SchemaType::Object {
+                    properties: old_props,
+                    ..
+                },
+                SchemaType::Object {
+                    properties: new_props,
+                    ..
+                },
+            ) => {
+                let mut names: Vec<_> = old_props
+                    .keys()
+                    .filter(|k| new_props.contains_key(*k))
+                    .collect();
+                names.sort();
+                for prop in names {
+                    Self::tightened_constraints(
+                        &format!("{location}/properties/{prop}"),
+                        &format!("{path}.{prop}"),
+                        &old_props[prop],
+                        &new_props[prop],
+                        out,
+                    );
+                }
+            }
+            (
+                SchemaType::Array {
+                    items: old_items, ..
+                },
+                SchemaType::Array {
+                    items: new_items, ..
+                },
+            ) => {
+                Self::tightened_constraints(
+                    &format!("{location}/items"),
+                    &format!("{path}[]"),
+                    old_items,
+                    new_items,
+                    out,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Recursively diff two schema shapes, reporting changes by JSON pointer.
+    /// Narrowed nullability and validation keywords are handled by
+    /// `tightened_constraints`
+    fn structural_changes(
+        pointer: &str,
+        path: &str,
+        old: &SchemaType,
+        new: &SchemaType,
+        usage: SchemaUsage,
+        out: &mut Vec<SchemaFieldChange>,
+    ) {
+        let unwrap = |schema_type: &SchemaType| match schema_type.base_shape() {
+            SchemaType::Nullable { inner } => inner.base_shape().clone(),
+            other => other.clone(),
+        };
+        let nullable = |schema_type: &SchemaType| {
+            matches!(
+                schema_type.base_shape(),
+                SchemaType::Nullable { .. } | SchemaType::Null
+            )
+        };
+        let became_nullable = !nullable(old) && nullable(new);
+        let (old, new) = (unwrap(old), unwrap(new));
+        let mut push = |kind: SchemaFieldChangeKind, message: String| {
+            out.push(SchemaFieldChange {
+                pointer: pointer.to_string(),
+                kind,
+                message,
+                breaking: kind.breaking_for(usage),
+            })
+        };
+
+        if became_nullable {
+            push(
+                SchemaFieldChangeKind::BecameNullable,
+                format!("'{path}' became nullable"),
+            );
+        }
+
+        if type_kind(&old) != type_kind(&new) {
+            push(
+                SchemaFieldChangeKind::TypeChanged,
+                format!("Type of '{path}' changed: {old} -> {new}"),
+            );
+            return;
+        }
+
+        match (&old, &new) {
+            (
+                SchemaType::String {
+                    format: old_format,
+                    enum_values: old_values,
+                    ..
+                },
+                SchemaType::String {
+                    format: new_format,
+                    enum_values: new_values,
+                    ..
+                },
+            ) => {
+                if old_format != new_format {
+                    push(
+                        SchemaFieldChangeKind::FormatChanged,
+                        format!("Format of '{path}' changed: {old} -> {new}"),
+                    );
+                }
+                if let (Some(old_values), Some(new_values)) = (old_values, new_values) {
+                    for removed in old_values.iter().filter(|v| !new_values.contains(v)) {
+                        push(
+                            SchemaFieldChangeKind::EnumValueRemoved,
+                            format!("Enum value '{removed}' removed from '{path}'"),
+                        );
+                    }
+                    for added in new_values.iter().filter(|v| !old_values.contains(v)) {
+                        push(
+                            SchemaFieldChangeKind::EnumValueAdded,
+                            format!("Enum value '{added}' added to '{path}'"),
+                        );
+                    }
+                }
+            }
+            (
+                SchemaType::Number {
+                    format: old_format, ..
+                },
+                SchemaType::Number {
+                    format: new_format, ..
+                },
+            )
+            | (
+                SchemaType::Integer {
+                    format: old_format, ..
+                },
+                SchemaType::Integer {
+                    format: new_format, ..
+                },
+            ) if old_format != new_format => {
+                push(
+                    SchemaFieldChangeKind::FormatChanged,
+                    format!("Format of '{path}' changed: {old} -> {new}"),
+                );
+            }
+            (SchemaType::Ref { reference: o }, SchemaType::Ref { reference: n }) if o != n => {
+                push(
+                    SchemaFieldChangeKind::TypeChanged,
+                    format!("Type of '{path}' changed: {o} -> {n}"),
+                );
+            }
+            (SchemaType::Const { value: o }, SchemaType::Const { value: n }) if o != n => {
+                push(
+                    SchemaFieldChangeKind::TypeChanged,
+                    format!("Type of '{path}' changed: {old} -> {new}"),
+                );
+            }
+            (
+                SchemaType::Object {
+                    properties: old_props,
+                    required: old_required,
+                    ..
+                },
+                SchemaType::Object {
+                    properties: new_props,
+                    required: new_required,
+                    ..
+                },
+            ) => {
+                let mut names: Vec<_> = old_props.keys().chain(new_props.keys()).collect();
+                names.sort();
+                names.dedup();
+
+                for prop in names {
+                    let prop_pointer = format!("{pointer}/properties/{prop}");
+                    let prop_path = format!("{path}.{prop}");
+                    let was_required = old_required.contains(prop);
+                    let is_required = new_required.contains(prop);
+                    let mut push = |kind: SchemaFieldChangeKind, message: String| {
+                        out.push(SchemaFieldChange {
+                            pointer: prop_pointer.clone(),
+                            kind,
+                            message,
+                            breaking: kind.breaking_for(usage),
+                        })
+                    };
+
+                    match (old_props.get(prop), new_props.get(prop)) {
+                        (Some(old_prop), None) => match old_prop.constraints() {
+                            Some(c) if c.deprecated => push(
+                                SchemaFieldChangeKind::DeprecatedPropertyRemoved,
+                                format!(
+                                    "Deprecated property '{prop_path}' removed{}",
+                                    dates_suffix(&c.deprecation_dates)
+                                ),
+                            ),
+                            _ => push(
+                                SchemaFieldChangeKind::PropertyRemoved,
+                                format!("Property '{prop_path}' removed"),
+                            ),
+                        },
+                        (None, Some(_)) if is_required => push(
+                            SchemaFieldChangeKind::RequiredPropertyAdded,
+                            format!("Required property '{prop_path}' added"),
+                        ),
+                        (None, Some(_)) => push(
+                            SchemaFieldChangeKind::PropertyAdded,
+                            format!("Property '{prop_path}' added"),
+                        ),
+                        (Some(old_prop), Some(new_prop)) => {
+                            if is_required && !was_required {
+                                push(
+                                    SchemaFieldChangeKind::BecameRequired,
+                                    format!("Property '{prop_path}' became required"),
+                                );
+                            } else if was_required && !is_required {
+                                push(
+                                    SchemaFieldChangeKind::BecameOptional,
+                                    format!("Property '{prop_path}' became optional"),
+                                );
+                            }
+                            Self::structural_changes(
+                                &prop_pointer,
+                                &prop_path,
+                                old_prop,
+                                new_prop,
+                                usage,
+                                out,
+                            );
+                        }
+                        (None, None) => {}
+                    }
+                }
+            }
+            (
+                SchemaType::Array {
+                    items: old_items, ..
+                },
+                SchemaType::Array {
+                    items: new_items, ..
+                },
+            ) => Self::structural_changes(
+                &format!("{pointer}/items"),
+                &format!("{path}[]"),
+                old_items,
+                new_items,
+                usage,
+                out,
+            ),
+            (
+                SchemaType::Map {
+                    values: old_values, ..
+                },
+                SchemaType::Map {
+                    values: new_values, ..
+                },
+            ) => Self::structural_changes(
+                &format!("{pointer}/additionalProperties"),
+                &format!("{path}{{}}"),
+                old_values,
+                new_values,
+                usage,
+                out,
+            ),
+            (old_other, new_other) => {
+                // Composite shapes: compare rendered form only
+                let (o, n) = (old_other.to_string(), new_other.to_string());
+                if o != n {
+                    push(
+                        SchemaFieldChangeKind::TypeChanged,
+                        format!("Type of '{path}' changed: {o} -> {n}"),
+                    );
+                }
+            }
+        }
+    }
+
     /// Compare two endpoints
     fn compare_endpoints(old: &Endpoint, new: &Endpoint) -> Vec<String> {
         let mut changes = Vec::new();
@@ -289,21 +1590,21 @@ impl DiffEngine {
             return changes;
         }
 
-        // Compare parameters
-        let old_params: HashMap<_, _> = old.parameters.iter().map(|p| (&p.name, p)).collect();
-        let new_params: HashMap<_, _> = new.parameters.iter().map(|p| (&p.name, p)).collect();
-
-        for (name, param) in &new_params {
-            if !old_params.contains_key(name) && param.required {
-                changes.push(format!("Added required parameter: {name}"));
-            }
+        changes.extend(
+            Self::endpoint_deprecations(old, new)
+                .into_iter()
+                .map(|d| d.message),
+        );
+        if old.deprecated && !new.deprecated {
+            changes.push("Operation is no longer deprecated".to_string());
         }
 
-        for name in old_params.keys() {
-            if !new_params.contains_key(name) {
-                changes.push(format!("Removed parameter: {name}"));
-            }
-        }
+        // Compare parameters
+        changes.extend(
+            Self::parameter_changes(old, new)
+                .into_iter()
+                .map(|change| change.message),
+        );
 
         // Compare request body
         match (&old.request_body, &new.request_body) {
@@ -313,6 +1614,12 @@ impl DiffEngine {
                 if old_body.schema_ref != new_body.schema_ref {
                     changes.push("Request body schema changed".to_string());
                 }
+                Self::compare_media_types(
+                    "Request body",
+                    &old_body.content,
+                    &new_body.content,
+                    &mut changes,
+                );
             }
             _ => {}
         }
@@ -329,6 +1636,40 @@ impl DiffEngine {
             changes.push(format!("Removed response: {status}"));
         }
 
+        let mut common: Vec<_> = old_responses.intersection(&new_responses).collect();
+        common.sort();
+        for status in common {
+            let (old_resp, new_resp) = (&old.responses[*status], &new.responses[*status]);
+            let location = format!("Response {status}");
+            Self::compare_media_types(
+                &location,
+                &old_resp.content,
+                &new_resp.content,
+                &mut changes,
+            );
+            Self::compare_response_headers(
+                &location,
+                &old_resp.headers,
+                &new_resp.headers,
+                &mut changes,
+            );
+
+            for link in &old_resp.links {
+                match new_resp.links.iter().find(|l| l.name == link.name) {
+                    None => changes.push(format!("{location}: removed link {}", link.name)),
+                    Some(new_link) if new_link != link => {
+                        changes.push(format!("{location}: link {} changed", link.name))
+                    }
+                    _ => {}
+                }
+            }
+            for link in &new_resp.links {
+                if !old_resp.links.iter().any(|l| l.name == link.name) {
+                    changes.push(format!("{location}: added link {}", link.name));
+                }
+            }
+        }
+
         // If no specific changes but hash different
         if changes.is_empty() {
             changes.push("Endpoint definition changed".to_string());
@@ -336,19 +1677,1429 @@ impl DiffEngine {
 
         changes
     }
+
+    /// Diff parameters keyed by (name, location); header names are case-insensitive.
+    /// A parameter that disappears from one location and appears in another is a move
+    fn parameter_changes(old: &Endpoint, new: &Endpoint) -> Vec<ParameterChange> {
+        let key = |p: &Parameter| {
+            let name = match p.location {
+                ParameterLocation::Header => p.name.to_ascii_lowercase(),
+                _ => p.name.clone(),
+            };
+            (name, p.location.to_string())
+        };
+        let mut old_params: BTreeMap<_, _> = old.parameters.iter().map(|p| (key(p), p)).collect();
+        let mut new_params: BTreeMap<_, _> = new.parameters.iter().map(|p| (key(p), p)).collect();
+        let mut changes = Vec::new();
+
+        let common: Vec<_> = old_params
+            .keys()
+            .filter(|k| new_params.contains_key(*k))
+            .cloned()
+            .collect();
+        for k in common {
+            let (o, n) = (
+                old_params.remove(&k).unwrap(),
+                new_params.remove(&k).unwrap(),
+            );
+            let label = format!("Parameter {} ({})", o.name, o.location);
+            let location = format!("{}/{}", o.location, o.name);
+            let mut push = |message: String, category: Option<BreakingChangeCategory>| {
+                changes.push(ParameterChange {
+                    location: location.clone(),
+                    message,
+                    category,
+                })
+            };
+
+            let type_of = |p: &Parameter| {
+                p.schema_ref
+                    .clone()
+                    .or_else(|| p.schema_type.clone())
+                    .unwrap_or_else(|| "unspecified".to_string())
+            };
+            if type_of(o) != type_of(n) {
+                push(
+                    format!("{label} type changed: {} -> {}", type_of(o), type_of(n)),
+                    Some(BreakingChangeCategory::ParameterTypeChanged),
+                );
+            }
+            if o.format != n.format {
+                push(
+                    format!(
+                        "{label} format changed: {} -> {}",
+                        o.format.as_deref().unwrap_or("none"),
+                        n.format.as_deref().unwrap_or("none")
+                    ),
+                    Some(BreakingChangeCategory::ParameterFormatChanged),
+                );
+            }
+            if n.required && !o.required {
+                push(
+                    format!("{label} is now required"),
+                    Some(BreakingChangeCategory::ParameterBecameRequired),
+                );
+            } else if o.required && !n.required {
+                push(format!("{label} is no longer required"), None);
+            }
+            if o.style != n.style {
+                push(
+                    format!("{label} style changed: {} -> {}", o.style, n.style),
+                    Some(BreakingChangeCategory::ParameterStyleChanged),
+                );
+            }
+            if o.explode != n.explode {
+                push(
+                    format!("{label} explode changed: {} -> {}", o.explode, n.explode),
+                    Some(BreakingChangeCategory::ParameterStyleChanged),
+                );
+            }
+        }
+
+        for ((name, old_location), param) in old_params {
+            let moved_to = new_params
+                .keys()
+                .find(|(new_name, _)| *new_name == name)
+                .cloned();
+            match moved_to.and_then(|k| new_params.remove_entry(&k)) {
+                Some(((_, new_location), _)) => changes.push(ParameterChange {
+                    location: format!("{old_location}/{}", param.name),
+                    message: format!(
+                        "Parameter {} moved from {old_location} to {new_location}",
+                        param.name
+                    ),
+                    category: Some(BreakingChangeCategory::ParameterLocationChanged),
+                }),
+                None => {
+                    let (message, category) = if param.deprecated {
+                        (
+                            format!("Removed deprecated parameter: {}", param.name),
+                            BreakingChangeCategory::DeprecatedParameterRemoved,
+                        )
+                    } else {
+                        (
+                            format!("Removed parameter: {}", param.name),
+                            BreakingChangeCategory::ParameterRemoved,
+                        )
+                    };
+                    changes.push(ParameterChange {
+                        location: format!("{old_location}/{}", param.name),
+                        message,
+                        category: Some(category),
+                    })
+                }
+            }
+        }
+
+        for ((_, location), param) in new_params {
+            let (message, category) = if param.required {
+                (
+                    format!("Added required parameter: {}", param.name),
+                    Some(BreakingChangeCategory::ParameterAdded),
+                )
+            } else {
+                (format!("Added parameter: {}", param.name), None)
+            };
+            changes.push(ParameterChange {
+                location: format!("{location}/{}", param.name),
+                message,
+                category,
+            });
+        }
+
+        changes
+    }
+
+    /// Operation and parameters the new endpoint deprecates (or re-dates)
+    fn endpoint_deprecations(old: &Endpoint, new: &Endpoint) -> Vec<Deprecation> {
+        let location = format!("{} {}", new.method, new.path);
+        let mut deprecations: Vec<Deprecation> = deprecation(
+            DeprecationTarget::Operation,
+            &location,
+            "Operation",
+            (old.deprecated, &old.deprecation_dates),
+            (new.deprecated, &new.deprecation_dates),
+        )
+        .into_iter()
+        .collect();
+
+        for n in &new.parameters {
+            let Some(o) = old.parameters.iter().find(|o| {
+                o.location == n.location
+                    && match n.location {
+                        ParameterLocation::Header => o.name.eq_ignore_ascii_case(&n.name),
+                        _ => o.name == n.name,
+                    }
+            }) else {
+                continue;
+            };
+            deprecations.extend(deprecation(
+                DeprecationTarget::Parameter,
+                &format!("{location} -> parameters/{}/{}", n.location, n.name),
+                &format!("Parameter {} ({})", n.name, n.location),
+                (o.deprecated, &o.deprecation_dates),
+                (n.deprecated, &n.deprecation_dates),
+            ));
+        }
+
+        deprecations
+    }
+
+    /// Schema and properties (at any depth) the new definition deprecates (or re-dates)
+    fn schema_deprecations(name: &str, old: &SchemaType, new: &SchemaType) -> Vec<Deprecation> {
+        let root = format!("#/components/schemas/{name}");
+        let mut old_nodes = BTreeMap::new();
+        let mut new_nodes = BTreeMap::new();
+        schema_nodes(root.clone(), name.to_string(), old, &mut old_nodes);
+        schema_nodes(root.clone(), name.to_string(), new, &mut new_nodes);
+
+        new_nodes
+            .into_iter()
+            .filter_map(|(pointer, (path, n))| {
+                let (_, o) = old_nodes.get(&pointer)?;
+                let (target, label) = if pointer == root {
+                    (DeprecationTarget::Schema, format!("Schema '{path}'"))
+                } else {
+                    (DeprecationTarget::Property, format!("Property '{path}'"))
+                };
+                deprecation(
+                    target,
+                    &pointer,
+                    &label,
+                    (o.deprecated, &o.deprecation_dates),
+                    (n.deprecated, &n.deprecation_dates),
+                )
+            })
+            .collect()
+    }
+
+    /// Response compatibility per status code and media type. Removing or
+    /// changing a success response breaks consumers; error responses only
+    /// break them when headers they may read disappear
+    fn response_breaking_changes(old: &Endpoint, new: &Endpoint) -> Vec<BreakingChange> {
+        let mut breaking = Vec::new();
+        let mut statuses: Vec<_> = old.responses.keys().collect();
+        statuses.sort();
+
+        for status in statuses {
+            let old_resp = &old.responses[status];
+            let location = format!("{} {} -> {status}", old.method, old.path);
+            let success = status.starts_with('2');
+
+            let Some(new_resp) = new.responses.get(status) else {
+                if success {
+                    breaking.push(BreakingChange::new(
+                        BreakingChangeCategory::ResponseRemoved,
+                        format!("Success response {status} was removed"),
+                        location,
+                    ));
+                }
+                continue;
+            };
+
+            for header in &old_resp.headers {
+                if !new_resp
+                    .headers
+                    .iter()
+                    .any(|h| h.name.eq_ignore_ascii_case(&header.name))
+                {
+                    breaking.push(BreakingChange::new(
+                        BreakingChangeCategory::ResponseHeaderRemoved,
+                        format!("Response header '{}' was removed", header.name),
+                        format!("{location}/headers/{}", header.name),
+                    ));
+                }
+            }
+
+            if !success {
+                continue;
+            }
+
+            for media in &old_resp.content {
+                let media_location = format!("{location}/content/{}", media.content_type);
+                let Some(new_media) = new_resp
+                    .content
+                    .iter()
+                    .find(|m| m.content_type == media.content_type)
+                else {
+                    breaking.push(BreakingChange::new(
+                        BreakingChangeCategory::MediaTypeRemoved,
+                        format!("Response media type '{}' was removed", media.content_type),
+                        media_location,
+                    ));
+                    continue;
+                };
+
+                match (&media.schema, &new_media.schema) {
+                    // Both inline: analyse the shapes as response schemas
+                    (Some(old_schema), Some(new_schema)) => {
+                        let mut field_changes = Vec::new();
+                        Self::structural_changes(
+                            &format!("{media_location}/schema"),
+                            &format!("{status} {}", media.content_type),
+                            old_schema,
+                            new_schema,
+                            SchemaUsage::RESPONSE,
+                            &mut field_changes,
+                        );
+                        breaking.extend(field_changes.into_iter().filter(|c| c.breaking).map(
+                            |c| {
+                                BreakingChange::new(
+                                    BreakingChangeCategory::ResponseTypeChanged,
+                                    c.message,
+                                    c.pointer,
+                                )
+                            },
+                        ));
+                    }
+                    // Documenting a schema where there was none is not a change in type
+                    _ if (media.schema_ref.is_some() || media.schema.is_some())
+                        && (media.schema_ref != new_media.schema_ref
+                            || media.schema.is_some() != new_media.schema.is_some()) =>
+                    {
+                        let describe = |m: &MediaType| {
+                            m.schema_ref.clone().unwrap_or_else(|| match &m.schema {
+                                Some(schema) => schema.to_string(),
+                                None => "none".to_string(),
+                            })
+                        };
+                        breaking.push(BreakingChange::new(
+                            BreakingChangeCategory::ResponseTypeChanged,
+                            format!(
+                                "Response {status} {} schema changed: {} -> {}",
+                                media.content_type,
+                                describe(media),
+                                describe(new_media)
+                            ),
+                            format!("{media_location}/schema"),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        breaking
+    }
+
+    /// Compare per-media-type schemas of a request body or response
+    fn compare_media_types(
+        location: &str,
+        old: &[MediaType],
+        new: &[MediaType],
+        changes: &mut Vec<String>,
+    ) {
+        for media in old {
+            match new.iter().find(|m| m.content_type == media.content_type) {
+                None => changes.push(format!(
+                    "{location}: removed media type {}",
+                    media.content_type
+                )),
+                Some(new_media)
+                    if new_media.schema_ref != media.schema_ref
+                        || new_media.schema != media.schema =>
+                {
+                    changes.push(format!(
+                        "{location}: {} schema changed: {} -> {}",
+                        media.content_type,
+                        media.schema_ref.as_deref().unwrap_or("inline"),
+                        new_media.schema_ref.as_deref().unwrap_or("inline"),
+                    ))
+                }
+                _ => {}
+            }
+        }
+        for media in new {
+            if !old.iter().any(|m| m.content_type == media.content_type) {
+                changes.push(format!(
+                    "{location}: added media type {}",
+                    media.content_type
+                ));
+            }
+        }
+    }
+
+    /// Compare response headers (names are case-insensitive)
+    fn compare_response_headers(
+        location: &str,
+        old: &[ResponseHeader],
+        new: &[ResponseHeader],
+        changes: &mut Vec<String>,
+    ) {
+        let find = |headers: &'_ [ResponseHeader], name: &str| {
+            headers
+                .iter()
+                .find(|h| h.name.eq_ignore_ascii_case(name))
+                .cloned()
+        };
+
+        for header in old {
+            match find(new, &header.name) {
+                None => changes.push(format!("{location}: removed header {}", header.name)),
+                Some(new_header) => {
+                    if header.required && !new_header.required {
+                        changes.push(format!(
+                            "{location}: header {} is no longer required",
+                            header.name
+                        ));
+                    }
+                    if header.schema_type != new_header.schema_type
+                        || header.schema_ref != new_header.schema_ref
+                    {
+                        changes.push(format!("{location}: header {} type changed", header.name));
+                    }
+                }
+            }
+        }
+        for header in new {
+            if find(old, &header.name).is_none() {
+                changes.push(format!("{location}: added header {}", header.name));
+            }
+        }
+    }
+}
+
+/// One parameter difference; `category` is set when it breaks consumers
+struct ParameterChange {
+    /// `{in}/{name}` of the old parameter (or the new one, if added)
+    location: String,
+    message: String,
+    category: Option<BreakingChangeCategory>,
+}
+
+/// Deprecation `new` announces relative to `old`: newly deprecated, or still
+/// deprecated with different dates
+fn deprecation(
+    target: DeprecationTarget,
+    location: &str,
+    label: &str,
+    (was_deprecated, old_dates): (bool, &DeprecationDates),
+    (is_deprecated, new_dates): (bool, &DeprecationDates),
+) -> Option<Deprecation> {
+    let message = match (was_deprecated, is_deprecated) {
+        (false, true) => format!("{label} deprecated{}", dates_suffix(new_dates)),
+        (true, true) if old_dates != new_dates => format!(
+            "{label} deprecation dates changed: {} -> {}",
+            describe_dates(old_dates).as_deref().unwrap_or("none"),
+            describe_dates(new_dates).as_deref().unwrap_or("none")
+        ),
+        _ => return None,
+    };
+    Some(Deprecation {
+        target,
+        location: location.to_string(),
+        message,
+        dates: new_dates.clone(),
+    })
+}
+
+/// `deprecated at …, sunset …`, or `None` when no dates are announced
+fn describe_dates(dates: &DeprecationDates) -> Option<String> {
+    let parts: Vec<String> = [
+        ("deprecated at", &dates.deprecated_at),
+        ("sunset", &dates.sunset),
+    ]
+    .into_iter()
+    .filter_map(|(label, date)| Some(format!("{label} {}", date.as_ref()?)))
+    .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// ` (deprecated at …, sunset …)` for appending to a message
+fn dates_suffix(dates: &DeprecationDates) -> String {
+    describe_dates(dates)
+        .map(|text| format!(" ({text})"))
+        .unwrap_or_default()
+}
+
+/// Constraints of a schema and its nested properties, array items and map
+/// values, by JSON pointer, with their dotted path
+fn schema_nodes<'a>(
+    pointer: String,
+    path: String,
+    schema_type: &'a SchemaType,
+    out: &mut BTreeMap<String, (String, &'a SchemaConstraints)>,
+) {
+    if let SchemaType::Nullable { inner } = schema_type {
+        return schema_nodes(pointer, path, inner, out);
+    }
+    match schema_type.base_shape() {
+        SchemaType::Object { properties, .. } => {
+            for (name, property) in properties {
+                schema_nodes(
+                    format!("{pointer}/properties/{name}"),
+                    format!("{path}.{name}"),
+                    property,
+                    out,
+                );
+            }
+        }
+        SchemaType::Array { items, .. } => {
+            schema_nodes(format!("{pointer}/items"), format!("{path}[]"), items, out)
+        }
+        SchemaType::Map { values, .. } => schema_nodes(
+            format!("{pointer}/additionalProperties"),
+            format!("{path}{{}}"),
+            values,
+            out,
+        ),
+        _ => {}
+    }
+    if let Some(constraints) = schema_type.constraints() {
+        out.insert(pointer, (path, constraints));
+    }
+}
+
+/// Whether two items serialize identically apart from the `ignored` fields
+fn same_definition<T: Serialize>(old: &T, new: &T, ignored: &[&str]) -> bool {
+    let value = |item: &T| {
+        let mut value = serde_json::to_value(item).ok()?;
+        if let Some(object) = value.as_object_mut() {
+            for field in ignored {
+                object.remove(*field);
+            }
+        }
+        Some(value)
+    };
+    value(old).is_some_and(|old| Some(old) == value(new))
+}
+
+/// Jaccard index of two feature sets; fewer than two shared features is no evidence
+fn similarity(old: &HashSet<String>, new: &HashSet<String>) -> f64 {
+    let shared = old.intersection(new).count();
+    if shared < 2 {
+        return 0.0;
+    }
+    shared as f64 / old.union(new).count() as f64
+}
+
+/// Properties (with types and requiredness), enum values or variants of a schema
+fn schema_features(schema_type: &SchemaType) -> HashSet<String> {
+    match schema_type {
+        SchemaType::Nullable { inner } => schema_features(inner),
+        SchemaType::Conditional {
+            base: Some(base), ..
+        } => schema_features(base),
+        SchemaType::Object {
+            properties,
+            required,
+            ..
+        } => properties
+            .iter()
+            .map(|(name, schema)| format!("property:{name}:{schema}"))
+            .chain(required.iter().map(|name| format!("required:{name}")))
+            .collect(),
+        SchemaType::String {
+            enum_values: Some(values),
+            ..
+        } => values.iter().map(|v| format!("enum:{v}")).collect(),
+        SchemaType::OneOf { variants, .. }
+        | SchemaType::AnyOf { variants, .. }
+        | SchemaType::AllOf { variants } => {
+            variants.iter().map(|v| format!("variant:{v}")).collect()
+        }
+        _ => HashSet::new(),
+    }
+}
+
+/// Parameters, request body and response schemas of an operation
+fn endpoint_features(endpoint: &Endpoint) -> HashSet<String> {
+    let media = |m: &MediaType| {
+        let schema = m
+            .schema_ref
+            .clone()
+            .or_else(|| m.schema.as_ref().map(|s| s.to_string()))
+            .unwrap_or_default();
+        format!("{}:{schema}", m.content_type)
+    };
+
+    let mut features: HashSet<String> = endpoint
+        .parameters
+        .iter()
+        .map(|p| {
+            let schema = p.schema_ref.as_ref().or(p.schema_type.as_ref());
+            format!("param:{}:{}:{schema:?}", p.location, p.name)
+        })
+        .collect();
+    if let Some(body) = &endpoint.request_body {
+        features.extend(body.content.iter().map(|m| format!("body:{}", media(m))));
+    }
+    for (status, response) in &endpoint.responses {
+        features.insert(format!("response:{status}"));
+        features.extend(
+            response
+                .content
+                .iter()
+                .map(|m| format!("response:{status}:{}", media(m))),
+        );
+    }
+    features
+}
+
+fn last_static_segment(path: &str) -> Option<&str> {
+    path.split('/')
+        .rfind(|s| !s.is_empty() && !s.starts_with('{'))
+}
+
+/// Shape name used to decide whether a schema's type changed outright
+fn type_kind(schema_type: &SchemaType) -> &'static str {
+    match schema_type {
+        SchemaType::String { .. } => "string",
+        SchemaType::Number { .. } => "number",
+        SchemaType::Integer { .. } => "integer",
+        SchemaType::Boolean { .. } => "boolean",
+        SchemaType::Null => "null",
+        SchemaType::Array { .. } => "array",
+        SchemaType::Tuple { .. } => "tuple",
+        SchemaType::Object { .. } => "object",
+        SchemaType::Map { .. } => "map",
+        SchemaType::Ref { .. } => "ref",
+        SchemaType::Nullable { inner } => type_kind(inner),
+        SchemaType::Const { .. } => "const",
+        SchemaType::OneOf { .. } => "oneOf",
+        SchemaType::AnyOf { .. } => "anyOf",
+        SchemaType::AllOf { .. } => "allOf",
+        SchemaType::Conditional {
+            base: Some(base), ..
+        } => type_kind(base),
+        SchemaType::Conditional { .. } => "conditional",
+        SchemaType::Unknown => "unknown",
+    }
 }
 
 enum ChangeType {
     Added,
-    Modified(Vec<String>),
+    Modified(Vec<String>, Vec<SchemaFieldChange>),
     Removed,
     Unchanged,
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
+    use super::*;
+    use crate::services::{GraphBuilder, OpenApiParser};
+
+    /// Parse a 3.0 document from its `paths` and `components.schemas`
+    pub(crate) fn spec(paths: serde_json::Value, schemas: serde_json::Value) -> ParsedSpec {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": paths,
+            "components": { "schemas": schemas }
+        });
+        OpenApiParser::parse_value(spec, "pets.json").unwrap()
+    }
+
+    #[test]
+    fn test_tightened_constraints_are_breaking() {
+        let user_spec = |age_minimum: u64, name_max_length: u64| {
+            spec(
+                serde_json::json!({}),
+                serde_json::json!({ "User": {
+                    "type": "object",
+                    "properties": {
+                        "age": { "type": "integer", "minimum": age_minimum },
+                        "name": { "type": "string", "maxLength": name_max_length, "nullable": true }
+                    }
+                } }),
+            )
+        };
+        let diff = DiffEngine::diff(&user_spec(0, 100), &user_spec(18, 50), None);
+
+        let locations: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .filter(|c| matches!(c.category, BreakingChangeCategory::ConstraintTightened))
+            .map(|c| c.location.as_str())
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                "#/components/schemas/User/properties/age",
+                "#/components/schemas/User/properties/name",
+            ]
+        );
+        assert!(
+            diff.modified_schemas[0]
+                .changes
+                .contains(&"Constraint tightened on 'User.age': minimum 0 -> 18".to_string())
+        );
+
+        // Relaxing is not breaking
+        let relaxed = DiffEngine::diff(&user_spec(18, 50), &user_spec(0, 100), None);
+        assert!(relaxed.breaking_changes.is_empty());
+    }
+
+    #[test]
+    fn test_structural_schema_diff() {
+        let schema_spec = |user: serde_json::Value| {
+            spec(serde_json::json!({}), serde_json::json!({ "User": user }))
+        };
+        let old = schema_spec(serde_json::json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {
+                "id": { "type": "integer" },
+                "email": { "type": "string" },
+                "created": { "type": "string", "format": "date" },
+                "status": { "type": "string", "enum": ["active", "banned"] },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        }));
+        let new = schema_spec(serde_json::json!({
+            "type": "object",
+            "required": ["id", "created", "nickname"],
+            "properties": {
+                "id": { "type": "string" },
+                "created": { "type": "string", "format": "date-time" },
+                "status": { "type": "string", "enum": ["active", "suspended"] },
+                "tags": { "type": "array", "items": { "type": "integer" } },
+                "nickname": { "type": "string" },
+                "bio": { "type": "string" }
+            }
+        }));
+
+        let diff = DiffEngine::diff(&old, &new, None);
+        let changes: Vec<_> = diff.modified_schemas[0]
+            .field_changes
+            .iter()
+            .map(|c| (c.pointer.as_str(), c.kind, c.breaking))
+            .collect();
+
+        let base = "#/components/schemas/User/properties";
+        use SchemaFieldChangeKind::*;
+        assert_eq!(
+            changes,
+            vec![
+                (format!("{base}/bio").as_str(), PropertyAdded, false),
+                (format!("{base}/created").as_str(), BecameRequired, true),
+                (format!("{base}/created").as_str(), FormatChanged, true),
+                (format!("{base}/email").as_str(), PropertyRemoved, true),
+                (format!("{base}/id").as_str(), TypeChanged, true),
+                (
+                    format!("{base}/nickname").as_str(),
+                    RequiredPropertyAdded,
+                    true
+                ),
+                (format!("{base}/status").as_str(), EnumValueRemoved, true),
+                // Without a graph the schema may be a response, so new values break
+                (format!("{base}/status").as_str(), EnumValueAdded, true),
+                (format!("{base}/tags/items").as_str(), TypeChanged, true),
+            ]
+        );
+
+        assert!(diff.breaking_changes.iter().any(|c| {
+            matches!(c.category, BreakingChangeCategory::SchemaFieldRemoved)
+                && c.location == format!("{base}/email")
+        }));
+        assert_eq!(
+            diff.breaking_changes
+                .iter()
+                .filter(|c| matches!(c.category, BreakingChangeCategory::SchemaFieldTypeChanged))
+                .count(),
+            3
+        );
+        assert!(
+            diff.modified_schemas[0]
+                .changes
+                .contains(&"Type of 'User.id' changed: integer -> string".to_string())
+        );
+    }
+
+    /// `NewPet` is only sent in a request body and `Pet` only received in a response
+    fn direction_spec(pet: serde_json::Value) -> ParsedSpec {
+        spec(
+            serde_json::json!({
+                "/pets": {
+                    "post": {
+                        "requestBody": {
+                            "content": {
+                                "application/json": { "schema": { "$ref": "#/components/schemas/NewPet" } }
+                            }
+                        },
+                        "responses": {
+                            "201": {
+                                "description": "created",
+                                "content": {
+                                    "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                                }
+                            }
+                        }
+                    }
+                }
+            }),
+            serde_json::json!({ "NewPet": pet.clone(), "Pet": pet }),
+        )
+    }
+
+    #[test]
+    fn test_direction_aware_classification() {
+        let old = direction_spec(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "maxLength": 100 },
+                "kind": { "type": "string", "enum": ["cat", "dog"] }
+            }
+        }));
+        let new = direction_spec(serde_json::json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string", "maxLength": 50 },
+                "kind": { "type": "string", "enum": ["cat", "bird"] }
+            }
+        }));
+
+        let graph = crate::services::GraphBuilder::build(&new);
+        let diff = DiffEngine::diff(&old, &new, Some(&graph));
+
+        let breaking = |schema: &str| -> Vec<String> {
+            diff.breaking_changes
+                .iter()
+                .filter(|c| {
+                    c.location
+                        .starts_with(&format!("#/components/schemas/{schema}/"))
+                })
+                .map(|c| format!("{:?}", c.category))
+                .collect()
+        };
+
+        // Sent by the consumer: stricter input breaks, new enum values do not
+        assert_eq!(
+            breaking("NewPet"),
+            vec![
+                "EnumValueRemoved",
+                "SchemaFieldRequired",
+                "ConstraintTightened"
+            ]
+        );
+        // Received by the consumer: unexpected enum values break, stricter output does not
+        assert_eq!(breaking("Pet"), vec!["EnumValueAdded"]);
+
+        let pet = diff
+            .modified_schemas
+            .iter()
+            .find(|s| s.name == "Pet")
+            .unwrap();
+        assert_eq!(pet.usage, SchemaUsage::RESPONSE);
+    }
+
+    #[test]
+    fn test_became_nullable() {
+        let old = direction_spec(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "nickname": { "type": "string" }
+            }
+        }));
+        let new = direction_spec(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": ["string", "null"] },
+                "nickname": { "type": "string", "nullable": true }
+            }
+        }));
+
+        let graph = crate::services::GraphBuilder::build(&new);
+        let diff = DiffEngine::diff(&old, &new, Some(&graph));
+
+        let nullable = |schema: &str| -> Vec<(String, bool)> {
+            diff.modified_schemas
+                .iter()
+                .find(|s| s.name == schema)
+                .unwrap()
+                .field_changes
+                .iter()
+                .filter(|c| c.kind == SchemaFieldChangeKind::BecameNullable)
+                .map(|c| (c.pointer.clone(), c.breaking))
+                .collect()
+        };
+        // Consumers receiving the value may now get null; senders are unaffected
+        assert_eq!(
+            nullable("Pet"),
+            vec![
+                ("#/components/schemas/Pet/properties/name".to_string(), true),
+                (
+                    "#/components/schemas/Pet/properties/nickname".to_string(),
+                    true
+                ),
+            ]
+        );
+        assert!(nullable("NewPet").iter().all(|(_, breaking)| !breaking));
+        assert_eq!(nullable("NewPet").len(), 2);
+
+        let breaking: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .map(|c| (format!("{:?}", c.category), c.location.as_str()))
+            .collect();
+        assert_eq!(
+            breaking,
+            vec![
+                (
+                    "SchemaFieldNullable".to_string(),
+                    "#/components/schemas/Pet/properties/name"
+                ),
+                (
+                    "SchemaFieldNullable".to_string(),
+                    "#/components/schemas/Pet/properties/nickname"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parameter_changes() {
+        let params_spec = |parameters: serde_json::Value| {
+            spec(
+                serde_json::json!({
+                    "/search": { "get": { "parameters": parameters, "responses": {} } }
+                }),
+                serde_json::json!({}),
+            )
+        };
+        let old = params_spec(serde_json::json!([
+            { "name": "q", "in": "query", "schema": { "type": "string" } },
+            { "name": "limit", "in": "query", "schema": { "type": "integer", "format": "int32" } },
+            { "name": "tags", "in": "query", "schema": { "type": "array", "items": { "type": "string" } } },
+            { "name": "token", "in": "query", "schema": { "type": "string" } },
+            { "name": "id", "in": "header", "schema": { "type": "string" } }
+        ]));
+        let new = params_spec(serde_json::json!([
+            { "name": "q", "in": "query", "required": true, "schema": { "type": "string" } },
+            { "name": "limit", "in": "query", "schema": { "type": "string", "format": "int64" } },
+            { "name": "tags", "in": "query", "explode": false, "schema": { "type": "array", "items": { "type": "string" } } },
+            { "name": "token", "in": "header", "schema": { "type": "string" } },
+            { "name": "id", "in": "query", "schema": { "type": "string" } },
+            { "name": "ID", "in": "header", "schema": { "type": "string" } }
+        ]));
+
+        let diff = DiffEngine::diff(&old, &new, None);
+        let changes = &diff.modified_endpoints[0].changes;
+
+        assert!(
+            changes
+                .contains(&"Parameter limit (query) type changed: integer -> string".to_string())
+        );
+        assert!(
+            changes.contains(&"Parameter limit (query) format changed: int32 -> int64".to_string())
+        );
+        assert!(changes.contains(&"Parameter q (query) is now required".to_string()));
+        assert!(
+            changes.contains(&"Parameter tags (query) explode changed: true -> false".to_string())
+        );
+        assert!(changes.contains(&"Parameter token moved from query to header".to_string()));
+        // Same name in another location is a distinct parameter
+        assert!(changes.contains(&"Added parameter: id".to_string()));
+        assert!(!changes.iter().any(|c| c.contains("Parameter id")));
+
+        let categories: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .map(|c| format!("{:?}", c.category))
+            .collect();
+        assert_eq!(
+            categories,
+            vec![
+                "ParameterTypeChanged",
+                "ParameterFormatChanged",
+                "ParameterBecameRequired",
+                "ParameterStyleChanged",
+                "ParameterLocationChanged",
+            ]
+        );
+        assert_eq!(
+            diff.breaking_changes[4].location,
+            "GET /search -> parameters/query/token"
+        );
+    }
+
+    #[test]
+    fn test_response_compatibility() {
+        let responses_spec = |responses: serde_json::Value| {
+            spec(
+                serde_json::json!({ "/pets": { "get": { "responses": responses } } }),
+                serde_json::json!({
+                    "Pet": { "type": "object", "properties": { "name": { "type": "string" } } },
+                    "Dog": { "type": "object", "properties": { "name": { "type": "string" } } }
+                }),
+            )
+        };
+        let json = |schema: serde_json::Value| {
+            serde_json::json!({
+                "description": "ok",
+                "content": { "application/json": { "schema": schema } }
+            })
+        };
+        let old = responses_spec(serde_json::json!({
+            "200": json(serde_json::json!({ "$ref": "#/components/schemas/Pet" })),
+            "202": json(serde_json::json!({
+                "type": "object",
+                "properties": { "id": { "type": "string" }, "eta": { "type": "integer" } }
+            })),
+            "204": { "description": "empty" },
+            "404": json(serde_json::json!({ "type": "string" }))
+        }));
+        let new = responses_spec(serde_json::json!({
+            "200": json(serde_json::json!({ "$ref": "#/components/schemas/Dog" })),
+            "202": json(serde_json::json!({
+                "type": "object",
+                "properties": { "id": { "type": "string" } }
+            }))
+        }));
+
+        let diff = DiffEngine::diff(&old, &new, None);
+        let breaking: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .map(|c| (format!("{:?}", c.category), c.location.as_str()))
+            .collect();
+
+        assert_eq!(
+            breaking,
+            vec![
+                (
+                    "ResponseTypeChanged".to_string(),
+                    "GET /pets -> 200/content/application/json/schema"
+                ),
+                (
+                    "ResponseTypeChanged".to_string(),
+                    "GET /pets -> 202/content/application/json/schema/properties/eta"
+                ),
+                ("ResponseRemoved".to_string(), "GET /pets -> 204"),
+            ]
+        );
+        // A removed error response is reported but not breaking
+        assert!(
+            diff.modified_endpoints[0]
+                .changes
+                .contains(&"Removed response: 404".to_string())
+        );
+    }
+
+    #[test]
+    fn test_discriminator_mapping_removal() {
+        let event_spec = |mapping: serde_json::Value| {
+            spec(
+                serde_json::json!({}),
+                serde_json::json!({
+                    "Created": { "type": "object", "properties": { "type": { "type": "string" } } },
+                    "Deleted": { "type": "object", "properties": { "type": { "type": "string" } } },
+                    "Event": {
+                        "oneOf": [
+                            { "$ref": "#/components/schemas/Created" },
+                            { "$ref": "#/components/schemas/Deleted" }
+                        ],
+                        "discriminator": { "propertyName": "type", "mapping": mapping }
+                    }
+                }),
+            )
+        };
+        let old = event_spec(serde_json::json!({
+            "created": "#/components/schemas/Created",
+            "deleted": "#/components/schemas/Deleted",
+            "removed": "#/components/schemas/Deleted"
+        }));
+        let new = event_spec(serde_json::json!({
+            "created": "#/components/schemas/Created",
+            "deleted": "#/components/schemas/Deleted"
+        }));
+
+        let diff = DiffEngine::diff(&old, &new, None);
+        let removed: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c.category,
+                    BreakingChangeCategory::DiscriminatorMappingRemoved
+                )
+            })
+            .map(|c| c.message.as_str())
+            .collect();
+        assert_eq!(
+            removed,
+            vec!["Discriminator mapping 'removed' -> Deleted removed from 'Event'"]
+        );
+    }
+
+    #[test]
+    fn test_webhook_changes() {
+        // Webhooks live outside `paths`, so this document is built by hand
+        let webhook_spec = |webhooks: serde_json::Value| {
+            let spec = serde_json::json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Pets", "version": "1.0.0" },
+                "paths": {},
+                "webhooks": webhooks
+            });
+            OpenApiParser::parse_value(spec, "pets.json").unwrap()
+        };
+        let old = webhook_spec(serde_json::json!({
+            "petAdded": { "post": { "responses": {} } },
+            "petRemoved": { "post": { "responses": {} } }
+        }));
+        let new = webhook_spec(serde_json::json!({
+            "petAdded": {
+                "post": {
+                    "parameters": [{ "name": "X-Signature", "in": "header", "required": true }],
+                    "responses": {}
+                }
+            },
+            "petSold": { "post": { "responses": {} } }
+        }));
+
+        let diff = DiffEngine::diff(&old, &new, None);
+
+        assert_eq!(diff.added_webhooks[0].key, "webhook:petSold:post");
+        assert_eq!(diff.removed_webhooks[0].key, "webhook:petRemoved:post");
+        assert_eq!(
+            diff.modified_webhooks[0].changes,
+            vec!["Added required parameter: X-Signature".to_string()]
+        );
+        assert!(
+            diff.breaking_changes
+                .iter()
+                .any(|c| matches!(c.category, BreakingChangeCategory::WebhookRemoved))
+        );
+    }
+
+    fn response_spec(response: serde_json::Value) -> ParsedSpec {
+        spec(
+            serde_json::json!({ "/pets": { "get": { "responses": { "200": response } } } }),
+            serde_json::json!({ "Pets": { "type": "array", "items": { "type": "string" } } }),
+        )
+    }
+
+    #[test]
+    fn test_response_headers_and_media_types() {
+        let old = response_spec(serde_json::json!({
+            "description": "ok",
+            "headers": {
+                "X-Rate-Limit": { "schema": { "type": "integer" } },
+                "ETag": { "schema": { "type": "string" } }
+            },
+            "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Pets" } },
+                "application/xml": { "schema": { "$ref": "#/components/schemas/Pets" } }
+            }
+        }));
+        let new = response_spec(serde_json::json!({
+            "description": "ok",
+            "headers": { "etag": { "schema": { "type": "string" } } },
+            "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Pets" } }
+            },
+            "links": { "next": { "operationId": "listPets" } }
+        }));
+
+        let diff = DiffEngine::diff(&old, &new, None);
+        let changes = &diff.modified_endpoints[0].changes;
+
+        assert!(changes.contains(&"Response 200: removed header X-Rate-Limit".to_string()));
+        assert!(changes.contains(&"Response 200: removed media type application/xml".to_string()));
+        assert!(changes.contains(&"Response 200: added link next".to_string()));
+        assert!(!changes.iter().any(|c| c.contains("ETag")));
+
+        let categories: Vec<_> = diff.breaking_changes.iter().map(|c| c.category).collect();
+        assert!(matches!(
+            categories.as_slice(),
+            [
+                BreakingChangeCategory::ResponseHeaderRemoved,
+                BreakingChangeCategory::MediaTypeRemoved
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_rule_configuration() {
+        let old = response_spec(serde_json::json!({
+            "description": "ok",
+            "headers": { "X-Rate-Limit": { "schema": { "type": "integer" } } },
+            "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Pets" } },
+                "application/xml": { "schema": { "$ref": "#/components/schemas/Pets" } }
+            }
+        }));
+        let new = response_spec(serde_json::json!({
+            "description": "ok",
+            "content": {
+                "application/json": { "schema": { "$ref": "#/components/schemas/Pets" } }
+            }
+        }));
+
+        let mut diff = DiffEngine::diff(&old, &new, None);
+        let defaults: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .map(|c| (c.rule.as_str(), c.severity))
+            .collect();
+        assert_eq!(
+            defaults,
+            vec![
+                ("response-header-removed", Severity::Warning),
+                ("media-type-removed", Severity::Error),
+            ]
+        );
+
+        let config: DiffConfig = serde_json::from_value(serde_json::json!({
+            "rules": {
+                "response-header-removed": { "enabled": false },
+                "media-type-removed": { "severity": "info" }
+            }
+        }))
+        .unwrap();
+        DiffEngine::apply_rules(&mut diff, &config).unwrap();
+        assert_eq!(diff.breaking_changes.len(), 1);
+        assert_eq!(diff.breaking_changes[0].severity, Severity::Info);
+
+        let unknown: DiffConfig =
+            serde_json::from_value(serde_json::json!({ "rules": { "no-such-rule": {} } })).unwrap();
+        assert!(DiffEngine::apply_rules(&mut diff, &unknown).is_err());
+    }
+
+    #[test]
+    fn test_rename_and_move_detection() {
+        let rename_spec = |prefix: &str, user: &str, pet: &str, pet_schema: serde_json::Value| {
+            spec(
+                serde_json::json!({
+                    format!("{prefix}/users"): { "get": {
+                        "operationId": "listUsers",
+                        "responses": { "200": { "description": "ok", "content": {
+                            "application/json": { "schema": { "$ref": format!("#/components/schemas/{user}") } }
+                        } } }
+                    } },
+                    "/pets": { "get": {
+                        "responses": { "200": { "description": "ok", "content": {
+                            "application/json": { "schema": { "$ref": format!("#/components/schemas/{pet}") } }
+                        } } }
+                    } }
+                }),
+                serde_json::json!({
+                    user: {
+                        "type": "object",
+                        "required": ["id"],
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "pet": { "$ref": format!("#/components/schemas/{pet}") }
+                        }
+                    },
+                    pet: pet_schema
+                }),
+            )
+        };
+        let pet = serde_json::json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string" },
+                "tag": { "type": "string" },
+                "age": { "type": "integer" }
+            }
+        });
+        let mut animal = pet.clone();
+        animal["properties"]["weight"] = serde_json::json!({ "type": "number" });
+
+        let old = rename_spec("/v1", "User", "Pet", pet);
+        let new = rename_spec("/v2", "Account", "Animal", animal);
+        let graph = GraphBuilder::build(&new);
+        let diff = DiffEngine::diff(&old, &new, Some(&graph));
+
+        assert!(diff.removed_schemas.is_empty() && diff.added_schemas.is_empty());
+        assert!(diff.removed_endpoints.is_empty() && diff.added_endpoints.is_empty());
+
+        let renames: Vec<_> = diff
+            .renamed_schemas
+            .iter()
+            .map(|r| (r.old_name.as_str(), r.new_name.as_str(), r.evidence))
+            .collect();
+        assert_eq!(
+            renames,
+            vec![
+                ("Pet", "Animal", MatchEvidence::StructuralSimilarity),
+                ("User", "Account", MatchEvidence::IdenticalDefinition),
+            ]
+        );
+        assert!(diff.renamed_schemas[0].confidence < 1.0);
+        assert_eq!(
+            diff.renamed_schemas[0].affected_endpoints,
+            vec!["get:/pets", "get:/v2/users"]
+        );
+
+        assert_eq!(diff.moved_endpoints.len(), 1);
+        let moved = &diff.moved_endpoints[0];
+        assert_eq!(
+            (moved.old_key.as_str(), moved.new_key.as_str()),
+            ("get:/v1/users", "get:/v2/users")
+        );
+        assert_eq!(moved.evidence, MatchEvidence::SameOperationId);
+        assert_eq!(moved.confidence, 1.0);
+
+        // The only content change is Animal.weight; references to the
+        // renamed schemas do not count as modifications
+        let modified: Vec<_> = diff
+            .modified_schemas
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(modified, vec!["Animal"]);
+        let modified: Vec<_> = diff
+            .modified_endpoints
+            .iter()
+            .map(|e| e.key.as_str())
+            .collect();
+        assert_eq!(modified, vec!["get:/pets"]);
+
+        let rules: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .map(|c| c.rule.as_str())
+            .collect();
+        assert_eq!(rules.iter().filter(|r| **r == "schema-renamed").count(), 2);
+        assert!(rules.contains(&"endpoint-moved"));
+        let moved = diff
+            .breaking_changes
+            .iter()
+            .find(|c| c.rule == "endpoint-moved")
+            .unwrap();
+        assert_eq!(moved.location, "GET /v2/users");
+        assert!(!rules.contains(&"schema-removed") && !rules.contains(&"endpoint-removed"));
+    }
+
+    /// 0: nothing deprecated, 1: deprecations announced, 2: deprecated items removed
+    fn lifecycle_spec(stage: u8) -> ParsedSpec {
+        let deprecated = stage >= 1;
+        let get = |deprecated: bool| {
+            serde_json::json!({
+                "deprecated": deprecated,
+                "x-sunset": if deprecated { Some("2025-06-30") } else { None },
+                "responses": { "200": { "description": "ok" } }
+            })
+        };
+        let mut paths = serde_json::json!({
+            "/pets": {
+                "get": {
+                    "parameters": [
+                        { "name": "limit", "in": "query", "deprecated": deprecated, "schema": { "type": "integer" } },
+                        { "name": "offset", "in": "query", "schema": { "type": "integer" } }
+                    ],
+                    "responses": { "200": { "description": "ok" } }
+                }
+            },
+            "/owners": { "get": get(deprecated) },
+            "/vets": { "get": get(false) }
+        });
+        let mut pet_properties = serde_json::json!({
+            "name": { "type": "string" },
+            "nickname": {
+                "type": "string",
+                "deprecated": deprecated,
+                "x-deprecated-at": if deprecated { Some("2024-01-15") } else { None }
+            }
+        });
+        let mut schemas = serde_json::json!({
+            "Legacy": { "type": "object", "deprecated": deprecated, "properties": { "id": { "type": "string" } } }
+        });
+        if stage >= 2 {
+            for path in ["/owners", "/vets"] {
+                paths.as_object_mut().unwrap().remove(path);
+            }
+            paths["/pets"]["get"]["parameters"] = serde_json::json!([]);
+            pet_properties.as_object_mut().unwrap().remove("nickname");
+            schemas.as_object_mut().unwrap().remove("Legacy");
+        }
+        schemas["Pet"] = serde_json::json!({ "type": "object", "properties": pet_properties });
+        spec(paths, schemas)
+    }
+
     #[test]
-    fn test_breaking_change_detection() {
-        // Test would create two specs and verify breaking changes are detected
+    fn test_deprecation_lifecycle() {
+        let diff = DiffEngine::diff(&lifecycle_spec(0), &lifecycle_spec(1), None);
+        assert!(diff.breaking_changes.is_empty());
+
+        let mut deprecations: Vec<_> = diff
+            .deprecations
+            .iter()
+            .map(|d| (d.target, d.location.as_str(), d.message.as_str()))
+            .collect();
+        deprecations.sort_by_key(|(_, location, _)| *location);
+        assert_eq!(
+            deprecations,
+            vec![
+                (
+                    DeprecationTarget::Schema,
+                    "#/components/schemas/Legacy",
+                    "Schema 'Legacy' deprecated"
+                ),
+                (
+                    DeprecationTarget::Property,
+                    "#/components/schemas/Pet/properties/nickname",
+                    "Property 'Pet.nickname' deprecated (deprecated at 2024-01-15)"
+                ),
+                (
+                    DeprecationTarget::Operation,
+                    "GET /owners",
+                    "Operation deprecated (sunset 2025-06-30)"
+                ),
+                (
+                    DeprecationTarget::Parameter,
+                    "GET /pets -> parameters/query/limit",
+                    "Parameter limit (query) deprecated"
+                ),
+            ]
+        );
+        let owners = diff
+            .deprecations
+            .iter()
+            .find(|d| d.location == "GET /owners");
+        assert_eq!(
+            owners.and_then(|d| d.dates.sunset.as_deref()),
+            Some("2025-06-30")
+        );
+
+        // Removing deprecated items is reported apart from unannounced removals
+        let diff = DiffEngine::diff(&lifecycle_spec(1), &lifecycle_spec(2), None);
+        let mut removals: Vec<_> = diff
+            .breaking_changes
+            .iter()
+            .map(|c| (c.rule.as_str(), c.severity, c.message.as_str()))
+            .collect();
+        removals.sort();
+        assert_eq!(
+            removals,
+            vec![
+                (
+                    "deprecated-endpoint-removed",
+                    Severity::Warning,
+                    "Deprecated endpoint 'get:/owners' was removed (sunset 2025-06-30)"
+                ),
+                (
+                    "deprecated-parameter-removed",
+                    Severity::Warning,
+                    "Removed deprecated parameter: limit"
+                ),
+                (
+                    "deprecated-schema-field-removed",
+                    Severity::Warning,
+                    "Deprecated property 'Pet.nickname' removed (deprecated at 2024-01-15)"
+                ),
+                (
+                    "deprecated-schema-removed",
+                    Severity::Warning,
+                    "Deprecated schema 'Legacy' was removed"
+                ),
+                (
+                    "endpoint-removed",
+                    Severity::Error,
+                    "Endpoint 'get:/vets' was removed"
+                ),
+                (
+                    "parameter-removed",
+                    Severity::Error,
+                    "Removed parameter: offset"
+                ),
+            ]
+        );
     }
 }
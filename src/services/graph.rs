@@ -1,6 +1,6 @@
 //! Dependency graph builder service
 
-use crate::types::{DependencyGraph, ParsedSpec};
+use crate::types::{DependencyGraph, Endpoint, ParsedSpec, SchemaUsage};
 
 /// Build a dependency graph from a parsed OpenAPI spec
 pub struct GraphBuilder;
@@ -10,8 +10,14 @@ impl GraphBuilder {
     pub fn build(spec: &ParsedSpec) -> DependencyGraph {
         let mut graph = DependencyGraph::new();
 
-        // Add schema -> schema dependencies
+        // Add schema -> schema dependencies, labeled with the referencing
+        // property where the shape shows one
         for (name, schema) in &spec.schemas {
+            let mut sites = Vec::new();
+            schema.schema_type.collect_ref_sites(&mut sites);
+            for site in &sites {
+                graph.add_schema_field_dep(name, &site.field, &site.reference, site.kind);
+            }
             for ref_name in &schema.refs {
                 graph.add_schema_schema_dep(name, ref_name);
             }
@@ -22,10 +28,44 @@ impl GraphBuilder {
             for schema_ref in &endpoint.schema_refs {
                 graph.add_path_schema_dep(key, schema_ref);
             }
+            Self::add_usage(
+                &mut graph,
+                endpoint,
+                SchemaUsage::REQUEST,
+                SchemaUsage::RESPONSE,
+            );
+        }
+
+        // Webhooks and callbacks are tracked like paths, under their own keys.
+        // The provider sends the request, so directions are swapped for the consumer
+        for (key, webhook) in &spec.webhooks {
+            for schema_ref in &webhook.endpoint.schema_refs {
+                graph.add_path_schema_dep(key, schema_ref);
+            }
+            Self::add_usage(
+                &mut graph,
+                &webhook.endpoint,
+                SchemaUsage::RESPONSE,
+                SchemaUsage::REQUEST,
+            );
         }
 
         graph
     }
+
+    fn add_usage(
+        graph: &mut DependencyGraph,
+        endpoint: &Endpoint,
+        request: SchemaUsage,
+        response: SchemaUsage,
+    ) {
+        for schema in endpoint.request_schema_refs() {
+            graph.add_schema_usage(&schema, request);
+        }
+        for schema in endpoint.response_schema_refs() {
+            graph.add_schema_usage(&schema, response);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +86,9 @@ mod tests {
                 schema_type: SchemaType::Object {
                     properties: HashMap::new(),
                     required: vec![],
+                    additional_properties: None,
+                    unevaluated_properties: None,
+                    constraints: SchemaConstraints::default(),
                 },
                 description: None,
                 refs: vec![],
@@ -61,6 +104,9 @@ mod tests {
                 schema_type: SchemaType::Object {
                     properties: HashMap::new(),
                     required: vec![],
+                    additional_properties: None,
+                    unevaluated_properties: None,
+                    constraints: SchemaConstraints::default(),
                 },
                 description: None,
                 refs: vec!["User".to_string()],
@@ -76,6 +122,9 @@ mod tests {
                 schema_type: SchemaType::Object {
                     properties: HashMap::new(),
                     required: vec![],
+                    additional_properties: None,
+                    unevaluated_properties: None,
+                    constraints: SchemaConstraints::default(),
                 },
                 description: None,
                 refs: vec!["User".to_string(), "Post".to_string()],
@@ -97,6 +146,9 @@ mod tests {
                 request_body: None,
                 responses: HashMap::new(),
                 deprecated: false,
+                deprecation_dates: Default::default(),
+                servers: vec![],
+                security: vec![],
                 hash: "ep1".to_string(),
                 schema_refs: vec!["User".to_string()],
             },
@@ -116,6 +168,9 @@ mod tests {
                 request_body: None,
                 responses: HashMap::new(),
                 deprecated: false,
+                deprecation_dates: Default::default(),
+                servers: vec![],
+                security: vec![],
                 hash: "ep2".to_string(),
                 schema_refs: vec!["Post".to_string()],
             },
@@ -135,6 +190,9 @@ mod tests {
                 request_body: None,
                 responses: HashMap::new(),
                 deprecated: false,
+                deprecation_dates: Default::default(),
+                servers: vec![],
+                security: vec![],
                 hash: "ep3".to_string(),
                 schema_refs: vec!["Comment".to_string()],
             },
@@ -149,16 +207,22 @@ mod tests {
                 endpoint_count: 3,
                 schema_count: 3,
                 tag_count: 3,
+                webhook_count: 0,
             },
             endpoints,
+            webhooks: HashMap::new(),
             schemas,
             tags: vec![
                 "users".to_string(),
                 "posts".to_string(),
                 "comments".to_string(),
             ],
+            servers: vec![],
+            security_schemes: HashMap::new(),
+            security: vec![],
             spec_hash: "spec123".to_string(),
             source: "test.yaml".to_string(),
+            external_files: vec![],
         }
     }
 
@@ -184,4 +248,81 @@ mod tests {
         assert!(dependents.contains("Post"));
         assert!(dependents.contains("Comment"));
     }
+
+    #[test]
+    fn test_reference_fields() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Blog", "version": "1.0.0" },
+            "paths": {
+                "/posts": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": { "$ref": "#/components/schemas/Post" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": { "type": "object", "properties": { "name": { "type": "string" } } },
+                    "Post": {
+                        "type": "object",
+                        "properties": {
+                            "author": { "$ref": "#/components/schemas/User" },
+                            "meta": {
+                                "type": "object",
+                                "properties": {
+                                    "reviewers": {
+                                        "type": "array",
+                                        "items": { "$ref": "#/components/schemas/User" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        let spec = crate::services::OpenApiParser::parse_value(spec, "blog.json").unwrap();
+        let graph = GraphBuilder::build(&spec);
+
+        let chains: Vec<_> = graph
+            .dependency_chains("get:/posts", true, "User", true)
+            .into_iter()
+            .map(|c| c.chain)
+            .collect();
+        assert_eq!(
+            chains,
+            vec![
+                "get:/posts -> Post.author -> User",
+                "get:/posts -> Post.meta.reviewers[] -> User",
+            ]
+        );
+
+        // Optional properties and arrays are recorded on the edge
+        let mut sites = Vec::new();
+        spec.schemas["Post"]
+            .schema_type
+            .collect_ref_sites(&mut sites);
+        sites.sort_by(|a, b| a.field.cmp(&b.field));
+        let kinds: Vec<_> = sites
+            .iter()
+            .map(|s| (s.field.as_str(), s.kind.optional, s.kind.collection))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![("author", true, false), ("meta.reviewers[]", true, true)]
+        );
+    }
 }
@@ -0,0 +1,161 @@
+//! Dependency graph rendering: Graphviz DOT and Mermaid flowcharts
+
+use crate::types::{GraphExport, GraphNodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Graph output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    #[default]
+    Mermaid,
+    /// Node/edge JSON document
+    Json,
+}
+
+/// Text renderer for exported graphs
+pub struct GraphRenderer;
+
+impl GraphRenderer {
+    /// Graphviz digraph; paths are rounded boxes, schemas ellipses
+    pub fn dot(graph: &GraphExport) -> String {
+        let mut dot = String::from("digraph dependencies {\n  rankdir=LR;\n");
+        for node in &graph.nodes {
+            let shape = match node.kind {
+                GraphNodeKind::Path => "box, style=rounded",
+                GraphNodeKind::Schema => "ellipse",
+            };
+            dot.push_str(&format!("  {} [shape={shape}];\n", dot_id(&node.id)));
+        }
+        for edge in &graph.edges {
+            dot.push_str(&format!("  {} -> {}", dot_id(&edge.from), dot_id(&edge.to)));
+            if !edge.fields.is_empty() {
+                dot.push_str(&format!(" [label={}]", dot_id(&edge.fields.join(", "))));
+            }
+            dot.push_str(";\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Mermaid flowchart; nodes get generated ids since path keys and schema
+    /// names may contain characters Mermaid ids cannot
+    pub fn mermaid(graph: &GraphExport) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+        let mut ids = HashMap::new();
+        for (index, node) in graph.nodes.iter().enumerate() {
+            let id = format!("n{index}");
+            let label = mermaid_label(&node.id);
+            match node.kind {
+                GraphNodeKind::Path => mermaid.push_str(&format!("  {id}([{label}])\n")),
+                GraphNodeKind::Schema => mermaid.push_str(&format!("  {id}[{label}]\n")),
+            }
+            ids.insert(node.id.as_str(), id);
+        }
+        for edge in &graph.edges {
+            let (Some(from), Some(to)) = (ids.get(edge.from.as_str()), ids.get(edge.to.as_str()))
+            else {
+                continue;
+            };
+            if edge.fields.is_empty() {
+                mermaid.push_str(&format!("  {from} --> {to}\n"));
+            } else {
+                let label = mermaid_label(&edge.fields.join(", "));
+                mermaid.push_str(&format!("  {from} -->|{label}| {to}\n"));
+            }
+        }
+        mermaid
+    }
+}
+
+/// Quoted DOT identifier
+fn dot_id(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quoted Mermaid label, with quotes as entity codes
+fn mermaid_label(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "#quot;"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DependencyGraph, GraphNode, GraphNodeKind, ReferenceKind};
+
+    fn graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        graph.add_path_schema_dep("get:/posts/{id}", "Post");
+        graph.add_path_schema_dep("get:/tags", "Tag");
+        graph.add_schema_field_dep("Post", "author", "User", ReferenceKind::default());
+        graph.add_schema_field_dep("Post", "tags[]", "Tag", ReferenceKind::default());
+        graph
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let export = graph().export(None);
+        assert_eq!(export.nodes.len(), 5);
+        assert_eq!(export.edges.len(), 4);
+
+        assert_eq!(
+            GraphRenderer::dot(&export),
+            r#"digraph dependencies {
+  rankdir=LR;
+  "get:/posts/{id}" [shape=box, style=rounded];
+  "get:/tags" [shape=box, style=rounded];
+  "Post" [shape=ellipse];
+  "Tag" [shape=ellipse];
+  "User" [shape=ellipse];
+  "Post" -> "Tag" [label="tags[]"];
+  "Post" -> "User" [label="author"];
+  "get:/posts/{id}" -> "Post";
+  "get:/tags" -> "Tag";
+}
+"#
+        );
+        assert_eq!(
+            GraphRenderer::mermaid(&export),
+            r#"flowchart LR
+  n0(["get:/posts/{id}"])
+  n1(["get:/tags"])
+  n2["Post"]
+  n3["Tag"]
+  n4["User"]
+  n2 -->|"tags[]"| n3
+  n2 -->|"author"| n4
+  n0 --> n2
+  n1 --> n3
+"#
+        );
+    }
+
+    #[test]
+    fn test_subgraph_around_schema() {
+        let root = GraphNode {
+            id: "User".to_string(),
+            kind: GraphNodeKind::Schema,
+        };
+        let export = graph().export(Some(&[root]));
+        let ids: Vec<_> = export.nodes.iter().map(|n| n.id.as_str()).collect();
+        // Tag and its path are unrelated to User
+        assert_eq!(ids, vec!["get:/posts/{id}", "Post", "User"]);
+        assert_eq!(export.edges.len(), 2);
+    }
+
+    #[test]
+    fn test_subgraph_around_path_without_schemas() {
+        let root = GraphNode {
+            id: "get:/health".to_string(),
+            kind: GraphNodeKind::Path,
+        };
+        let export = graph().export(Some(&[root]));
+        let ids: Vec<_> = export.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["get:/health"]);
+        assert!(export.edges.is_empty());
+    }
+}
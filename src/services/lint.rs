@@ -0,0 +1,140 @@
+//! Unused and dangling component detection
+
+use crate::types::{DependencyGraph, ParsedSpec};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Component hygiene of a spec
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentReport {
+    /// Schemas no operation or webhook reaches, sorted
+    pub unused_schemas: Vec<String>,
+    /// References to schemas the spec does not define
+    pub dangling_refs: Vec<DanglingRef>,
+}
+
+/// Reference to a schema that does not exist
+#[derive(Debug, Clone, Serialize)]
+pub struct DanglingRef {
+    pub reference: String,
+    /// Paths, webhooks and schemas holding the reference
+    pub referenced_by: Vec<String>,
+}
+
+/// Component linter
+pub struct ComponentLinter;
+
+impl ComponentLinter {
+    /// Find unused schemas and dangling references
+    pub fn lint(spec: &ParsedSpec, graph: &DependencyGraph) -> ComponentReport {
+        let defined: HashSet<&str> = spec.schemas.keys().map(String::as_str).collect();
+
+        ComponentReport {
+            unused_schemas: graph
+                .unreachable_schemas(defined.iter().copied())
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            dangling_refs: graph
+                .dangling_refs(&defined)
+                .into_iter()
+                .map(|(reference, referenced_by)| DanglingRef {
+                    reference,
+                    referenced_by,
+                })
+                .collect(),
+        }
+    }
+
+    /// Copy of the raw document without the `unused` schemas. Used schemas
+    /// never reference unused ones, so no dangling references are introduced
+    pub fn prune(document: &serde_json::Value, unused: &[String]) -> serde_json::Value {
+        let mut pruned = document.clone();
+        for pointer in ["/components/schemas", "/definitions"] {
+            if let Some(schemas) = pruned.pointer_mut(pointer).and_then(|v| v.as_object_mut()) {
+                for name in unused {
+                    schemas.remove(name);
+                }
+            }
+        }
+        pruned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{GraphBuilder, OpenApiParser};
+
+    #[test]
+    fn test_unused_and_dangling_components() {
+        let document = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Pet" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "owner": { "$ref": "#/components/schemas/Owner" },
+                            "toy": { "$ref": "#/components/schemas/Toy" },
+                            "kind": { "$ref": "#/components/schemas/Kind" }
+                        }
+                    },
+                    // Dog is only reachable through the mapping
+                    "Kind": {
+                        "oneOf": [{ "$ref": "#/components/schemas/Cat" }],
+                        "discriminator": {
+                            "propertyName": "type",
+                            "mapping": {
+                                "cat": "#/components/schemas/Cat",
+                                "dog": "#/components/schemas/Dog"
+                            }
+                        }
+                    },
+                    "Cat": { "type": "object" },
+                    "Dog": { "type": "object" },
+                    "Owner": { "type": "object" },
+                    "Legacy": {
+                        "type": "object",
+                        "properties": { "item": { "$ref": "#/components/schemas/LegacyItem" } }
+                    },
+                    "LegacyItem": { "type": "object" }
+                }
+            }
+        });
+        let spec = OpenApiParser::parse_value(document.clone(), "pets.json").unwrap();
+        let report = ComponentLinter::lint(&spec, &GraphBuilder::build(&spec));
+
+        assert_eq!(report.unused_schemas, vec!["Legacy", "LegacyItem"]);
+        assert_eq!(report.dangling_refs.len(), 1);
+        assert_eq!(report.dangling_refs[0].reference, "Toy");
+        assert_eq!(report.dangling_refs[0].referenced_by, vec!["Pet"]);
+
+        let pruned = ComponentLinter::prune(&document, &report.unused_schemas);
+        let mut names: Vec<_> = pruned["components"]["schemas"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Cat", "Dog", "Kind", "Owner", "Pet"]);
+    }
+}
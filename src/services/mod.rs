@@ -1,11 +1,24 @@
 //! Service implementations for OAS MCP server
 
 mod cache;
+mod changelog;
 mod diff;
 mod graph;
+mod graph_export;
+mod lint;
 mod parser;
+mod report;
+mod resolver;
+mod semver;
+mod source_map;
 
 pub use cache::*;
+pub use changelog::*;
 pub use diff::*;
 pub use graph::*;
+pub use graph_export::*;
+pub use lint::*;
 pub use parser::*;
+pub use report::*;
+pub use semver::*;
+pub use source_map::*;
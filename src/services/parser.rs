@@ -6,11 +6,13 @@
 //! - Single-pass reference extraction
 //! - Zero-copy where possible
 
+use super::resolver::RefResolver;
+use super::source_map::{SourceDocument, SourceMap};
 use crate::types::*;
 use once_cell::sync::Lazy;
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 /// Global HTTP client for connection pooling
@@ -55,10 +57,20 @@ impl OpenApiParser {
     /// Parse OpenAPI spec and return HTTP headers (for caching)
     pub async fn parse_with_headers(source: &str) -> OasResult<(ParsedSpec, HttpHeaders)> {
         let (content, headers) = Self::fetch_content(source).await?;
-        let spec = Self::parse_content(&content, source)?;
+        let spec = Self::parse_content(&content, source).await?;
         Ok((spec, headers))
     }
 
+    /// Load the raw (unbundled) document with source positions of its nodes
+    pub async fn load_document(source: &str) -> OasResult<SourceDocument> {
+        let (content, _headers) = Self::fetch_content(source).await?;
+        Ok(SourceDocument {
+            source: source.to_string(),
+            value: Self::deserialize_content(&content)?,
+            positions: SourceMap::scan(&content),
+        })
+    }
+
     /// Fetch content from URL or file
     async fn fetch_content(source: &str) -> OasResult<(String, HttpHeaders)> {
         if source.starts_with("http://") || source.starts_with("https://") {
@@ -70,7 +82,7 @@ impl OpenApiParser {
     }
 
     /// Fetch from remote URL using global client
-    async fn fetch_remote(url: &str) -> OasResult<(String, HttpHeaders)> {
+    pub(crate) async fn fetch_remote(url: &str) -> OasResult<(String, HttpHeaders)> {
         let response = HTTP_CLIENT
             .get(url)
             .send()
@@ -107,7 +119,7 @@ impl OpenApiParser {
     }
 
     /// Read from local file
-    fn read_local(path: &str) -> OasResult<String> {
+    pub(crate) fn read_local(path: &str) -> OasResult<String> {
         let path = Path::new(path);
 
         // Security: prevent path traversal
@@ -137,14 +149,29 @@ impl OpenApiParser {
     }
 
     /// Parse content as JSON or YAML (public for cache reuse)
-    pub fn parse_content(content: &str, source: &str) -> OasResult<ParsedSpec> {
+    ///
+    /// External $refs are resolved relative to `source` and bundled first.
+    pub async fn parse_content(content: &str, source: &str) -> OasResult<ParsedSpec> {
+        let value = Self::deserialize_content(content)?;
+        let mut resolver = RefResolver::new(source)?;
+        let value = resolver.bundle(value).await?;
+        let mut spec = Self::parse_value(value, source)?;
+        spec.external_files = resolver.local_files();
+        Ok(spec)
+    }
+
+    /// Deserialize content as JSON or YAML
+    pub(crate) fn deserialize_content(content: &str) -> OasResult<serde_json::Value> {
         // Try JSON first (faster), then YAML
-        let value: serde_json::Value = if content.trim().starts_with('{') {
-            serde_json::from_str(content).map_err(|e| OasError::InvalidJson(e.to_string()))?
+        if content.trim().starts_with('{') {
+            serde_json::from_str(content).map_err(|e| OasError::InvalidJson(e.to_string()))
         } else {
-            serde_yaml::from_str(content).map_err(|e| OasError::InvalidYaml(e.to_string()))?
-        };
+            serde_yaml::from_str(content).map_err(|e| OasError::InvalidYaml(e.to_string()))
+        }
+    }
 
+    /// Parse an already deserialized (and bundled) document
+    pub(crate) fn parse_value(value: serde_json::Value, source: &str) -> OasResult<ParsedSpec> {
         // Detect OpenAPI version
         let version = Self::detect_version(&value)?;
 
@@ -207,7 +234,15 @@ impl OpenApiParser {
         let schemas = Self::parse_swagger2_definitions_parallel(&value);
 
         // Parse paths (parallel)
-        let endpoints = Self::parse_swagger2_paths_parallel(&value);
+        let mut endpoints = Self::parse_swagger2_paths_parallel(&value);
+
+        // Swagger 2.0 has no webhooks or callbacks
+        let webhooks = HashMap::new();
+
+        let servers = Self::parse_swagger2_servers(&value);
+        let security_schemes = Self::parse_security_schemes(value.get("securityDefinitions"));
+        let security = Self::parse_security_requirements(value.get("security"));
+        Self::apply_operation_context(&mut endpoints, &value, &servers, &security);
 
         // Collect tags
         let tags: Vec<String> = endpoints
@@ -229,15 +264,203 @@ impl OpenApiParser {
                 endpoint_count: endpoints.len(),
                 schema_count: schemas.len(),
                 tag_count: tags.len(),
+                webhook_count: webhooks.len(),
             },
             endpoints,
+            webhooks,
             schemas,
             tags,
+            servers,
+            security_schemes,
+            security,
             spec_hash,
             source: source.to_string(),
+            external_files: vec![],
         })
     }
 
+    /// Derive servers from Swagger 2.0 `schemes`, `host` and `basePath`
+    fn parse_swagger2_servers(value: &serde_json::Value) -> Vec<Server> {
+        let base_path = value.get("basePath").and_then(|v| v.as_str()).unwrap_or("");
+
+        let Some(host) = value.get("host").and_then(|v| v.as_str()) else {
+            // Relative to wherever the spec is served from
+            return vec![Server {
+                url: if base_path.is_empty() { "/" } else { base_path }.to_string(),
+                description: None,
+                variables: BTreeMap::new(),
+            }];
+        };
+
+        let schemes: Vec<&str> = value
+            .get("schemes")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+            .filter(|schemes: &Vec<&str>| !schemes.is_empty())
+            .unwrap_or_else(|| vec!["https"]);
+
+        schemes
+            .into_iter()
+            .map(|scheme| Server {
+                url: format!("{scheme}://{host}{base_path}"),
+                description: None,
+                variables: BTreeMap::new(),
+            })
+            .collect()
+    }
+
+    /// Parse an OpenAPI 3.x `servers` list
+    fn parse_servers(servers: Option<&serde_json::Value>) -> Vec<Server> {
+        servers
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|server| {
+                        Some(Server {
+                            url: server.get("url")?.as_str()?.to_string(),
+                            description: server
+                                .get("description")
+                                .and_then(|v| v.as_str())
+                                .map(String::from),
+                            variables: server
+                                .get("variables")
+                                .and_then(|v| v.as_object())
+                                .map(|vars| {
+                                    vars.iter()
+                                        .filter_map(|(name, var)| {
+                                            let default = var.get("default")?.as_str()?;
+                                            Some((name.clone(), default.to_string()))
+                                        })
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parse security schemes (3.x `securitySchemes` or 2.0 `securityDefinitions`)
+    fn parse_security_schemes(
+        schemes: Option<&serde_json::Value>,
+    ) -> HashMap<String, SecurityScheme> {
+        let Some(schemes) = schemes.and_then(|v| v.as_object()) else {
+            return HashMap::new();
+        };
+
+        schemes
+            .iter()
+            .filter_map(|(name, scheme)| {
+                let str_field =
+                    |key: &str| scheme.get(key).and_then(|v| v.as_str()).map(String::from);
+
+                let parsed = match scheme.get("type")?.as_str()? {
+                    "http" => SecurityScheme::Http {
+                        scheme: str_field("scheme")?.to_lowercase(),
+                        bearer_format: str_field("bearerFormat"),
+                    },
+                    // Swagger 2.0
+                    "basic" => SecurityScheme::Http {
+                        scheme: "basic".to_string(),
+                        bearer_format: None,
+                    },
+                    "apiKey" => SecurityScheme::ApiKey {
+                        name: str_field("name")?,
+                        location: match scheme.get("in")?.as_str()? {
+                            "query" => ParameterLocation::Query,
+                            "cookie" => ParameterLocation::Cookie,
+                            _ => ParameterLocation::Header,
+                        },
+                    },
+                    "oauth2" => SecurityScheme::OAuth2 {
+                        flows: match scheme.get("flows").and_then(|v| v.as_object()) {
+                            Some(flows) => flows.keys().cloned().collect(),
+                            None => str_field("flow").into_iter().collect(),
+                        },
+                    },
+                    "openIdConnect" => SecurityScheme::OpenIdConnect {
+                        open_id_connect_url: str_field("openIdConnectUrl")?,
+                    },
+                    "mutualTLS" => SecurityScheme::MutualTls,
+                    _ => return None,
+                };
+
+                Some((name.clone(), parsed))
+            })
+            .collect()
+    }
+
+    /// Parse a `security` requirement list
+    fn parse_security_requirements(
+        security: Option<&serde_json::Value>,
+    ) -> Vec<SecurityRequirement> {
+        security
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|req| req.as_object())
+                    .map(|req| {
+                        req.iter()
+                            .map(|(name, scopes)| {
+                                let scopes = scopes
+                                    .as_array()
+                                    .map(|s| {
+                                        s.iter()
+                                            .filter_map(|v| v.as_str().map(String::from))
+                                            .collect()
+                                    })
+                                    .unwrap_or_default();
+                                (name.clone(), scopes)
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Resolve effective servers and security for every endpoint.
+    /// Operation-level values override path-level, which override the root.
+    fn apply_operation_context(
+        endpoints: &mut HashMap<String, Endpoint>,
+        value: &serde_json::Value,
+        servers: &[Server],
+        security: &[SecurityRequirement],
+    ) {
+        let Some(paths) = value.get("paths").and_then(|v| v.as_object()) else {
+            return;
+        };
+
+        for (path, path_item) in paths {
+            let Some(operations) = path_item.as_object() else {
+                continue;
+            };
+            let path_servers = path_item
+                .get("servers")
+                .map(|s| Self::parse_servers(Some(s)));
+
+            for (method, operation) in operations {
+                let Some(http_method) = Self::parse_http_method(method) else {
+                    continue;
+                };
+                let key = format!("{}:{path}", http_method.to_string().to_lowercase());
+                let Some(endpoint) = endpoints.get_mut(&key) else {
+                    continue;
+                };
+
+                endpoint.servers = match operation.get("servers") {
+                    Some(s) => Self::parse_servers(Some(s)),
+                    None => path_servers.clone().unwrap_or_else(|| servers.to_vec()),
+                };
+                endpoint.security = match operation.get("security") {
+                    Some(s) => Self::parse_security_requirements(Some(s)),
+                    None => security.to_vec(),
+                };
+            }
+        }
+    }
+
     /// Parse Swagger 2.0 definitions in parallel
     fn parse_swagger2_definitions_parallel(value: &serde_json::Value) -> HashMap<String, Schema> {
         if let Some(definitions) = value.get("definitions").and_then(|v| v.as_object()) {
@@ -298,7 +521,7 @@ impl OpenApiParser {
                         endpoint: Self::parse_swagger2_operation_optimized(
                             path,
                             http_method,
-                            operation,
+                            &Self::deref_components(operation, value),
                         ),
                     })
                 })
@@ -367,6 +590,9 @@ impl OpenApiParser {
             request_body,
             responses,
             deprecated,
+            deprecation_dates: Self::parse_deprecation_dates(operation),
+            servers: Vec::new(),
+            security: Vec::new(),
             hash,
             schema_refs,
         }
@@ -391,6 +617,19 @@ impl OpenApiParser {
                     _ => continue,
                 };
 
+                // 2.0 `collectionFormat` maps onto 3.x style/explode
+                let (style, explode) = match param.get("collectionFormat").and_then(|v| v.as_str())
+                {
+                    Some("multi") => ("form", true),
+                    Some("ssv") => ("spaceDelimited", false),
+                    Some("pipes") => ("pipeDelimited", false),
+                    Some("tsv") => ("tabDelimited", false),
+                    _ if param.get("type").and_then(|v| v.as_str()) == Some("array") => {
+                        (location.default_style(), false)
+                    }
+                    _ => (location.default_style(), location.default_style() == "form"),
+                };
+
                 params.push(Parameter {
                     name: param
                         .get("name")
@@ -408,6 +647,17 @@ impl OpenApiParser {
                         .map(String::from),
                     schema_ref: None,
                     schema_type: param.get("type").and_then(|v| v.as_str()).map(String::from),
+                    format: param
+                        .get("format")
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
+                    style: style.to_string(),
+                    explode,
+                    deprecated: param
+                        .get("deprecated")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                    deprecation_dates: Self::parse_deprecation_dates(param),
                 });
             }
         }
@@ -426,6 +676,16 @@ impl OpenApiParser {
                         .and_then(|v| v.as_str())
                         .map(|r| r.replace("#/definitions/", ""));
 
+                    let content_types: Vec<String> = operation
+                        .get("consumes")
+                        .and_then(|v| v.as_array())
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|v| v.as_str().map(String::from))
+                                .collect()
+                        })
+                        .unwrap_or_else(|| vec!["application/json".to_string()]);
+
                     return Some(RequestBody {
                         required: param
                             .get("required")
@@ -435,15 +695,8 @@ impl OpenApiParser {
                             .get("description")
                             .and_then(|v| v.as_str())
                             .map(String::from),
-                        content_types: operation
-                            .get("consumes")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| {
-                                arr.iter()
-                                    .filter_map(|v| v.as_str().map(String::from))
-                                    .collect()
-                            })
-                            .unwrap_or_else(|| vec!["application/json".to_string()]),
+                        content: Self::swagger2_media_types(&content_types, param.get("schema")),
+                        content_types,
                         schema_ref,
                     });
                 }
@@ -464,6 +717,16 @@ impl OpenApiParser {
                     .and_then(|v| v.as_str())
                     .map(|r| r.replace("#/definitions/", ""));
 
+                let content_types: Vec<String> = operation
+                    .get("produces")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|v| v.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_else(|| vec!["application/json".to_string()]);
+
                 responses.insert(
                     status.clone(),
                     Response {
@@ -472,16 +735,12 @@ impl OpenApiParser {
                             .get("description")
                             .and_then(|v| v.as_str())
                             .map(String::from),
-                        content_types: operation
-                            .get("produces")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| {
-                                arr.iter()
-                                    .filter_map(|v| v.as_str().map(String::from))
-                                    .collect()
-                            })
-                            .unwrap_or_else(|| vec!["application/json".to_string()]),
+                        content: Self::swagger2_media_types(&content_types, resp.get("schema")),
+                        content_types,
                         schema_ref,
+                        // 2.0 headers carry `type` directly instead of a `schema`
+                        headers: Self::parse_response_headers(resp, |h| Some(h)),
+                        links: Vec::new(),
                     },
                 );
             }
@@ -490,6 +749,37 @@ impl OpenApiParser {
         responses
     }
 
+    /// Swagger 2.0 shares one schema across all `consumes`/`produces` types
+    fn swagger2_media_types(
+        content_types: &[String],
+        schema: Option<&serde_json::Value>,
+    ) -> Vec<MediaType> {
+        let Some(schema) = schema else {
+            return Vec::new();
+        };
+        content_types
+            .iter()
+            .map(|content_type| Self::media_type(content_type, schema))
+            .collect()
+    }
+
+    /// Media type entry: a component `$ref`, or the schema parsed inline
+    fn media_type(content_type: &str, schema: &serde_json::Value) -> MediaType {
+        let schema_ref = schema
+            .get("$ref")
+            .and_then(|v| v.as_str())
+            .and_then(Self::schema_ref_name);
+
+        MediaType {
+            content_type: content_type.to_string(),
+            schema: match schema_ref {
+                Some(_) => None,
+                None => Some(Self::parse_schema_type(schema)),
+            },
+            schema_ref,
+        }
+    }
+
     /// Parse OpenAPI 3.x spec (optimized)
     fn parse_openapi3(
         value: serde_json::Value,
@@ -521,7 +811,17 @@ impl OpenApiParser {
         let schemas = Self::parse_openapi3_schemas_parallel(&value);
 
         // Parse paths in parallel
-        let endpoints = Self::parse_openapi3_paths_parallel(&value);
+        let mut endpoints = Self::parse_openapi3_paths_parallel(&value);
+        let webhooks = Self::parse_openapi3_webhooks(&value);
+
+        let servers = Self::parse_servers(value.get("servers"));
+        let security_schemes = Self::parse_security_schemes(
+            value
+                .get("components")
+                .and_then(|c| c.get("securitySchemes")),
+        );
+        let security = Self::parse_security_requirements(value.get("security"));
+        Self::apply_operation_context(&mut endpoints, &value, &servers, &security);
 
         // Collect tags
         let tags: Vec<String> = endpoints
@@ -543,12 +843,18 @@ impl OpenApiParser {
                 endpoint_count: endpoints.len(),
                 schema_count: schemas.len(),
                 tag_count: tags.len(),
+                webhook_count: webhooks.len(),
             },
             endpoints,
+            webhooks,
             schemas,
             tags,
+            servers,
+            security_schemes,
+            security,
             spec_hash,
             source: source.to_string(),
+            external_files: vec![],
         })
     }
 
@@ -557,16 +863,19 @@ impl OpenApiParser {
         if let Some(components) = value.get("components")
             && let Some(schema_obj) = components.get("schemas").and_then(|v| v.as_object())
         {
-            // Convert to Vec for parallel iteration
-            let items: Vec<_> = schema_obj.iter().collect();
+            // Convert to Vec for parallel iteration ($defs become schemas of their own)
+            let mut items = Vec::new();
+            for (name, def) in schema_obj {
+                Self::collect_schema_defs(name.clone(), def, &mut items);
+            }
             let parsed: Vec<ParsedSchema> = items
                 .par_iter()
                 .map(|(name, def)| {
                     let (refs, hash) = Self::extract_refs_and_hash(def);
                     ParsedSchema {
-                        name: (*name).clone(),
+                        name: name.clone(),
                         schema: Schema {
-                            name: (*name).clone(),
+                            name: name.clone(),
                             schema_type: Self::parse_schema_type(def),
                             description: def
                                 .get("description")
@@ -584,6 +893,21 @@ impl OpenApiParser {
         HashMap::new()
     }
 
+    /// Collect a schema and its nested `$defs`, named by their pointer below
+    /// `#/components/schemas/` (e.g. `Foo/$defs/Bar`) so refs resolve to them
+    fn collect_schema_defs<'a>(
+        name: String,
+        def: &'a serde_json::Value,
+        items: &mut Vec<(String, &'a serde_json::Value)>,
+    ) {
+        if let Some(defs) = def.get("$defs").and_then(|v| v.as_object()) {
+            for (def_name, nested) in defs {
+                Self::collect_schema_defs(format!("{name}/$defs/{def_name}"), nested, items);
+            }
+        }
+        items.push((name, def));
+    }
+
     /// Parse OpenAPI 3.x paths in parallel
     fn parse_openapi3_paths_parallel(value: &serde_json::Value) -> HashMap<String, Endpoint> {
         if let Some(paths) = value.get("paths").and_then(|v| v.as_object()) {
@@ -613,7 +937,7 @@ impl OpenApiParser {
                         endpoint: Self::parse_openapi3_operation_optimized(
                             path,
                             http_method,
-                            operation,
+                            &Self::deref_components(operation, value),
                         ),
                     })
                 })
@@ -628,6 +952,86 @@ impl OpenApiParser {
         }
     }
 
+    /// Parse 3.1 `webhooks` and operation-level `callbacks`
+    fn parse_openapi3_webhooks(value: &serde_json::Value) -> HashMap<String, Webhook> {
+        let mut webhooks = HashMap::new();
+
+        if let Some(entries) = value.get("webhooks").and_then(|v| v.as_object()) {
+            for (name, path_item) in entries {
+                Self::collect_webhook_operations(
+                    WebhookKind::Webhook,
+                    name,
+                    None,
+                    None,
+                    path_item,
+                    value,
+                    &mut webhooks,
+                );
+            }
+        }
+
+        let paths = value.get("paths").and_then(|v| v.as_object());
+        for (path, path_item) in paths.into_iter().flatten() {
+            for (method, operation) in path_item.as_object().into_iter().flatten() {
+                let (Some(http_method), Some(callbacks)) =
+                    (Self::parse_http_method(method), operation.get("callbacks"))
+                else {
+                    continue;
+                };
+                let parent = format!("{}:{path}", http_method.to_string().to_lowercase());
+                let callbacks = Self::deref_components(callbacks, value);
+
+                for (name, expressions) in callbacks.as_object().into_iter().flatten() {
+                    for (expression, callback_item) in expressions.as_object().into_iter().flatten()
+                    {
+                        Self::collect_webhook_operations(
+                            WebhookKind::Callback,
+                            name,
+                            Some(&parent),
+                            Some(expression),
+                            callback_item,
+                            value,
+                            &mut webhooks,
+                        );
+                    }
+                }
+            }
+        }
+
+        webhooks
+    }
+
+    /// Parse every operation of a webhook/callback path item
+    fn collect_webhook_operations(
+        kind: WebhookKind,
+        name: &str,
+        parent: Option<&str>,
+        expression: Option<&str>,
+        path_item: &serde_json::Value,
+        root: &serde_json::Value,
+        webhooks: &mut HashMap<String, Webhook>,
+    ) {
+        let path_item = Self::deref_components(path_item, root);
+
+        for (method, operation) in path_item.as_object().into_iter().flatten() {
+            let Some(http_method) = Self::parse_http_method(method) else {
+                continue;
+            };
+            let webhook = Webhook {
+                kind,
+                name: name.to_string(),
+                parent: parent.map(String::from),
+                expression: expression.map(String::from),
+                endpoint: Self::parse_openapi3_operation_optimized(
+                    expression.unwrap_or(name),
+                    http_method,
+                    operation,
+                ),
+            };
+            webhooks.insert(webhook.key(), webhook);
+        }
+    }
+
     /// Parse a single OpenAPI 3.x operation (optimized)
     fn parse_openapi3_operation_optimized(
         path: &str,
@@ -682,6 +1086,9 @@ impl OpenApiParser {
             request_body,
             responses,
             deprecated,
+            deprecation_dates: Self::parse_deprecation_dates(operation),
+            servers: Vec::new(),
+            security: Vec::new(),
             hash,
             schema_refs,
         }
@@ -709,11 +1116,11 @@ impl OpenApiParser {
                     .and_then(|v| v.as_str())
                     .map(|r| r.replace("#/components/schemas/", ""));
 
-                let schema_type = param
-                    .get("schema")
-                    .and_then(|s| s.get("type"))
+                let schema_type = param.get("schema").and_then(Self::primary_type);
+                let style = param
+                    .get("style")
                     .and_then(|v| v.as_str())
-                    .map(String::from);
+                    .unwrap_or(location.default_style());
 
                 params.push(Parameter {
                     name: param
@@ -732,6 +1139,21 @@ impl OpenApiParser {
                         .map(String::from),
                     schema_ref,
                     schema_type,
+                    format: param
+                        .get("schema")
+                        .and_then(|s| s.get("format"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
+                    style: style.to_string(),
+                    explode: param
+                        .get("explode")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(style == "form"),
+                    deprecated: param
+                        .get("deprecated")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                    deprecation_dates: Self::parse_deprecation_dates(param),
                 });
             }
         }
@@ -742,17 +1164,7 @@ impl OpenApiParser {
     /// Parse OpenAPI 3.x request body
     fn parse_openapi3_body(operation: &serde_json::Value) -> Option<RequestBody> {
         let body = operation.get("requestBody")?;
-        let content = body.get("content").and_then(|v| v.as_object())?;
-
-        let content_types: Vec<String> = content.keys().cloned().collect();
-
-        let schema_ref = content
-            .values()
-            .next()
-            .and_then(|c| c.get("schema"))
-            .and_then(|s| s.get("$ref"))
-            .and_then(|v| v.as_str())
-            .map(|r| r.replace("#/components/schemas/", ""));
+        let content = Self::parse_media_types(body.get("content")?);
 
         Some(RequestBody {
             required: body
@@ -763,8 +1175,9 @@ impl OpenApiParser {
                 .get("description")
                 .and_then(|v| v.as_str())
                 .map(String::from),
-            content_types,
-            schema_ref,
+            content_types: content.iter().map(|m| m.content_type.clone()).collect(),
+            schema_ref: preferred_media_type(&content).and_then(|m| m.schema_ref.clone()),
+            content,
         })
     }
 
@@ -774,20 +1187,10 @@ impl OpenApiParser {
 
         if let Some(resp_obj) = operation.get("responses").and_then(|v| v.as_object()) {
             for (status, resp) in resp_obj {
-                let (content_types, schema_ref) =
-                    if let Some(content) = resp.get("content").and_then(|v| v.as_object()) {
-                        let types: Vec<String> = content.keys().cloned().collect();
-                        let schema = content
-                            .values()
-                            .next()
-                            .and_then(|c| c.get("schema"))
-                            .and_then(|s| s.get("$ref"))
-                            .and_then(|v| v.as_str())
-                            .map(|r| r.replace("#/components/schemas/", ""));
-                        (types, schema)
-                    } else {
-                        (vec![], None)
-                    };
+                let content = resp
+                    .get("content")
+                    .map(Self::parse_media_types)
+                    .unwrap_or_default();
 
                 responses.insert(
                     status.clone(),
@@ -797,8 +1200,12 @@ impl OpenApiParser {
                             .get("description")
                             .and_then(|v| v.as_str())
                             .map(String::from),
-                        content_types,
-                        schema_ref,
+                        content_types: content.iter().map(|m| m.content_type.clone()).collect(),
+                        schema_ref: preferred_media_type(&content)
+                            .and_then(|m| m.schema_ref.clone()),
+                        content,
+                        headers: Self::parse_response_headers(resp, |h| h.get("schema")),
+                        links: Self::parse_response_links(resp),
                     },
                 );
             }
@@ -807,6 +1214,95 @@ impl OpenApiParser {
         responses
     }
 
+    /// Parse a `content` map into per-media-type schemas
+    fn parse_media_types(content: &serde_json::Value) -> Vec<MediaType> {
+        content
+            .as_object()
+            .map(|obj| {
+                obj.iter()
+                    .map(|(content_type, media)| match media.get("schema") {
+                        Some(schema) => Self::media_type(content_type, schema),
+                        None => MediaType {
+                            content_type: content_type.clone(),
+                            schema_ref: None,
+                            schema: None,
+                        },
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parse response `headers`; `schema_of` locates the header's schema
+    fn parse_response_headers(
+        response: &serde_json::Value,
+        schema_of: impl Fn(&serde_json::Value) -> Option<&serde_json::Value>,
+    ) -> Vec<ResponseHeader> {
+        let Some(headers) = response.get("headers").and_then(|v| v.as_object()) else {
+            return Vec::new();
+        };
+
+        headers
+            .iter()
+            .map(|(name, header)| {
+                let schema = schema_of(header);
+                ResponseHeader {
+                    name: name.clone(),
+                    required: header
+                        .get("required")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                    description: header
+                        .get("description")
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
+                    schema_ref: schema
+                        .and_then(|s| s.get("$ref"))
+                        .and_then(|v| v.as_str())
+                        .and_then(Self::schema_ref_name),
+                    schema_type: schema.and_then(Self::primary_type),
+                }
+            })
+            .collect()
+    }
+
+    /// Parse response `links`
+    fn parse_response_links(response: &serde_json::Value) -> Vec<ResponseLink> {
+        let Some(links) = response.get("links").and_then(|v| v.as_object()) else {
+            return Vec::new();
+        };
+
+        links
+            .iter()
+            .map(|(name, link)| {
+                let str_field =
+                    |key: &str| link.get(key).and_then(|v| v.as_str()).map(String::from);
+                ResponseLink {
+                    name: name.clone(),
+                    operation_id: str_field("operationId"),
+                    operation_ref: str_field("operationRef"),
+                    parameters: link
+                        .get("parameters")
+                        .and_then(|v| v.as_object())
+                        .map(|params| {
+                            params
+                                .iter()
+                                .map(|(param, expr)| {
+                                    let expr = match expr {
+                                        serde_json::Value::String(s) => s.clone(),
+                                        other => other.to_string(),
+                                    };
+                                    (param.clone(), expr)
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    description: str_field("description"),
+                }
+            })
+            .collect()
+    }
+
     /// Parse HTTP method string
     fn parse_http_method(method: &str) -> Option<HttpMethod> {
         match method.to_lowercase().as_str() {
@@ -822,8 +1318,90 @@ impl OpenApiParser {
         }
     }
 
+    /// Inline local non-schema component refs (parameters, responses, requestBodies,
+    /// headers) so operations can be parsed without chasing pointers
+    fn deref_components(value: &serde_json::Value, root: &serde_json::Value) -> serde_json::Value {
+        let mut resolved = value.clone();
+        Self::deref_components_inner(&mut resolved, root, &mut Vec::new());
+        resolved
+    }
+
+    fn deref_components_inner(
+        value: &mut serde_json::Value,
+        root: &serde_json::Value,
+        stack: &mut Vec<String>,
+    ) {
+        if let Some(ref_str) = value.get("$ref").and_then(|v| v.as_str())
+            && Self::is_component_ref(ref_str)
+            && !stack.iter().any(|r| r == ref_str)
+            && let Some(target) = root.pointer(&ref_str[1..])
+        {
+            let ref_str = ref_str.to_string();
+            *value = target.clone();
+            stack.push(ref_str);
+            Self::deref_components_inner(value, root, stack);
+            stack.pop();
+            return;
+        }
+
+        match value {
+            serde_json::Value::Object(obj) => {
+                for child in obj.values_mut() {
+                    Self::deref_components_inner(child, root, stack);
+                }
+            }
+            serde_json::Value::Array(arr) => {
+                for child in arr {
+                    Self::deref_components_inner(child, root, stack);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Is this a local ref to a non-schema component?
+    fn is_component_ref(ref_str: &str) -> bool {
+        const PREFIXES: [&str; 9] = [
+            "#/components/parameters/",
+            "#/components/responses/",
+            "#/components/requestBodies/",
+            "#/components/headers/",
+            "#/components/callbacks/",
+            "#/components/pathItems/",
+            "#/components/links/",
+            "#/parameters/",
+            "#/responses/",
+        ];
+        PREFIXES.iter().any(|p| ref_str.starts_with(p))
+    }
+
+    /// Schema name for a local schema ref, if it is one
+    fn schema_ref_name(ref_str: &str) -> Option<String> {
+        ref_str
+            .strip_prefix("#/components/schemas/")
+            .or_else(|| ref_str.strip_prefix("#/definitions/"))
+            .map(String::from)
+    }
+
     /// Parse schema type
     fn parse_schema_type(schema: &serde_json::Value) -> SchemaType {
+        let parsed = Self::parse_schema_shape(schema);
+
+        // 3.0 `nullable: true`
+        let nullable = schema
+            .get("nullable")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        match parsed {
+            SchemaType::Null | SchemaType::Nullable { .. } => parsed,
+            inner if nullable => SchemaType::Nullable {
+                inner: Box::new(inner),
+            },
+            other => other,
+        }
+    }
+
+    fn parse_schema_shape(schema: &serde_json::Value) -> SchemaType {
         if let Some(ref_str) = schema.get("$ref").and_then(|v| v.as_str()) {
             return SchemaType::Ref {
                 reference: ref_str
@@ -832,15 +1410,47 @@ impl OpenApiParser {
             };
         }
 
+        if let Some(value) = schema.get("const") {
+            return SchemaType::Const {
+                value: value.clone(),
+            };
+        }
+
+        if let Some(condition) = schema.get("if") {
+            // Keywords next to `if` still describe the instance
+            let mut rest = schema.clone();
+            if let Some(object) = rest.as_object_mut() {
+                for keyword in ["if", "then", "else"] {
+                    object.remove(keyword);
+                }
+            }
+            let base = match Self::parse_schema_shape(&rest) {
+                SchemaType::Unknown => None,
+                base => Some(Box::new(base)),
+            };
+            return SchemaType::Conditional {
+                base,
+                condition: Box::new(Self::parse_schema_type(condition)),
+                then: schema
+                    .get("then")
+                    .map(|s| Box::new(Self::parse_schema_type(s))),
+                otherwise: schema
+                    .get("else")
+                    .map(|s| Box::new(Self::parse_schema_type(s))),
+            };
+        }
+
         if let Some(one_of) = schema.get("oneOf").and_then(|v| v.as_array()) {
             return SchemaType::OneOf {
                 variants: one_of.iter().map(Self::parse_schema_type).collect(),
+                discriminator: Self::parse_discriminator(schema),
             };
         }
 
         if let Some(any_of) = schema.get("anyOf").and_then(|v| v.as_array()) {
             return SchemaType::AnyOf {
                 variants: any_of.iter().map(Self::parse_schema_type).collect(),
+                discriminator: Self::parse_discriminator(schema),
             };
         }
 
@@ -850,7 +1460,46 @@ impl OpenApiParser {
             };
         }
 
-        match schema.get("type").and_then(|v| v.as_str()) {
+        // 3.1: `type` may be a list of types
+        if let Some(types) = schema.get("type").and_then(|v| v.as_array()) {
+            return Self::parse_type_union(schema, types);
+        }
+
+        Self::parse_single_type(schema, schema.get("type").and_then(|v| v.as_str()))
+    }
+
+    /// Parse a 3.1 `type: [...]` list, folding `"null"` into `Nullable`
+    fn parse_type_union(schema: &serde_json::Value, types: &[serde_json::Value]) -> SchemaType {
+        let names: Vec<&str> = types.iter().filter_map(|t| t.as_str()).collect();
+        let mut variants: Vec<SchemaType> = names
+            .iter()
+            .filter(|t| **t != "null")
+            .map(|t| Self::parse_single_type(schema, Some(t)))
+            .collect();
+
+        let inner = match variants.len() {
+            0 => return SchemaType::Null,
+            1 => variants.remove(0),
+            _ => SchemaType::AnyOf {
+                variants,
+                discriminator: None,
+            },
+        };
+
+        if names.contains(&"null") {
+            SchemaType::Nullable {
+                inner: Box::new(inner),
+            }
+        } else {
+            inner
+        }
+    }
+
+    /// Parse a schema as a single JSON type
+    fn parse_single_type(schema: &serde_json::Value, type_name: Option<&str>) -> SchemaType {
+        let constraints = Self::parse_constraints(schema);
+
+        match type_name {
             Some("string") => SchemaType::String {
                 format: schema
                     .get("format")
@@ -861,20 +1510,32 @@ impl OpenApiParser {
                         .filter_map(|v| v.as_str().map(String::from))
                         .collect()
                 }),
+                constraints,
             },
             Some("number") => SchemaType::Number {
                 format: schema
                     .get("format")
                     .and_then(|v| v.as_str())
                     .map(String::from),
+                constraints,
             },
             Some("integer") => SchemaType::Integer {
                 format: schema
                     .get("format")
                     .and_then(|v| v.as_str())
                     .map(String::from),
+                constraints,
+            },
+            Some("boolean") => SchemaType::Boolean { constraints },
+            Some("null") => SchemaType::Null,
+            Some("array") | None if schema.get("prefixItems").is_some() => SchemaType::Tuple {
+                prefix_items: schema
+                    .get("prefixItems")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| arr.iter().map(Self::parse_schema_type).collect())
+                    .unwrap_or_default(),
+                items: schema.get("items").and_then(Self::parse_additional_schema),
             },
-            Some("boolean") => SchemaType::Boolean,
             Some("array") => {
                 let items = schema
                     .get("items")
@@ -882,6 +1543,22 @@ impl OpenApiParser {
                     .unwrap_or(SchemaType::Unknown);
                 SchemaType::Array {
                     items: Box::new(items),
+                    constraints,
+                }
+            }
+            Some("object") | None
+                if schema.get("properties").is_none()
+                    && schema
+                        .get("additionalProperties")
+                        .is_some_and(|v| v.is_object() || v == &serde_json::Value::Bool(true)) =>
+            {
+                let values = match schema.get("additionalProperties") {
+                    Some(value @ serde_json::Value::Object(_)) => Self::parse_schema_type(value),
+                    _ => SchemaType::Unknown,
+                };
+                SchemaType::Map {
+                    values: Box::new(values),
+                    constraints,
                 }
             }
             Some("object") | None if schema.get("properties").is_some() => {
@@ -908,12 +1585,134 @@ impl OpenApiParser {
                 SchemaType::Object {
                     properties,
                     required,
+                    additional_properties: schema
+                        .get("additionalProperties")
+                        .and_then(Self::parse_additional_schema),
+                    unevaluated_properties: schema
+                        .get("unevaluatedProperties")
+                        .and_then(Self::parse_additional_schema),
+                    constraints,
                 }
             }
             _ => SchemaType::Unknown,
         }
     }
 
+    /// Parse validation keywords and value annotations
+    fn parse_constraints(schema: &serde_json::Value) -> SchemaConstraints {
+        let number = |key: &str| schema.get(key).and_then(|v| v.as_f64());
+        let count = |key: &str| schema.get(key).and_then(|v| v.as_u64());
+        let flag = |key: &str| schema.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let mut minimum = number("minimum");
+        let mut maximum = number("maximum");
+
+        // 3.0 uses boolean `exclusiveMinimum`/`exclusiveMaximum` modifying minimum/maximum
+        let exclusive_minimum = match schema.get("exclusiveMinimum") {
+            Some(serde_json::Value::Bool(true)) => minimum.take(),
+            other => other.and_then(|v| v.as_f64()),
+        };
+        let exclusive_maximum = match schema.get("exclusiveMaximum") {
+            Some(serde_json::Value::Bool(true)) => maximum.take(),
+            other => other.and_then(|v| v.as_f64()),
+        };
+
+        SchemaConstraints {
+            default: schema.get("default").cloned(),
+            // 3.1 `examples` is a list; keep the first one
+            example: schema.get("example").cloned().or_else(|| {
+                schema
+                    .get("examples")
+                    .and_then(|v| v.as_array())
+                    .and_then(|arr| arr.first())
+                    .cloned()
+            }),
+            minimum,
+            maximum,
+            exclusive_minimum,
+            exclusive_maximum,
+            multiple_of: number("multipleOf"),
+            min_length: count("minLength"),
+            max_length: count("maxLength"),
+            pattern: schema
+                .get("pattern")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            min_items: count("minItems"),
+            max_items: count("maxItems"),
+            unique_items: flag("uniqueItems"),
+            read_only: flag("readOnly"),
+            write_only: flag("writeOnly"),
+            deprecated: flag("deprecated"),
+            deprecation_dates: Self::parse_deprecation_dates(schema),
+        }
+    }
+
+    /// Parse `x-deprecated-at` / `x-sunset` extensions of an operation, parameter or schema
+    fn parse_deprecation_dates(value: &serde_json::Value) -> DeprecationDates {
+        let date = |key: &str| {
+            value.get(key).and_then(|v| match v {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Null => None,
+                other => Some(other.to_string()),
+            })
+        };
+        DeprecationDates {
+            deprecated_at: date("x-deprecated-at"),
+            sunset: date("x-sunset"),
+        }
+    }
+
+    /// Parse a `discriminator` object, normalizing mapping targets to schema names
+    fn parse_discriminator(schema: &serde_json::Value) -> Option<Discriminator> {
+        let discriminator = schema.get("discriminator")?;
+        let property_name = discriminator.get("propertyName")?.as_str()?.to_string();
+
+        let mapping = discriminator
+            .get("mapping")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(tag, target)| {
+                        let target = target.as_str()?;
+                        let name =
+                            Self::schema_ref_name(target).unwrap_or_else(|| target.to_string());
+                        Some((tag.clone(), name))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Discriminator {
+            property_name,
+            mapping,
+        })
+    }
+
+    /// Parse a boolean-or-schema keyword value
+    fn parse_additional_schema(value: &serde_json::Value) -> Option<AdditionalSchema> {
+        match value {
+            serde_json::Value::Bool(allowed) => Some(AdditionalSchema::Allowed(*allowed)),
+            serde_json::Value::Object(_) => Some(AdditionalSchema::Schema(Box::new(
+                Self::parse_schema_type(value),
+            ))),
+            _ => None,
+        }
+    }
+
+    /// First non-null `type` of a schema (3.1 allows a list)
+    fn primary_type(schema: &serde_json::Value) -> Option<String> {
+        match schema.get("type")? {
+            serde_json::Value::String(t) => Some(t.clone()),
+            serde_json::Value::Array(types) => types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .find(|t| *t != "null")
+                .map(String::from),
+            _ => None,
+        }
+    }
+
     /// Extract all $ref references AND compute hash in a single pass
     /// This is the key optimization - avoid walking the tree twice
     fn extract_refs_and_hash(value: &serde_json::Value) -> (Vec<String>, String) {
@@ -952,14 +1751,11 @@ impl OpenApiParser {
 
                     let val = &obj[key];
 
-                    // Check for $ref
+                    // Check for schema $ref
                     if key == "$ref"
-                        && let Some(ref_str) = val.as_str()
+                        && let Some(name) = val.as_str().and_then(Self::schema_ref_name)
                     {
-                        let clean_ref = ref_str
-                            .replace("#/definitions/", "")
-                            .replace("#/components/schemas/", "");
-                        refs.push(clean_ref);
+                        refs.push(name);
                     }
 
                     Self::collect_refs_and_hash(val, refs, hasher);
@@ -1039,6 +1835,618 @@ mod tests {
         assert_eq!(hash.len(), 16); // 8 bytes = 16 hex chars
     }
 
+    #[test]
+    fn test_component_refs_are_dereferenced() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Components", "version": "1.0.0" },
+            "paths": {
+                "/posts": {
+                    "post": {
+                        "parameters": [{ "$ref": "#/components/parameters/Limit" }],
+                        "requestBody": { "$ref": "#/components/requestBodies/NewPost" },
+                        "responses": {
+                            "200": { "$ref": "#/components/responses/PostPage" },
+                            "404": { "$ref": "#/components/responses/NotFound" }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "Limit": {
+                        "name": "limit",
+                        "in": "query",
+                        "schema": { "$ref": "#/components/schemas/PageSize" }
+                    }
+                },
+                "requestBodies": {
+                    "NewPost": {
+                        "required": true,
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Post" } }
+                        }
+                    }
+                },
+                "responses": {
+                    "PostPage": {
+                        "description": "A page of posts",
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Page" } }
+                        }
+                    },
+                    "NotFound": {
+                        "description": "Not found",
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Error" } }
+                        }
+                    }
+                },
+                "schemas": {
+                    "PageSize": { "type": "integer" },
+                    "Post": { "type": "object", "properties": { "title": { "type": "string" } } },
+                    "Page": { "type": "object", "properties": { "total": { "type": "integer" } } },
+                    "Error": { "type": "object", "properties": { "message": { "type": "string" } } }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "components.json").unwrap();
+        let endpoint = &parsed.endpoints["post:/posts"];
+
+        assert_eq!(endpoint.parameters.len(), 1);
+        assert_eq!(endpoint.parameters[0].name, "limit");
+        assert_eq!(
+            endpoint.parameters[0].schema_ref.as_deref(),
+            Some("PageSize")
+        );
+
+        let body = endpoint.request_body.as_ref().unwrap();
+        assert!(body.required);
+        assert_eq!(body.schema_ref.as_deref(), Some("Post"));
+
+        assert_eq!(
+            endpoint.responses["200"].schema_ref.as_deref(),
+            Some("Page")
+        );
+        assert_eq!(
+            endpoint.responses["404"].schema_ref.as_deref(),
+            Some("Error")
+        );
+
+        assert_eq!(
+            endpoint.schema_refs,
+            vec!["Error", "Page", "PageSize", "Post"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_openapi_31_schema_constructs() {
+        let spec = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": { "title": "JSON Schema", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Name": { "type": ["string", "null"] },
+                    "Id": { "type": ["string", "integer"] },
+                    "Kind": { "const": "user" },
+                    "Point": {
+                        "type": "array",
+                        "prefixItems": [{ "type": "number" }, { "type": "number" }],
+                        "items": false
+                    },
+                    "Closed": {
+                        "type": "object",
+                        "properties": { "tag": { "$ref": "#/components/schemas/Closed/$defs/Tag" } },
+                        "unevaluatedProperties": false,
+                        "$defs": { "Tag": { "type": "string" } }
+                    },
+                    "Shape": {
+                        "if": { "properties": { "kind": { "const": "circle" } } },
+                        "then": { "$ref": "#/components/schemas/Point" },
+                        "else": { "type": "null" }
+                    },
+                    "Payment": {
+                        "type": "object",
+                        "required": ["method"],
+                        "minProperties": 1,
+                        "properties": {
+                            "method": { "type": "string", "enum": ["card", "bank"] },
+                            "card": { "$ref": "#/components/schemas/Point" }
+                        },
+                        "if": { "properties": { "method": { "const": "card" } } },
+                        "then": { "required": ["card"] }
+                    }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "jsonschema.json").unwrap();
+        let schema = |name: &str| &parsed.schemas[name].schema_type;
+
+        assert_eq!(schema("Name").to_string(), "string | null");
+        assert_eq!(schema("Id").to_string(), "anyOf<string | integer>");
+        assert_eq!(
+            schema("Kind"),
+            &SchemaType::Const {
+                value: serde_json::json!("user")
+            }
+        );
+        assert!(matches!(
+            schema("Point"),
+            SchemaType::Tuple {
+                prefix_items,
+                items: Some(AdditionalSchema::Allowed(false)),
+            } if prefix_items.len() == 2
+        ));
+        assert!(matches!(
+            schema("Closed"),
+            SchemaType::Object {
+                unevaluated_properties: Some(AdditionalSchema::Allowed(false)),
+                ..
+            }
+        ));
+        assert!(parsed.schemas.contains_key("Closed/$defs/Tag"));
+        assert!(matches!(
+            schema("Shape"),
+            SchemaType::Conditional {
+                base: None,
+                then: Some(_),
+                otherwise: Some(_),
+                ..
+            }
+        ));
+
+        // Properties next to `if` stay on the base shape
+        let SchemaType::Conditional {
+            base: Some(base),
+            then: Some(_),
+            otherwise: None,
+            ..
+        } = schema("Payment")
+        else {
+            panic!("Payment should be a conditional with a base shape");
+        };
+        let SchemaType::Object {
+            properties,
+            required,
+            ..
+        } = base.as_ref()
+        else {
+            panic!("Payment base should be an object");
+        };
+        let mut names: Vec<_> = properties.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["card", "method"]);
+        assert_eq!(required, &vec!["method".to_string()]);
+        assert_eq!(schema("Payment").to_string(), "object & if/then/else");
+        assert_eq!(parsed.schemas["Payment"].refs, vec!["Point".to_string()]);
+    }
+
+    #[test]
+    fn test_nullable_and_constraints() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Constraints", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Price": {
+                        "type": "number",
+                        "nullable": true,
+                        "minimum": 0,
+                        "exclusiveMinimum": true,
+                        "maximum": 1000,
+                        "multipleOf": 0.01,
+                        "default": 1,
+                        "example": 9.99
+                    },
+                    "Tags": {
+                        "type": "array",
+                        "items": { "type": "string", "pattern": "^[a-z]+$", "maxLength": 20 },
+                        "minItems": 1,
+                        "uniqueItems": true,
+                        "readOnly": true
+                    }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "constraints.json").unwrap();
+
+        let price = &parsed.schemas["Price"].schema_type;
+        assert!(matches!(price, SchemaType::Nullable { .. }));
+        let constraints = price.constraints().unwrap();
+        assert_eq!(constraints.minimum, None);
+        assert_eq!(constraints.exclusive_minimum, Some(0.0));
+        assert_eq!(constraints.maximum, Some(1000.0));
+        assert_eq!(constraints.multiple_of, Some(0.01));
+        assert_eq!(constraints.default, Some(serde_json::json!(1)));
+        assert_eq!(constraints.example, Some(serde_json::json!(9.99)));
+
+        let SchemaType::Array { items, constraints } = &parsed.schemas["Tags"].schema_type else {
+            panic!("expected array");
+        };
+        assert_eq!(constraints.min_items, Some(1));
+        assert!(constraints.unique_items && constraints.read_only);
+        let items = items.constraints().unwrap();
+        assert_eq!(items.pattern.as_deref(), Some("^[a-z]+$"));
+        assert_eq!(items.max_length, Some(20));
+    }
+
+    #[test]
+    fn test_additional_properties_map() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Maps", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Foo": { "type": "object", "properties": { "id": { "type": "string" } } },
+                    "FooMap": {
+                        "type": "object",
+                        "additionalProperties": { "$ref": "#/components/schemas/Foo" }
+                    },
+                    "Labels": { "type": "object", "additionalProperties": true },
+                    "Extensible": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } },
+                        "additionalProperties": { "type": "integer" }
+                    }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "maps.json").unwrap();
+
+        assert_eq!(
+            parsed.schemas["FooMap"].schema_type,
+            SchemaType::Map {
+                values: Box::new(SchemaType::Ref {
+                    reference: "Foo".to_string()
+                }),
+                constraints: SchemaConstraints::default(),
+            }
+        );
+        assert_eq!(
+            parsed.schemas["Labels"].schema_type.to_string(),
+            "map<unknown>"
+        );
+        assert!(matches!(
+            &parsed.schemas["Extensible"].schema_type,
+            SchemaType::Object {
+                additional_properties: Some(AdditionalSchema::Schema(_)),
+                ..
+            }
+        ));
+
+        let graph = crate::services::GraphBuilder::build(&parsed);
+        assert!(graph.get_schema_dependents("Foo").contains("FooMap"));
+    }
+
+    #[test]
+    fn test_servers_and_security() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Secured", "version": "1.0.0" },
+            "servers": [{ "url": "https://api.example.com/v1" }],
+            "security": [{ "bearerAuth": [] }],
+            "paths": {
+                "/public": {
+                    "servers": [{ "url": "https://public.example.com" }],
+                    "get": { "security": [], "responses": {} }
+                },
+                "/admin": {
+                    "get": { "security": [{ "apiKey": [] }, { "oauth": ["admin"] }], "responses": {} }
+                },
+                "/me": { "get": { "responses": {} } }
+            },
+            "components": {
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "Bearer", "bearerFormat": "JWT" },
+                    "apiKey": { "type": "apiKey", "name": "X-API-Key", "in": "header" },
+                    "oauth": { "type": "oauth2", "flows": { "clientCredentials": {} } }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "secured.json").unwrap();
+
+        assert_eq!(parsed.servers[0].url, "https://api.example.com/v1");
+        assert_eq!(
+            parsed.security_schemes["bearerAuth"],
+            SecurityScheme::Http {
+                scheme: "bearer".to_string(),
+                bearer_format: Some("JWT".to_string()),
+            }
+        );
+        assert_eq!(
+            parsed.security_schemes["oauth"],
+            SecurityScheme::OAuth2 {
+                flows: vec!["clientCredentials".to_string()],
+            }
+        );
+
+        let public = &parsed.endpoints["get:/public"];
+        assert!(public.security.is_empty());
+        assert_eq!(public.servers[0].url, "https://public.example.com");
+
+        let admin = &parsed.endpoints["get:/admin"];
+        assert_eq!(admin.security.len(), 2);
+        assert_eq!(admin.security[1]["oauth"], vec!["admin".to_string()]);
+
+        let me = &parsed.endpoints["get:/me"];
+        assert_eq!(me.security, parsed.security);
+        assert_eq!(me.servers, parsed.servers);
+    }
+
+    #[test]
+    fn test_swagger2_servers_and_security() {
+        let spec = serde_json::json!({
+            "swagger": "2.0",
+            "info": { "title": "Legacy", "version": "1.0.0" },
+            "host": "legacy.example.com",
+            "basePath": "/api",
+            "schemes": ["http", "https"],
+            "securityDefinitions": {
+                "basicAuth": { "type": "basic" },
+                "token": { "type": "apiKey", "name": "token", "in": "query" }
+            },
+            "security": [{ "basicAuth": [] }],
+            "paths": {}
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "legacy.json").unwrap();
+
+        let urls: Vec<_> = parsed.servers.iter().map(|s| s.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "http://legacy.example.com/api",
+                "https://legacy.example.com/api"
+            ]
+        );
+        assert_eq!(
+            parsed.security_schemes["token"],
+            SecurityScheme::ApiKey {
+                name: "token".to_string(),
+                location: ParameterLocation::Query,
+            }
+        );
+        assert!(matches!(
+            parsed.security_schemes["basicAuth"],
+            SecurityScheme::Http { ref scheme, .. } if scheme == "basic"
+        ));
+        assert!(parsed.security[0].contains_key("basicAuth"));
+    }
+
+    #[test]
+    fn test_webhooks_and_callbacks() {
+        let spec = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Hooks", "version": "1.0.0" },
+            "paths": {
+                "/subscriptions": {
+                    "post": {
+                        "operationId": "subscribe",
+                        "callbacks": {
+                            "onEvent": { "$ref": "#/components/callbacks/EventCallback" }
+                        },
+                        "responses": {}
+                    }
+                }
+            },
+            "webhooks": {
+                "newPet": {
+                    "post": {
+                        "requestBody": {
+                            "content": {
+                                "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                            }
+                        },
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            },
+            "components": {
+                "callbacks": {
+                    "EventCallback": {
+                        "{$request.body#/callbackUrl}": {
+                            "post": {
+                                "requestBody": {
+                                    "content": {
+                                        "application/json": { "schema": { "$ref": "#/components/schemas/Event" } }
+                                    }
+                                },
+                                "responses": {}
+                            }
+                        },
+                        "{$request.body#/fallbackUrl}": {
+                            "post": { "responses": {} }
+                        }
+                    }
+                },
+                "schemas": {
+                    "Pet": { "type": "object", "properties": { "name": { "type": "string" } } },
+                    "Event": { "type": "object", "properties": { "id": { "type": "string" } } }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "hooks.json").unwrap();
+        assert_eq!(parsed.metadata.webhook_count, 3);
+
+        let webhook = &parsed.webhooks["webhook:newPet:post"];
+        assert_eq!(webhook.kind, WebhookKind::Webhook);
+        assert_eq!(webhook.endpoint.schema_refs, vec!["Pet".to_string()]);
+
+        let callback = &parsed.webhooks["callback:post:/subscriptions:onEvent:{$request.body#/callbackUrl}:post"];
+        assert_eq!(callback.kind, WebhookKind::Callback);
+        assert_eq!(callback.parent.as_deref(), Some("post:/subscriptions"));
+        assert_eq!(
+            callback.expression.as_deref(),
+            Some("{$request.body#/callbackUrl}")
+        );
+        assert_eq!(
+            callback
+                .endpoint
+                .request_body
+                .as_ref()
+                .unwrap()
+                .schema_ref
+                .as_deref(),
+            Some("Event")
+        );
+        // Same callback name and method, different expression
+        let fallback = &parsed.webhooks["callback:post:/subscriptions:onEvent:{$request.body#/fallbackUrl}:post"];
+        assert_eq!(
+            fallback.expression.as_deref(),
+            Some("{$request.body#/fallbackUrl}")
+        );
+        assert!(fallback.endpoint.request_body.is_none());
+
+        let graph = crate::services::GraphBuilder::build(&parsed);
+        assert!(
+            graph
+                .get_affected_paths("Pet")
+                .contains("webhook:newPet:post")
+        );
+    }
+
+    #[test]
+    fn test_response_headers_links_and_media_types() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "headers": {
+                                    "X-Rate-Limit": { "$ref": "#/components/headers/RateLimit" }
+                                },
+                                "content": {
+                                    "application/xml": { "schema": { "$ref": "#/components/schemas/PetsXml" } },
+                                    "application/json": { "schema": { "$ref": "#/components/schemas/Pets" } }
+                                },
+                                "links": {
+                                    "next": {
+                                        "operationId": "listPets",
+                                        "parameters": { "cursor": "$response.body#/next" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "headers": {
+                    "RateLimit": { "required": true, "schema": { "type": "integer" } }
+                },
+                "schemas": {
+                    "Pets": { "type": "array", "items": { "type": "string" } },
+                    "PetsXml": { "type": "string" }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "pets.json").unwrap();
+        let response = &parsed.endpoints["get:/pets"].responses["200"];
+
+        assert_eq!(response.content.len(), 2);
+        assert_eq!(response.schema_ref.as_deref(), Some("Pets"));
+        assert_eq!(
+            response.headers,
+            vec![ResponseHeader {
+                name: "X-Rate-Limit".to_string(),
+                required: true,
+                description: None,
+                schema_ref: None,
+                schema_type: Some("integer".to_string()),
+            }]
+        );
+        assert_eq!(response.links[0].operation_id.as_deref(), Some("listPets"));
+        assert_eq!(
+            response.links[0].parameters["cursor"],
+            "$response.body#/next"
+        );
+    }
+
+    #[test]
+    fn test_inline_body_and_response_schemas() {
+        let spec = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Users", "version": "1.0.0" },
+            "paths": {
+                "/users": {
+                    "post": {
+                        "operationId": "createUser",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "required": ["name"],
+                                        "properties": {
+                                            "name": { "type": "string" },
+                                            "address": { "$ref": "#/components/schemas/Address" }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        "responses": {
+                            "201": {
+                                "description": "created",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "type": "array", "items": { "type": "string" } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Address": { "type": "object", "properties": { "city": { "type": "string" } } }
+                }
+            }
+        });
+
+        let parsed = OpenApiParser::parse_value(spec, "users.json").unwrap();
+        let endpoint = &parsed.endpoints["post:/users"];
+
+        let body = &endpoint.request_body.as_ref().unwrap().content[0];
+        assert!(body.schema_ref.is_none());
+        let Some(SchemaType::Object { properties, .. }) = &body.schema else {
+            panic!("expected inline object body, got {:?}", body.schema);
+        };
+        assert_eq!(
+            properties["address"],
+            SchemaType::Ref {
+                reference: "Address".to_string()
+            }
+        );
+
+        let response = &endpoint.responses["201"].content[0];
+        assert_eq!(
+            response.schema.as_ref().map(|s| s.to_string()).as_deref(),
+            Some("array<string>")
+        );
+
+        let graph = crate::services::GraphBuilder::build(&parsed);
+        assert!(graph.get_affected_paths("Address").contains("post:/users"));
+    }
+
     #[test]
     fn test_parallel_parsing() {
         // Verify rayon is working
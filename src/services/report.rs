@@ -0,0 +1,460 @@
+//! Machine-readable diff reports: SARIF, JUnit XML and JSON Patch
+
+use crate::services::{
+    BreakingChange, BreakingChangeCategory, SourceDocument, SpecDiff, escape_pointer_token,
+};
+use crate::types::Severity;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+
+/// Report formats `oas_diff` can produce besides its JSON diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportFormat {
+    /// SARIF 2.1.0, located in the new spec
+    Sarif,
+    /// JUnit XML, one test case per breaking change
+    Junit,
+    /// RFC 6902 JSON Patch from the old to the new document
+    JsonPatch,
+}
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF base id that artifact URIs under the project directory are relative to
+const SRCROOT: &str = "%SRCROOT%";
+
+/// Diff report builder
+pub struct DiffReporter;
+
+impl DiffReporter {
+    /// SARIF log with one result per breaking change, located in `new_document`.
+    /// Local specs under `base_dir` are reported relative to `%SRCROOT%`
+    pub fn sarif(diff: &SpecDiff, new_document: &SourceDocument, base_dir: &Path) -> Value {
+        let base_dir = absolute_path(base_dir);
+        let artifact = artifact_location(&new_document.source, &base_dir);
+        let swagger2 = new_document.value.get("swagger").is_some();
+        let rules: Vec<Value> = BreakingChangeCategory::ALL
+            .iter()
+            .map(|category| {
+                json!({
+                    "id": category.rule_id(),
+                    "shortDescription": { "text": format!("{category:?}") },
+                    "defaultConfiguration": { "level": sarif_level(category.default_severity()) }
+                })
+            })
+            .collect();
+
+        let results: Vec<Value> = diff
+            .breaking_changes
+            .iter()
+            .map(|change| {
+                let pointer = Self::location_pointer(change, &new_document.value, swagger2);
+                let position = new_document.positions.locate(&pointer);
+                json!({
+                    "ruleId": change.rule,
+                    "level": sarif_level(change.severity),
+                    "message": { "text": change.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": artifact,
+                            "region": {
+                                "startLine": position.line,
+                                "startColumn": position.column
+                            }
+                        },
+                        "logicalLocations": [{ "fullyQualifiedName": change.location }]
+                    }]
+                })
+            })
+            .collect();
+
+        let mut run = json!({
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules
+                }
+            },
+            "results": results
+        });
+        if let Ok(root) = Url::from_directory_path(&base_dir) {
+            run["originalUriBaseIds"] = json!({ SRCROOT: { "uri": root.as_str() } });
+        }
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [run]
+        })
+    }
+
+    /// JUnit XML: error-severity changes fail, warnings and infos pass with output
+    pub fn junit(diff: &SpecDiff, suite: &str) -> String {
+        let failures = diff
+            .breaking_changes
+            .iter()
+            .filter(|c| c.severity == Severity::Error)
+            .count();
+        let tests = diff.breaking_changes.len();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"oas_diff\" tests=\"{tests}\" failures=\"{failures}\">\n"
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{tests}\" failures=\"{failures}\">\n",
+            xml_escape(suite)
+        ));
+        for change in &diff.breaking_changes {
+            xml.push_str(&format!(
+                "    <testcase classname=\"oas_diff.{}\" name=\"{}\">",
+                change.rule,
+                xml_escape(&change.location)
+            ));
+            let message = xml_escape(&change.message);
+            if change.severity == Severity::Error {
+                xml.push_str(&format!(
+                    "\n      <failure type=\"error\" message=\"{message}\">{message}</failure>\n    "
+                ));
+            } else {
+                xml.push_str(&format!(
+                    "\n      <system-out>{}: {message}</system-out>\n    ",
+                    severity_name(change.severity)
+                ));
+            }
+            xml.push_str("</testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    /// RFC 6902 operations turning `old` into `new`. Arrays are compared by
+    /// index; trailing items are added or removed from the end
+    pub fn json_patch(old: &Value, new: &Value) -> Vec<Value> {
+        let mut operations = Vec::new();
+        Self::patch_value("", old, new, &mut operations);
+        operations
+    }
+
+    fn patch_value(pointer: &str, old: &Value, new: &Value, out: &mut Vec<Value>) {
+        match (old, new) {
+            (Value::Object(old), Value::Object(new)) => {
+                for (key, old_value) in old {
+                    let path = format!("{pointer}/{}", escape_pointer_token(key));
+                    match new.get(key) {
+                        Some(new_value) => Self::patch_value(&path, old_value, new_value, out),
+                        None => out.push(json!({ "op": "remove", "path": path })),
+                    }
+                }
+                for (key, new_value) in new {
+                    if !old.contains_key(key) {
+                        let path = format!("{pointer}/{}", escape_pointer_token(key));
+                        out.push(json!({ "op": "add", "path": path, "value": new_value }));
+                    }
+                }
+            }
+            (Value::Array(old), Value::Array(new)) => {
+                for (index, (old_item, new_item)) in old.iter().zip(new).enumerate() {
+                    Self::patch_value(&format!("{pointer}/{index}"), old_item, new_item, out);
+                }
+                for index in (new.len()..old.len()).rev() {
+                    out.push(json!({ "op": "remove", "path": format!("{pointer}/{index}") }));
+                }
+                for (index, item) in new.iter().enumerate().skip(old.len()) {
+                    out.push(
+                        json!({ "op": "add", "path": format!("{pointer}/{index}"), "value": item }),
+                    );
+                }
+            }
+            _ if old != new => out.push(json!({ "op": "replace", "path": pointer, "value": new })),
+            _ => {}
+        }
+    }
+
+    /// JSON pointer into the document for a breaking change location:
+    /// `#/components/schemas/...`, `METHOD /path -> ...` or a bare path
+    fn location_pointer(change: &BreakingChange, document: &Value, swagger2: bool) -> String {
+        let location = change.location.as_str();
+
+        if let Some(pointer) = location.strip_prefix('#') {
+            return match pointer.strip_prefix("/components/schemas/") {
+                Some(rest) if swagger2 => format!("/definitions/{rest}"),
+                _ => pointer.to_string(),
+            };
+        }
+
+        if let Some((operation, rest)) = location.split_once(" -> ") {
+            let Some((method, path)) = operation.split_once(' ') else {
+                return String::new();
+            };
+            let path_item = format!("/paths/{}", escape_pointer_token(path));
+            let operation = format!("{path_item}/{}", method.to_lowercase());
+
+            if let Some(parameter) = rest.strip_prefix("parameters/") {
+                let (location, name) = parameter.split_once('/').unwrap_or((parameter, ""));
+                return [&operation, &path_item]
+                    .into_iter()
+                    .find_map(|owner| {
+                        let index = Self::parameter_index(document, owner, location, name)?;
+                        Some(format!("{owner}/parameters/{index}"))
+                    })
+                    .unwrap_or_else(|| format!("{operation}/parameters"));
+            }
+
+            // `{status}[/headers/{name}|/content/{media type}[/schema]]`
+            let (status, detail) = rest.split_once('/').unwrap_or((rest, ""));
+            let mut pointer = format!("{operation}/responses/{status}");
+            if let Some(header) = detail.strip_prefix("headers/") {
+                pointer.push_str(&format!("/headers/{}", escape_pointer_token(header)));
+            } else if let Some(media) = detail.strip_prefix("content/") {
+                let (media, schema) = match media.strip_suffix("/schema") {
+                    Some(media) => (media, "/schema"),
+                    None => (media, ""),
+                };
+                pointer.push_str(&format!("/content/{}{schema}", escape_pointer_token(media)));
+            }
+            return pointer;
+        }
+
+        if location.starts_with('/') {
+            format!("/paths/{}", escape_pointer_token(location))
+        } else {
+            format!("/webhooks/{}", escape_pointer_token(location))
+        }
+    }
+
+    /// Index of a parameter in `{owner}/parameters`, following local `$ref`s
+    fn parameter_index(document: &Value, owner: &str, location: &str, name: &str) -> Option<usize> {
+        let parameters = document
+            .pointer(&format!("{owner}/parameters"))?
+            .as_array()?;
+        parameters.iter().position(|parameter| {
+            let parameter = match parameter.get("$ref").and_then(|r| r.as_str()) {
+                Some(reference) => reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| document.pointer(pointer))
+                    .unwrap_or(parameter),
+                None => parameter,
+            };
+            let field = |key| parameter.get(key).and_then(|v| v.as_str());
+            field("in") == Some(location)
+                && field("name").is_some_and(|n| {
+                    n == name || (location == "header" && n.eq_ignore_ascii_case(name))
+                })
+        })
+    }
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// `artifactLocation` for a spec source: relative to `%SRCROOT%` when the
+/// file lives under `base_dir`, otherwise the source as given
+fn artifact_location(source: &str, base_dir: &Path) -> Value {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return json!({ "uri": source });
+    }
+    let path = absolute_path(Path::new(source));
+    if path.starts_with(base_dir)
+        && let (Ok(root), Ok(file)) = (
+            Url::from_directory_path(base_dir),
+            Url::from_file_path(&path),
+        )
+        && let Some(relative) = root.make_relative(&file)
+    {
+        return json!({ "uri": relative, "uriBaseId": SRCROOT });
+    }
+    match Url::from_file_path(&path) {
+        Ok(file) => json!({ "uri": file.as_str() }),
+        Err(()) => json!({ "uri": source }),
+    }
+}
+
+/// Canonical form of `path` when it exists, so symlinked roots still compare
+fn absolute_path(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{DiffEngine, OpenApiParser, SourceMap};
+
+    const OLD: &str = r#"{
+  "openapi": "3.0.3",
+  "info": { "title": "Pets", "version": "1.0.0" },
+  "paths": {
+    "/pets": {
+      "get": {
+        "parameters": [
+          { "name": "limit", "in": "query", "schema": { "type": "integer" } }
+        ],
+        "responses": {
+          "200": {
+            "description": "ok",
+            "headers": { "X-Rate-Limit": { "schema": { "type": "integer" } } }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+    const NEW: &str = r#"{
+  "openapi": "3.0.3",
+  "info": { "title": "Pets", "version": "1.0.0" },
+  "paths": {
+    "/pets": {
+      "get": {
+        "parameters": [
+          { "name": "offset", "in": "query", "schema": { "type": "integer" } },
+          { "name": "limit", "in": "query", "required": true, "schema": { "type": "integer" } }
+        ],
+        "responses": {
+          "200": {
+            "description": "ok"
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+    fn document(content: &str) -> SourceDocument {
+        SourceDocument {
+            source: "pets.json".to_string(),
+            value: serde_json::from_str(content).unwrap(),
+            positions: SourceMap::scan(content),
+        }
+    }
+
+    fn diff() -> SpecDiff {
+        let parse = |content: &str| {
+            OpenApiParser::parse_value(serde_json::from_str(content).unwrap(), "pets.json").unwrap()
+        };
+        DiffEngine::diff(&parse(OLD), &parse(NEW), None)
+    }
+
+    #[test]
+    fn test_sarif_locations() {
+        let sarif = DiffReporter::sarif(&diff(), &document(NEW), Path::new("."));
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+
+        let located: Vec<_> = results
+            .iter()
+            .map(|r| {
+                let region = &r["locations"][0]["physicalLocation"]["region"];
+                (
+                    r["ruleId"].as_str().unwrap(),
+                    r["level"].as_str().unwrap(),
+                    region["startLine"].as_u64().unwrap(),
+                    region["startColumn"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                ("parameter-became-required", "error", 9, 11),
+                // Removed header: nearest surviving node is the response
+                ("response-header-removed", "warning", 12, 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sarif_artifact_uri() {
+        let uri = |source: &str, base_dir: &str| {
+            let document = SourceDocument {
+                source: source.to_string(),
+                ..document(NEW)
+            };
+            let sarif = DiffReporter::sarif(&diff(), &document, Path::new(base_dir));
+            let run = &sarif["runs"][0];
+            (
+                run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"].clone(),
+                run["originalUriBaseIds"]["%SRCROOT%"]["uri"].clone(),
+            )
+        };
+
+        assert_eq!(
+            uri("/srv/api/specs/pet store.json", "/srv/api"),
+            (
+                json!({ "uri": "specs/pet%20store.json", "uriBaseId": "%SRCROOT%" }),
+                json!("file:///srv/api/")
+            )
+        );
+        // Relative sources resolve against the working directory
+        assert_eq!(
+            uri("specs/pets.json", ".").0,
+            json!({ "uri": "specs/pets.json", "uriBaseId": "%SRCROOT%" })
+        );
+        assert_eq!(
+            uri("/srv/api/pets.json", "/srv/other").0,
+            json!({ "uri": "file:///srv/api/pets.json" })
+        );
+        assert_eq!(
+            uri("https://example.com/pets.json", "/srv/api").0,
+            json!({ "uri": "https://example.com/pets.json" })
+        );
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let junit = DiffReporter::junit(&diff(), "Pets");
+
+        assert!(junit.contains(r#"<testsuite name="Pets" tests="2" failures="1">"#));
+        assert!(junit.contains(
+            r#"<testcase classname="oas_diff.parameter-became-required" name="GET /pets -&gt; parameters/query/limit">"#
+        ));
+        assert!(junit.contains(
+            r#"<failure type="error" message="Parameter limit (query) is now required">"#
+        ));
+        assert!(junit.contains(
+            "<system-out>warning: Response header &apos;X-Rate-Limit&apos; was removed</system-out>"
+        ));
+    }
+
+    #[test]
+    fn test_json_patch() {
+        let patch = DiffReporter::json_patch(&document(OLD).value, &document(NEW).value);
+
+        assert_eq!(
+            patch,
+            vec![
+                json!({ "op": "replace", "path": "/paths/~1pets/get/parameters/0/name", "value": "offset" }),
+                json!({ "op": "add", "path": "/paths/~1pets/get/parameters/1", "value": {
+                    "name": "limit", "in": "query", "required": true, "schema": { "type": "integer" }
+                } }),
+                json!({ "op": "remove", "path": "/paths/~1pets/get/responses/200/headers" }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,569 @@
+//! External `$ref` resolution
+//!
+//! Bundles relative-file and URL references into the root document so the
+//! rest of the parser only ever sees local `#/...` references:
+//! - Schema refs are hoisted into `components/schemas` (or `definitions`)
+//! - Everything else (path items, parameters, responses) is inlined
+//! - Local files must stay inside the root spec's directory
+
+use super::parser::OpenApiParser;
+use crate::types::*;
+use reqwest::Url;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Location of a loaded document
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DocLocation {
+    File(PathBuf),
+    Url(Url),
+}
+
+impl DocLocation {
+    /// Resolve the document part of a reference relative to this location
+    fn join(&self, reference: &str, root_dir: Option<&Path>) -> OasResult<DocLocation> {
+        if reference.starts_with("http://") || reference.starts_with("https://") {
+            let url = Url::parse(reference)
+                .map_err(|e| OasError::UnresolvedRef(format!("{reference} ({e})")))?;
+            return Ok(DocLocation::Url(url));
+        }
+
+        match self {
+            DocLocation::Url(base) => {
+                let url = base
+                    .join(reference)
+                    .map_err(|e| OasError::UnresolvedRef(format!("{reference} ({e})")))?;
+                // Security: a remote document may not reach into the local file system
+                if url.scheme() != "http" && url.scheme() != "https" {
+                    return Err(OasError::PathTraversal(reference.to_string()));
+                }
+                Ok(DocLocation::Url(url))
+            }
+            DocLocation::File(path) => {
+                let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+                let joined = base_dir.join(reference);
+                let canonical = joined.canonicalize().map_err(|e| match e.kind() {
+                    std::io::ErrorKind::NotFound => {
+                        OasError::FileNotFound(joined.display().to_string())
+                    }
+                    _ => OasError::UnresolvedRef(format!("{reference} ({e})")),
+                })?;
+
+                // Security: refs may not escape the root spec's directory
+                if !root_dir.is_some_and(|dir| canonical.starts_with(dir)) {
+                    return Err(OasError::PathTraversal(reference.to_string()));
+                }
+
+                Ok(DocLocation::File(canonical))
+            }
+        }
+    }
+
+    /// Last path segment without extension (used to name whole-file schemas)
+    fn stem(&self) -> String {
+        let segment = match self {
+            DocLocation::File(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            DocLocation::Url(url) => url
+                .path_segments()
+                .and_then(|mut s| s.next_back().map(String::from))
+                .unwrap_or_default(),
+        };
+        segment
+            .split('.')
+            .next()
+            .filter(|s| !s.is_empty())
+            .unwrap_or("Schema")
+            .to_string()
+    }
+}
+
+/// A reference target: document + JSON pointer fragment
+type RefTarget = (DocLocation, String);
+
+/// Resolves and bundles external `$ref`s into a single document
+pub struct RefResolver {
+    root: DocLocation,
+    /// Canonical directory of the root spec (local sources only)
+    root_dir: Option<PathBuf>,
+    docs: HashMap<DocLocation, Value>,
+    /// Hoisted schema names by target
+    names: HashMap<RefTarget, String>,
+    /// Names already taken in the schema container
+    taken: HashSet<String>,
+    hoisted: Vec<(String, Value)>,
+    /// Prefix for bundled schema refs (`#/components/schemas/` or `#/definitions/`)
+    schema_prefix: &'static str,
+}
+
+impl RefResolver {
+    /// Create a resolver for a spec loaded from `source` (URL or file path)
+    pub fn new(source: &str) -> OasResult<Self> {
+        let (root, root_dir) = if source.starts_with("http://") || source.starts_with("https://") {
+            let url = Url::parse(source).map_err(|e| OasError::InvalidOpenApi(e.to_string()))?;
+            (DocLocation::Url(url), None)
+        } else {
+            match Path::new(source).canonicalize() {
+                Ok(canonical) => {
+                    let dir = canonical.parent().map(Path::to_path_buf);
+                    (DocLocation::File(canonical), dir)
+                }
+                // Content that didn't come from disk: only URL refs can resolve
+                Err(_) => (DocLocation::File(PathBuf::from(source)), None),
+            }
+        };
+
+        Ok(Self {
+            root,
+            root_dir,
+            docs: HashMap::new(),
+            names: HashMap::new(),
+            taken: HashSet::new(),
+            hoisted: Vec::new(),
+            schema_prefix: "#/components/schemas/",
+        })
+    }
+
+    /// Bundle all external refs of `document` into it
+    pub async fn bundle(&mut self, mut document: Value) -> OasResult<Value> {
+        let mut external = Vec::new();
+        collect_refs(&document, &mut external);
+        external.retain(|r| !r.starts_with('#'));
+        if external.is_empty() {
+            return Ok(document);
+        }
+
+        if document.get("swagger").is_some() {
+            self.schema_prefix = "#/definitions/";
+        }
+
+        self.load_documents(&document).await?;
+        self.register_root_schemas(&document)?;
+
+        let root = self.root.clone();
+        self.rewrite(&mut document, &root, false, false)?;
+
+        self.insert_hoisted(&mut document);
+        Ok(document)
+    }
+
+    /// Local files loaded while bundling, sorted
+    pub fn local_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self
+            .docs
+            .keys()
+            .filter_map(|location| match location {
+                DocLocation::File(path) => Some(path.to_string_lossy().to_string()),
+                DocLocation::Url(_) => None,
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Fetch every document reachable through external refs
+    async fn load_documents(&mut self, root: &Value) -> OasResult<()> {
+        let mut queue: VecDeque<(DocLocation, Vec<String>)> = VecDeque::new();
+        let mut refs = Vec::new();
+        collect_refs(root, &mut refs);
+        queue.push_back((self.root.clone(), refs));
+
+        while let Some((location, refs)) = queue.pop_front() {
+            for reference in refs {
+                let (doc_part, _) = split_ref(&reference);
+                if doc_part.is_empty() {
+                    continue;
+                }
+
+                let target = location.join(doc_part, self.root_dir.as_deref())?;
+                if target == self.root || self.docs.contains_key(&target) {
+                    continue;
+                }
+
+                let value = Self::fetch(&target).await?;
+                let mut nested = Vec::new();
+                collect_refs(&value, &mut nested);
+                self.docs.insert(target.clone(), value);
+                queue.push_back((target, nested));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load and deserialize a single document
+    async fn fetch(location: &DocLocation) -> OasResult<Value> {
+        let content = match location {
+            DocLocation::Url(url) => OpenApiParser::fetch_remote(url.as_str()).await?.0,
+            DocLocation::File(path) => OpenApiParser::read_local(&path.to_string_lossy())?,
+        };
+        OpenApiParser::deserialize_content(&content)
+    }
+
+    /// Root schemas that are themselves external refs keep their own name
+    fn register_root_schemas(&mut self, document: &Value) -> OasResult<()> {
+        let container = document
+            .pointer("/components/schemas")
+            .or_else(|| document.get("definitions"))
+            .and_then(|v| v.as_object());
+
+        let Some(container) = container else {
+            return Ok(());
+        };
+
+        for (name, schema) in container {
+            self.taken.insert(name.clone());
+            if let Some(reference) = schema.get("$ref").and_then(|v| v.as_str())
+                && !reference.starts_with('#')
+            {
+                let target = self.target(&self.root.clone(), reference)?;
+                self.names.entry(target).or_insert_with(|| name.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a reference string found in document `base`
+    fn target(&self, base: &DocLocation, reference: &str) -> OasResult<RefTarget> {
+        let (doc_part, fragment) = split_ref(reference);
+        let location = if doc_part.is_empty() {
+            base.clone()
+        } else {
+            base.join(doc_part, self.root_dir.as_deref())?
+        };
+        Ok((location, fragment.to_string()))
+    }
+
+    /// Look up the value a target points at
+    fn resolve(&self, target: &RefTarget) -> OasResult<Value> {
+        let (location, pointer) = target;
+        let doc = self
+            .docs
+            .get(location)
+            .ok_or_else(|| OasError::UnresolvedRef(format!("{location:?}")))?;
+        doc.pointer(pointer)
+            .cloned()
+            .ok_or_else(|| OasError::UnresolvedRef(format!("{location:?}#{pointer}")))
+    }
+
+    /// Rewrite refs inside `value`, which lives in document `base`
+    fn rewrite(
+        &mut self,
+        value: &mut Value,
+        base: &DocLocation,
+        in_schema: bool,
+        is_container: bool,
+    ) -> OasResult<()> {
+        self.rewrite_inner(value, base, in_schema, is_container, &mut Vec::new())
+    }
+
+    fn rewrite_inner(
+        &mut self,
+        value: &mut Value,
+        base: &DocLocation,
+        in_schema: bool,
+        is_container: bool,
+        inlining: &mut Vec<RefTarget>,
+    ) -> OasResult<()> {
+        if let Some(reference) = value.get("$ref").and_then(|v| v.as_str()) {
+            let reference = reference.to_string();
+
+            // Local refs in the root document are already bundled
+            if reference.starts_with('#') && *base == self.root {
+                return Ok(());
+            }
+
+            let target = self.target(base, &reference)?;
+            if target.0 == self.root {
+                *value = ref_object(&format!("#{}", target.1));
+                return Ok(());
+            }
+
+            if in_schema {
+                let name = self.hoist(&target)?;
+                *value = ref_object(&format!("{}{name}", self.schema_prefix));
+                return Ok(());
+            }
+
+            // Non-schema objects are inlined (guard against ref cycles)
+            if inlining.contains(&target) {
+                return Err(OasError::CircularRef(reference));
+            }
+            let mut resolved = self.resolve(&target)?;
+            inlining.push(target.clone());
+            self.rewrite_inner(&mut resolved, &target.0, in_schema, is_container, inlining)?;
+            inlining.pop();
+            *value = resolved;
+            return Ok(());
+        }
+
+        match value {
+            Value::Object(obj) => {
+                for (key, child) in obj.iter_mut() {
+                    let child_in_schema = in_schema || is_container || key == "schema";
+                    let child_container = !in_schema && (key == "schemas" || key == "definitions");
+                    self.rewrite_inner(child, base, child_in_schema, child_container, inlining)?;
+                }
+            }
+            Value::Array(arr) => {
+                for child in arr {
+                    self.rewrite_inner(child, base, in_schema, false, inlining)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Hoist an external schema into the schema container and return its name
+    fn hoist(&mut self, target: &RefTarget) -> OasResult<String> {
+        if let Some(name) = self.names.get(target)
+            && self.hoisted.iter().any(|(n, _)| n == name)
+        {
+            return Ok(name.clone());
+        }
+
+        let name = match self.names.get(target) {
+            Some(name) => name.clone(),
+            None => {
+                let name = self.unique_name(target);
+                self.names.insert(target.clone(), name.clone());
+                name
+            }
+        };
+
+        // Register before recursing so self-references terminate
+        self.hoisted.push((name.clone(), Value::Null));
+        let mut schema = self.resolve(target)?;
+        self.rewrite(&mut schema, &target.0, true, false)?;
+        if let Some(entry) = self.hoisted.iter_mut().find(|(n, _)| *n == name) {
+            entry.1 = schema;
+        }
+
+        Ok(name)
+    }
+
+    /// Derive a schema name that doesn't clash with existing ones
+    fn unique_name(&mut self, target: &RefTarget) -> String {
+        let (location, pointer) = target;
+        let base = pointer
+            .rsplit('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .unwrap_or_else(|| location.stem());
+
+        let mut name = base.clone();
+        let mut n = 2;
+        while self.taken.contains(&name) {
+            name = format!("{base}{n}");
+            n += 1;
+        }
+        self.taken.insert(name.clone());
+        name
+    }
+
+    /// Write hoisted schemas into the root document
+    fn insert_hoisted(&mut self, document: &mut Value) {
+        let Some(root) = document.as_object_mut() else {
+            return;
+        };
+
+        let container = if self.schema_prefix == "#/definitions/" {
+            root.entry("definitions")
+        } else {
+            root.entry("components")
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .map(|c| c.entry("schemas"))
+                .expect("components is an object")
+        }
+        .or_insert_with(|| Value::Object(Map::new()));
+
+        if let Some(container) = container.as_object_mut() {
+            for (name, schema) in self.hoisted.drain(..) {
+                // Root entries that were external refs are replaced by their content
+                let is_self_ref = container
+                    .get(&name)
+                    .and_then(|v| v.get("$ref"))
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|r| r == format!("{}{name}", self.schema_prefix));
+                if is_self_ref || !container.contains_key(&name) {
+                    container.insert(name, schema);
+                }
+            }
+        }
+    }
+}
+
+/// Split a reference into its document part and JSON pointer fragment
+fn split_ref(reference: &str) -> (&str, &str) {
+    match reference.split_once('#') {
+        Some((doc, fragment)) => (doc, fragment),
+        None => (reference, ""),
+    }
+}
+
+fn ref_object(reference: &str) -> Value {
+    let mut obj = Map::new();
+    obj.insert("$ref".to_string(), Value::String(reference.to_string()));
+    Value::Object(obj)
+}
+
+/// Collect every `$ref` string in a document
+fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(reference) = obj.get("$ref").and_then(|v| v.as_str()) {
+                refs.push(reference.to_string());
+            }
+            for child in obj.values() {
+                collect_refs(child, refs);
+            }
+        }
+        Value::Array(arr) => {
+            for child in arr {
+                collect_refs(child, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::GraphBuilder;
+
+    fn write(dir: &Path, name: &str, content: &str) -> String {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_bundles_split_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = write(
+            dir.path(),
+            "openapi.yaml",
+            r#"
+openapi: 3.0.3
+info: { title: Split, version: 1.0.0 }
+paths:
+  /posts/{id}:
+    $ref: ./paths/posts.yaml
+components:
+  schemas:
+    User:
+      $ref: ./schemas/User.yaml
+"#,
+        );
+        write(
+            dir.path(),
+            "paths/posts.yaml",
+            r#"
+get:
+  operationId: getPost
+  responses:
+    "200":
+      description: OK
+      content:
+        application/json:
+          schema:
+            $ref: ../schemas/common.yaml#/Post
+"#,
+        );
+        write(
+            dir.path(),
+            "schemas/common.yaml",
+            r#"
+Post:
+  type: object
+  properties:
+    author:
+      $ref: ./User.yaml
+"#,
+        );
+        write(
+            dir.path(),
+            "schemas/User.yaml",
+            "type: object\nproperties:\n  name: { type: string }\n",
+        );
+
+        let content = std::fs::read_to_string(&root).unwrap();
+        let spec = OpenApiParser::parse_content(&content, &root).await.unwrap();
+
+        assert!(spec.schemas.contains_key("Post"));
+        assert!(spec.schemas.contains_key("User"));
+        assert_eq!(spec.schemas.len(), 2);
+        assert_eq!(spec.schemas["Post"].refs, vec!["User".to_string()]);
+
+        let endpoint = &spec.endpoints["get:/posts/{id}"];
+        assert_eq!(endpoint.schema_refs, vec!["Post".to_string()]);
+
+        // Dependency tracking works across files
+        let graph = GraphBuilder::build(&spec);
+        assert!(graph.get_affected_paths("User").contains("get:/posts/{id}"));
+    }
+
+    #[tokio::test]
+    async fn test_name_clash_gets_suffix() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = write(
+            dir.path(),
+            "openapi.json",
+            r##"{
+  "openapi": "3.0.3",
+  "info": { "title": "Clash", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Error": { "type": "object", "properties": { "code": { "type": "integer" } } },
+      "Wrapper": { "type": "object", "properties": { "error": { "$ref": "./other.json#/Error" } } }
+    }
+  }
+}"##,
+        );
+        write(
+            dir.path(),
+            "other.json",
+            r#"{ "Error": { "type": "string" } }"#,
+        );
+
+        let content = std::fs::read_to_string(&root).unwrap();
+        let spec = OpenApiParser::parse_content(&content, &root).await.unwrap();
+
+        assert!(spec.schemas.contains_key("Error2"));
+        assert_eq!(spec.schemas["Wrapper"].refs, vec!["Error2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_blocks_refs_outside_root_dir() {
+        let outer = tempfile::tempdir().unwrap();
+        write(outer.path(), "secret.yaml", "type: string\n");
+        let root = write(
+            outer.path(),
+            "api/openapi.yaml",
+            r#"
+openapi: 3.0.3
+info: { title: Escape, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    Secret:
+      $ref: ../secret.yaml
+"#,
+        );
+
+        let content = std::fs::read_to_string(&root).unwrap();
+        let result = OpenApiParser::parse_content(&content, &root).await;
+        assert!(matches!(result, Err(OasError::PathTraversal(_))));
+    }
+}
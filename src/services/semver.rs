@@ -0,0 +1,309 @@
+//! Semantic version advice for spec changes
+
+use crate::services::{SchemaFieldChangeKind, SpecDiff};
+use crate::types::Severity;
+use serde::{Deserialize, Serialize};
+
+/// Size of a version change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionBump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+/// Recommended bump for a diff, checked against the author's `info.version` change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionRecommendation {
+    pub old_version: String,
+    pub new_version: String,
+    /// Smallest bump the changes require
+    pub required_bump: VersionBump,
+    /// Bump the spec author made, when both versions are semantic versions
+    pub actual_bump: Option<VersionBump>,
+    /// `old_version` with the required bump applied
+    pub recommended_version: Option<String>,
+    /// False when the author's bump is smaller than the changes require
+    pub consistent: bool,
+    /// Why the required bump is needed
+    pub reasons: Vec<String>,
+    /// Why the author's bump disagrees with the changes
+    pub message: Option<String>,
+}
+
+/// `major.minor.patch`, ignoring a leading `v`, pre-release and build metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl Version {
+    fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let core = version.split(['-', '+']).next()?;
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        match parts.as_slice() {
+            [major] => Some(Self::new(*major, 0, 0)),
+            [major, minor] => Some(Self::new(*major, *minor, 0)),
+            [major, minor, patch] => Some(Self::new(*major, *minor, *patch)),
+            _ => None,
+        }
+    }
+
+    fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    fn bump(self, bump: VersionBump) -> Self {
+        match bump {
+            VersionBump::None => self,
+            VersionBump::Patch => Self::new(self.major, self.minor, self.patch + 1),
+            VersionBump::Minor => Self::new(self.major, self.minor + 1, 0),
+            VersionBump::Major => Self::new(self.major + 1, 0, 0),
+        }
+    }
+
+    /// Smallest bump from `self` that satisfies `required`; before 1.0.0
+    /// a minor bump is enough for breaking changes
+    fn sufficient_bump(self, required: VersionBump) -> VersionBump {
+        if self.major == 0 && required == VersionBump::Major {
+            VersionBump::Minor
+        } else {
+            required
+        }
+    }
+
+    /// Bump that leads from `self` to `newer`, or `None` for a downgrade
+    fn bump_to(self, newer: Self) -> Option<VersionBump> {
+        if newer < self {
+            None
+        } else if newer.major != self.major {
+            Some(VersionBump::Major)
+        } else if newer.minor != self.minor {
+            Some(VersionBump::Minor)
+        } else if newer.patch != self.patch {
+            Some(VersionBump::Patch)
+        } else {
+            Some(VersionBump::None)
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Semantic versioning advisor
+pub struct VersionAdvisor;
+
+impl VersionAdvisor {
+    /// Recommend a bump for `diff` (after rule configuration is applied):
+    /// error-severity breaking changes need a major bump, additions,
+    /// deprecations and warnings a minor one, anything else a patch. Before
+    /// 1.0.0 a minor bump is enough for breaking changes
+    pub fn recommend(
+        diff: &SpecDiff,
+        old_version: &str,
+        new_version: &str,
+    ) -> VersionRecommendation {
+        let (required_bump, reasons) = Self::required_bump(diff);
+        let old = Version::parse(old_version);
+        let new = Version::parse(new_version);
+
+        let actual_bump = old.zip(new).and_then(|(old, new)| old.bump_to(new));
+        let recommended_version =
+            old.map(|old| old.bump(old.sufficient_bump(required_bump)).to_string());
+
+        let message = match (old, new, actual_bump) {
+            (None, _, _) => Some(format!("'{old_version}' is not a semantic version")),
+            (_, None, _) => Some(format!("'{new_version}' is not a semantic version")),
+            (_, _, None) => Some(format!(
+                "Version went backwards from {old_version} to {new_version}"
+            )),
+            (Some(old), _, Some(actual)) => {
+                let sufficient = old.sufficient_bump(required_bump);
+                (actual < sufficient).then(|| {
+                    format!(
+                        "Changes require a {} bump but the version changed {old_version} -> {new_version} ({})",
+                        bump_name(sufficient),
+                        bump_name(actual)
+                    )
+                })
+            }
+        };
+
+        VersionRecommendation {
+            old_version: old_version.to_string(),
+            new_version: new_version.to_string(),
+            required_bump,
+            actual_bump,
+            recommended_version,
+            consistent: message.is_none(),
+            reasons,
+            message,
+        }
+    }
+
+    fn required_bump(diff: &SpecDiff) -> (VersionBump, Vec<String>) {
+        let count = |severity: Severity| {
+            diff.breaking_changes
+                .iter()
+                .filter(|c| c.severity == severity)
+                .count()
+        };
+
+        let errors = count(Severity::Error);
+        if errors > 0 {
+            return (
+                VersionBump::Major,
+                vec![format!("{errors} breaking change(s) with error severity")],
+            );
+        }
+
+        let mut reasons = Vec::new();
+        let warnings = count(Severity::Warning);
+        if warnings > 0 {
+            reasons.push(format!(
+                "{warnings} breaking change(s) with warning severity"
+            ));
+        }
+        for (count, what) in [
+            (diff.added_endpoints.len(), "added endpoint(s)"),
+            (diff.added_webhooks.len(), "added webhook(s)"),
+            (diff.added_schemas.len(), "added schema(s)"),
+        ] {
+            if count > 0 {
+                reasons.push(format!("{count} {what}"));
+            }
+        }
+        let added_fields = diff
+            .modified_schemas
+            .iter()
+            .flat_map(|s| &s.field_changes)
+            .filter(|c| !c.breaking && c.kind == SchemaFieldChangeKind::PropertyAdded)
+            .count();
+        if added_fields > 0 {
+            reasons.push(format!("{added_fields} added schema field(s)"));
+        }
+        let added_operation_features = diff
+            .modified_endpoints
+            .iter()
+            .flat_map(|e| &e.changes)
+            .filter(|c| c.starts_with("Added "))
+            .count();
+        if added_operation_features > 0 {
+            reasons.push(format!(
+                "{added_operation_features} added parameter(s) or response(s)"
+            ));
+        }
+        if !diff.deprecations.is_empty() {
+            reasons.push(format!("{} deprecation(s)", diff.deprecations.len()));
+        }
+        if !reasons.is_empty() {
+            return (VersionBump::Minor, reasons);
+        }
+
+        let modified = diff.modified_endpoints.len()
+            + diff.modified_webhooks.len()
+            + diff.modified_schemas.len()
+            + diff.renamed_schemas.len()
+            + diff.breaking_changes.len();
+        if modified > 0 {
+            return (
+                VersionBump::Patch,
+                vec![format!("{modified} compatible change(s)")],
+            );
+        }
+
+        (VersionBump::None, vec![])
+    }
+}
+
+fn bump_name(bump: VersionBump) -> &'static str {
+    match bump {
+        VersionBump::None => "no",
+        VersionBump::Patch => "patch",
+        VersionBump::Minor => "minor",
+        VersionBump::Major => "major",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::DiffEngine;
+    use crate::services::diff::tests::spec;
+    use crate::types::ParsedSpec;
+
+    fn paths_spec(paths: &[&str]) -> ParsedSpec {
+        let paths: serde_json::Map<_, _> = paths
+            .iter()
+            .map(|path| {
+                let operation = serde_json::json!({
+                    "get": { "responses": { "200": { "description": "ok" } } }
+                });
+                (path.to_string(), operation)
+            })
+            .collect();
+        spec(paths.into(), serde_json::json!({}))
+    }
+
+    #[test]
+    fn test_version_parsing() {
+        assert_eq!(
+            Version::parse("v1.2.3-beta.1+42"),
+            Some(Version::new(1, 2, 3))
+        );
+        assert_eq!(Version::parse("2.1"), Some(Version::new(2, 1, 0)));
+        assert_eq!(Version::parse("2024-01-15"), Some(Version::new(2024, 0, 0)));
+        assert_eq!(Version::parse("latest"), None);
+        assert_eq!(Version::parse("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn test_bump_recommendation() {
+        let base = paths_spec(&["/pets", "/owners"]);
+
+        // Removing an endpoint is breaking: a minor bump is not enough
+        let removed = DiffEngine::diff(&base, &paths_spec(&["/pets"]), None);
+        let advice = VersionAdvisor::recommend(&removed, "1.4.2", "1.5.0");
+        assert_eq!(advice.required_bump, VersionBump::Major);
+        assert_eq!(advice.actual_bump, Some(VersionBump::Minor));
+        assert_eq!(advice.recommended_version.as_deref(), Some("2.0.0"));
+        assert!(!advice.consistent);
+
+        // Before 1.0.0 breaking changes only need a minor bump
+        let advice = VersionAdvisor::recommend(&removed, "0.3.0", "0.4.0");
+        assert!(advice.consistent);
+        assert_eq!(advice.recommended_version.as_deref(), Some("0.4.0"));
+
+        let added = DiffEngine::diff(&base, &paths_spec(&["/pets", "/owners", "/vets"]), None);
+        let advice = VersionAdvisor::recommend(&added, "1.4.2", "1.4.3");
+        assert_eq!(advice.required_bump, VersionBump::Minor);
+        assert!(!advice.consistent);
+        assert!(VersionAdvisor::recommend(&added, "1.4.2", "2.0.0").consistent);
+
+        let unchanged = DiffEngine::diff(&base, &base, None);
+        let advice = VersionAdvisor::recommend(&unchanged, "1.0.0", "1.0.0");
+        assert_eq!(advice.required_bump, VersionBump::None);
+        assert!(advice.consistent);
+
+        let advice = VersionAdvisor::recommend(&unchanged, "1.0.0", "0.9.0");
+        assert_eq!(advice.actual_bump, None);
+        assert!(!advice.consistent);
+    }
+}
@@ -0,0 +1,393 @@
+//! Source positions of spec document nodes, keyed by JSON pointer
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 1-based line and column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourcePosition {
+    const START: Self = Self { line: 1, column: 1 };
+}
+
+/// JSON pointer → position of the member key (or array item, or root value)
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    positions: HashMap<String, SourcePosition>,
+}
+
+/// Raw spec document with source positions, before bundling
+#[derive(Debug, Clone)]
+pub struct SourceDocument {
+    pub source: String,
+    pub value: serde_json::Value,
+    pub positions: SourceMap,
+}
+
+impl SourceMap {
+    /// Scan JSON or block-style YAML. Flow collections in YAML are not
+    /// descended into; their nodes resolve to the enclosing key
+    pub fn scan(content: &str) -> Self {
+        if content.trim_start().starts_with(['{', '[']) {
+            JsonScanner::scan(content)
+        } else {
+            YamlScanner::scan(content)
+        }
+    }
+
+    /// Position of `pointer`, or of its nearest ancestor present in the source
+    pub fn locate(&self, pointer: &str) -> SourcePosition {
+        let mut pointer = pointer;
+        loop {
+            if let Some(position) = self.positions.get(pointer) {
+                return *position;
+            }
+            match pointer.rfind('/') {
+                Some(index) => pointer = &pointer[..index],
+                None => return SourcePosition::START,
+            }
+        }
+    }
+
+    fn record(&mut self, pointer: &str, position: SourcePosition) {
+        self.positions
+            .entry(pointer.to_string())
+            .or_insert(position);
+    }
+}
+
+/// Escape a reference token (RFC 6901)
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+struct JsonScanner<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    position: SourcePosition,
+    map: SourceMap,
+}
+
+impl JsonScanner<'_> {
+    fn scan(content: &str) -> SourceMap {
+        let mut scanner = JsonScanner {
+            chars: content.chars().peekable(),
+            position: SourcePosition::START,
+            map: SourceMap::default(),
+        };
+        scanner.value("");
+        scanner.map
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    /// Scan one value; returns false on malformed input
+    fn value(&mut self, pointer: &str) -> bool {
+        self.skip_whitespace();
+        self.map.record(pointer, self.position);
+        match self.chars.peek() {
+            Some('{') => self.object(pointer),
+            Some('[') => self.array(pointer),
+            Some('"') => self.string().is_some(),
+            Some(_) => {
+                while self
+                    .chars
+                    .peek()
+                    .is_some_and(|c| !matches!(c, ',' | '}' | ']') && !c.is_whitespace())
+                {
+                    self.next();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn object(&mut self, pointer: &str) -> bool {
+        self.next();
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some('}') => {
+                    self.next();
+                    return true;
+                }
+                Some(',') => {
+                    self.next();
+                }
+                Some('"') => {
+                    let position = self.position;
+                    let Some(key) = self.string() else {
+                        return false;
+                    };
+                    let member = format!("{pointer}/{}", escape_pointer_token(&key));
+                    self.map.record(&member, position);
+                    self.skip_whitespace();
+                    if self.next() != Some(':') || !self.value(&member) {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    fn array(&mut self, pointer: &str) -> bool {
+        self.next();
+        let mut index = 0;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(']') => {
+                    self.next();
+                    return true;
+                }
+                Some(',') => {
+                    self.next();
+                }
+                Some(_) => {
+                    if !self.value(&format!("{pointer}/{index}")) {
+                        return false;
+                    }
+                    index += 1;
+                }
+                None => return false,
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.next();
+        let mut raw = String::from("\"");
+        loop {
+            let c = self.next()?;
+            raw.push(c);
+            match c {
+                '"' => break,
+                '\\' => raw.push(self.next()?),
+                _ => {}
+            }
+        }
+        serde_json::from_str(&raw).ok()
+    }
+}
+
+/// Frame of the block-structure stack: a mapping or sequence starting at `indent`
+struct YamlFrame {
+    indent: usize,
+    pointer: String,
+    sequence: bool,
+    next_index: usize,
+}
+
+struct YamlScanner;
+
+impl YamlScanner {
+    fn scan(content: &str) -> SourceMap {
+        let mut map = SourceMap::default();
+        let mut stack = vec![YamlFrame {
+            indent: 0,
+            pointer: String::new(),
+            sequence: false,
+            next_index: 0,
+        }];
+        // Key (or item) whose value starts on a following line
+        let mut pending: Option<(usize, String)> = None;
+        // Indent of a block scalar (`|`, `>`) whose lines are skipped
+        let mut block_scalar: Option<usize> = None;
+
+        for (number, line) in content.lines().enumerate() {
+            let rest = line.trim_start_matches(' ');
+            let indent = line.len() - rest.len();
+            if rest.is_empty() || rest.starts_with('#') {
+                continue;
+            }
+            if let Some(scalar_indent) = block_scalar {
+                if indent > scalar_indent {
+                    continue;
+                }
+                block_scalar = None;
+            }
+            if indent == 0 && (rest.starts_with("---") || rest.starts_with("...")) {
+                continue;
+            }
+
+            if let Some((parent_indent, pointer)) = pending.take() {
+                let sequence = rest.starts_with('-');
+                if indent > parent_indent || (indent == parent_indent && sequence) {
+                    stack.push(YamlFrame {
+                        indent,
+                        pointer,
+                        sequence,
+                        next_index: 0,
+                    });
+                }
+            }
+            while stack.len() > 1 {
+                let top = &stack[stack.len() - 1];
+                if top.indent > indent
+                    || (top.indent == indent && top.sequence && !rest.starts_with('-'))
+                {
+                    stack.pop();
+                } else {
+                    break;
+                }
+            }
+
+            let line = number + 1;
+            let mut column = indent;
+            let mut rest = rest;
+
+            // Sequence items, possibly nested on one line (`- - a`)
+            while rest == "-" || rest.starts_with("- ") {
+                let top = stack.last_mut().unwrap();
+                let item = format!("{}/{}", top.pointer, top.next_index);
+                top.next_index += 1;
+                map.record(
+                    &item,
+                    SourcePosition {
+                        line,
+                        column: column + 1,
+                    },
+                );
+
+                let after = rest[1..].trim_start_matches(' ');
+                column += rest.len() - after.len();
+                rest = after;
+                if rest.is_empty() {
+                    pending = Some((indent, item));
+                    break;
+                }
+                stack.push(YamlFrame {
+                    indent: column,
+                    pointer: item,
+                    sequence: rest.starts_with("- "),
+                    next_index: 0,
+                });
+            }
+            if rest.is_empty() {
+                continue;
+            }
+
+            let Some((key, value)) = Self::split_key(rest) else {
+                continue;
+            };
+            let parent = &stack.last().unwrap().pointer;
+            let pointer = format!("{parent}/{}", escape_pointer_token(&key));
+            map.record(
+                &pointer,
+                SourcePosition {
+                    line,
+                    column: column + 1,
+                },
+            );
+
+            let value = value.trim();
+            let value = match value.find(" #") {
+                Some(index) if !value.starts_with(['"', '\'']) => value[..index].trim(),
+                _ => value,
+            };
+            if value.starts_with(['|', '>']) {
+                block_scalar = Some(column);
+            } else if value.is_empty() || value.starts_with(['&', '!']) && !value.contains(' ') {
+                pending = Some((column, pointer));
+            }
+        }
+
+        map
+    }
+
+    /// Split `key: value` (or `key:`), unquoting the key
+    fn split_key(text: &str) -> Option<(String, &str)> {
+        if let Some(quote) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let end = text[1..].find(quote)? + 1;
+            let key = &text[1..end];
+            let rest = text[end + 1..].trim_start_matches(' ');
+            let value = rest.strip_prefix(':')?;
+            let key = if quote == '"' {
+                serde_json::from_str(&text[..=end]).unwrap_or_else(|_| key.to_string())
+            } else {
+                key.replace("''", "'")
+            };
+            return Some((key, value));
+        }
+
+        if let Some(key) = text.strip_suffix(':') {
+            return Some((key.trim_end().to_string(), ""));
+        }
+        let index = text.find(": ")?;
+        Some((text[..index].trim_end().to_string(), &text[index + 1..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_positions() {
+        let map = SourceMap::scan(
+            "{\n  \"paths\": {\n    \"/pets/{id}\": {\n      \"get\": { \"tags\": [\"a\", \"b\"] }\n    }\n  }\n}",
+        );
+        let at = |line, column| SourcePosition { line, column };
+
+        assert_eq!(map.locate("/paths"), at(2, 3));
+        assert_eq!(map.locate("/paths/~1pets~1{id}/get"), at(4, 7));
+        assert_eq!(map.locate("/paths/~1pets~1{id}/get/tags/1"), at(4, 30));
+        // Missing nodes resolve to the nearest ancestor
+        assert_eq!(map.locate("/paths/~1pets~1{id}/post/responses"), at(3, 5));
+        assert_eq!(map.locate("/webhooks"), at(1, 1));
+    }
+
+    #[test]
+    fn test_yaml_positions() {
+        let map = SourceMap::scan(
+            "openapi: 3.0.3
+info:
+  title: Pets # comment
+  description: |
+    not: a key
+paths:
+  /pets:
+    get:
+      parameters:
+      - name: limit
+        in: query
+      - $ref: '#/components/parameters/Offset'
+      responses:
+        '200':
+          description: ok
+",
+        );
+        let at = |line, column| SourcePosition { line, column };
+
+        assert_eq!(map.locate("/info/title"), at(3, 3));
+        assert_eq!(map.locate("/info/description/not"), at(4, 3));
+        assert_eq!(map.locate("/paths/~1pets/get/parameters/0"), at(10, 7));
+        assert_eq!(map.locate("/paths/~1pets/get/parameters/0/in"), at(11, 9));
+        assert_eq!(map.locate("/paths/~1pets/get/parameters/1/$ref"), at(12, 9));
+        assert_eq!(map.locate("/paths/~1pets/get/responses"), at(13, 7));
+        assert_eq!(
+            map.locate("/paths/~1pets/get/responses/200/description"),
+            at(15, 11)
+        );
+    }
+}
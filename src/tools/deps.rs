@@ -3,6 +3,7 @@
 use crate::services::{CacheManager, GraphBuilder, OpenApiParser};
 use crate::types::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 
 #[derive(Debug, Deserialize)]
 pub struct DepsInput {
@@ -12,25 +13,34 @@ pub struct DepsInput {
     pub schema: Option<String>,
     /// Path to check (mutually exclusive with schema)
     pub path: Option<String>,
-    /// Direction: upstream, downstream, or both
+    /// Direction: upstream, downstream, or both (default: downstream for
+    /// schemas, upstream for paths)
     #[serde(default)]
-    pub direction: DepsDirection,
+    pub direction: Option<DepsDirection>,
+    /// Stop after this many hops from the target
+    pub max_depth: Option<usize>,
     /// Project directory for caching
     pub project_dir: Option<String>,
     /// Whether to use cache (default: true when project_dir is provided)
     #[serde(default = "default_true")]
     pub use_cache: bool,
+    /// List every reference chain instead of the shortest one per result
+    #[serde(default)]
+    pub all_chains: bool,
+    /// Report schema reference cycles (those involving the target, or all
+    /// of them when no target is given)
+    #[serde(default)]
+    pub cycles: bool,
 }
 
 fn default_true() -> bool {
     true
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DepsDirection {
     Upstream,
-    #[default]
     Downstream,
     Both,
 }
@@ -43,13 +53,20 @@ pub struct DepsOutput {
     pub affected_paths: Vec<String>,
     pub affected_schemas: Vec<String>,
     pub total_affected: usize,
+    /// Hops from the target to each result
+    pub depths: BTreeMap<String, usize>,
+    /// Reference chains explaining each result
+    pub dependency_chain: Vec<DependencyChain>,
+    /// Schema reference cycles, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycles: Option<Vec<SchemaCycle>>,
     pub error: Option<String>,
 }
 
 /// Query dependency graph
 pub async fn query_deps(input: DepsInput) -> DepsOutput {
     // Validate input
-    if input.schema.is_none() && input.path.is_none() {
+    if input.schema.is_none() && input.path.is_none() && !input.cycles {
         return DepsOutput {
             success: false,
             target: String::new(),
@@ -57,7 +74,10 @@ pub async fn query_deps(input: DepsInput) -> DepsOutput {
             affected_paths: vec![],
             affected_schemas: vec![],
             total_affected: 0,
-            error: Some("Either 'schema' or 'path' must be provided".to_string()),
+            depths: BTreeMap::new(),
+            dependency_chain: vec![],
+            cycles: None,
+            error: Some("Either 'schema', 'path' or 'cycles' must be provided".to_string()),
         };
     }
 
@@ -69,6 +89,9 @@ pub async fn query_deps(input: DepsInput) -> DepsOutput {
             affected_paths: vec![],
             affected_schemas: vec![],
             total_affected: 0,
+            depths: BTreeMap::new(),
+            dependency_chain: vec![],
+            cycles: None,
             error: Some("Cannot specify both 'schema' and 'path'".to_string()),
         };
     }
@@ -86,6 +109,9 @@ pub async fn query_deps(input: DepsInput) -> DepsOutput {
                     affected_paths: vec![],
                     affected_schemas: vec![],
                     total_affected: 0,
+                    depths: BTreeMap::new(),
+                    dependency_chain: vec![],
+                    cycles: None,
                     error: Some(e.to_string()),
                 };
             }
@@ -101,6 +127,9 @@ pub async fn query_deps(input: DepsInput) -> DepsOutput {
                     affected_paths: vec![],
                     affected_schemas: vec![],
                     total_affected: 0,
+                    depths: BTreeMap::new(),
+                    dependency_chain: vec![],
+                    cycles: None,
                     error: Some(e.to_string()),
                 };
             }
@@ -110,26 +139,73 @@ pub async fn query_deps(input: DepsInput) -> DepsOutput {
     // Build dependency graph
     let graph = GraphBuilder::build(&spec);
 
-    let (target, is_schema) = if let Some(ref schema) = input.schema {
-        (schema.clone(), true)
-    } else {
-        (input.path.unwrap(), false)
+    let (target, is_schema) = match (input.schema, input.path) {
+        (Some(schema), _) => (schema, true),
+        (None, Some(path)) => (path, false),
+        (None, None) => {
+            return DepsOutput {
+                success: true,
+                target: String::new(),
+                is_schema: false,
+                affected_paths: vec![],
+                affected_schemas: vec![],
+                total_affected: 0,
+                depths: BTreeMap::new(),
+                dependency_chain: vec![],
+                cycles: Some(graph.cycles()),
+                error: None,
+            };
+        }
     };
 
-    // Convert direction
+    // Convert direction; paths default to the schemas they depend on
     let direction = match input.direction {
-        DepsDirection::Upstream => DependencyDirection::Upstream,
-        DepsDirection::Downstream => DependencyDirection::Downstream,
-        DepsDirection::Both => DependencyDirection::Both,
+        Some(DepsDirection::Upstream) => DependencyDirection::Upstream,
+        Some(DepsDirection::Downstream) => DependencyDirection::Downstream,
+        Some(DepsDirection::Both) => DependencyDirection::Both,
+        None if is_schema => DependencyDirection::Downstream,
+        None => DependencyDirection::Upstream,
     };
 
     // Query the graph
-    let result = graph.query(&target, direction, is_schema);
+    let result = graph.query(
+        &target,
+        direction,
+        is_schema,
+        input.all_chains,
+        input.max_depth,
+    );
 
-    let affected_paths: Vec<String> = result.affected_paths.into_iter().collect();
-    let affected_schemas: Vec<String> = result.affected_schemas.into_iter().collect();
+    // Nearest results first
+    let by_depth = |names: HashSet<String>| {
+        let mut names: Vec<String> = names.into_iter().collect();
+        names.sort_by(|a, b| {
+            result.depths[a]
+                .cmp(&result.depths[b])
+                .then_with(|| a.cmp(b))
+        });
+        names
+    };
+    let affected_paths = by_depth(result.affected_paths);
+    let affected_schemas = by_depth(result.affected_schemas);
     let total = affected_paths.len() + affected_schemas.len();
 
+    let cycles = input.cycles.then(|| {
+        graph
+            .cycles()
+            .into_iter()
+            .filter(|cycle| {
+                cycle.schemas.iter().any(|schema| {
+                    if is_schema {
+                        *schema == target
+                    } else {
+                        affected_schemas.contains(schema)
+                    }
+                })
+            })
+            .collect()
+    });
+
     DepsOutput {
         success: true,
         target,
@@ -137,6 +213,9 @@ pub async fn query_deps(input: DepsInput) -> DepsOutput {
         affected_paths,
         affected_schemas,
         total_affected: total,
+        depths: result.depths,
+        dependency_chain: result.dependency_chain,
+        cycles,
         error: None,
     }
 }
@@ -1,7 +1,13 @@
 //! oas_diff tool implementation
 
-use crate::services::{CacheManager, DiffEngine, GraphBuilder, OpenApiParser, SpecDiff};
+use crate::services::{
+    CacheManager, ChangelogFormat, ChangelogRenderer, DiffEngine, DiffReporter, GraphBuilder,
+    OpenApiParser, ReportFormat, SpecDiff, VersionAdvisor, VersionRecommendation,
+};
+use crate::types::{DiffConfig, RuleConfig, Severity};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 
 #[derive(Debug, Deserialize)]
 pub struct DiffInput {
@@ -20,6 +26,18 @@ pub struct DiffInput {
     /// Whether to use cache (default: true when project_dir is provided)
     #[serde(default = "default_true")]
     pub use_cache: bool,
+    /// Rule overrides layered over the `diff` section of `.openapi-sync.json`
+    #[serde(default)]
+    pub rules: HashMap<String, RuleConfig>,
+    /// Also render a human-readable changelog
+    pub changelog_format: Option<ChangelogFormat>,
+    /// Handlebars template file replacing the built-in changelog template
+    pub changelog_template: Option<String>,
+    /// Docs URL prefixed to changelog operation and schema anchors
+    pub docs_url: Option<String>,
+    /// Machine-readable reports to produce
+    #[serde(default)]
+    pub reports: Vec<ReportFormat>,
 }
 
 fn default_true() -> bool {
@@ -31,23 +49,76 @@ pub struct DiffOutput {
     pub success: bool,
     pub summary: Option<DiffSummary>,
     pub diff: Option<SpecDiff>,
+    /// Rendered changelog, when `changelog_format` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<String>,
+    /// Requested machine-readable reports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reports: Option<DiffReports>,
     pub error: Option<String>,
 }
 
+impl DiffOutput {
+    fn failure(error: String) -> Self {
+        Self {
+            success: false,
+            summary: None,
+            diff: None,
+            changelog: None,
+            reports: None,
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DiffReports {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sarif: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub junit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_patch: Option<Vec<serde_json::Value>>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct DiffSummary {
     pub added_endpoints: usize,
     pub modified_endpoints: usize,
     pub removed_endpoints: usize,
+    pub moved_endpoints: usize,
+    pub added_webhooks: usize,
+    pub modified_webhooks: usize,
+    pub removed_webhooks: usize,
     pub added_schemas: usize,
     pub modified_schemas: usize,
     pub removed_schemas: usize,
+    pub renamed_schemas: usize,
+    /// Newly deprecated (or re-dated) operations, parameters, schemas and properties
+    pub deprecations: usize,
     pub breaking_changes: usize,
+    /// Breaking changes per severity
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    /// True when any breaking change has `error` severity
     pub has_breaking_changes: bool,
+    /// Semantic version bump the changes require, checked against `info.version`
+    pub version: VersionRecommendation,
 }
 
 /// Compare two OpenAPI specs
 pub async fn diff_specs(input: DiffInput) -> DiffOutput {
+    // Rule configuration: project file, then inline overrides
+    let file_config = match &input.project_dir {
+        Some(project_dir) => match CacheManager::new(project_dir).load_diff_config() {
+            Ok(config) => config,
+            Err(e) => return DiffOutput::failure(e.to_string()),
+        },
+        None => DiffConfig::default(),
+    };
+    let rules = file_config.merged_with(&input.rules);
+
     // Create cache manager if project_dir provided
     let cache_manager =
         if let (true, Some(project_dir)) = (input.use_cache, input.project_dir.as_ref()) {
@@ -60,26 +131,12 @@ pub async fn diff_specs(input: DiffInput) -> DiffOutput {
     let old_spec = if let Some(ref cm) = cache_manager {
         match cm.parse_with_cache(&input.old_source, None).await {
             Ok(s) => s,
-            Err(e) => {
-                return DiffOutput {
-                    success: false,
-                    summary: None,
-                    diff: None,
-                    error: Some(format!("Failed to parse old spec: {e}")),
-                };
-            }
+            Err(e) => return DiffOutput::failure(format!("Failed to parse old spec: {e}")),
         }
     } else {
         match OpenApiParser::parse(&input.old_source).await {
             Ok(s) => s,
-            Err(e) => {
-                return DiffOutput {
-                    success: false,
-                    summary: None,
-                    diff: None,
-                    error: Some(format!("Failed to parse old spec: {e}")),
-                };
-            }
+            Err(e) => return DiffOutput::failure(format!("Failed to parse old spec: {e}")),
         }
     };
 
@@ -87,26 +144,12 @@ pub async fn diff_specs(input: DiffInput) -> DiffOutput {
     let new_spec = if let Some(ref cm) = cache_manager {
         match cm.parse_with_cache(&input.new_source, None).await {
             Ok(s) => s,
-            Err(e) => {
-                return DiffOutput {
-                    success: false,
-                    summary: None,
-                    diff: None,
-                    error: Some(format!("Failed to parse new spec: {e}")),
-                };
-            }
+            Err(e) => return DiffOutput::failure(format!("Failed to parse new spec: {e}")),
         }
     } else {
         match OpenApiParser::parse(&input.new_source).await {
             Ok(s) => s,
-            Err(e) => {
-                return DiffOutput {
-                    success: false,
-                    summary: None,
-                    diff: None,
-                    error: Some(format!("Failed to parse new spec: {e}")),
-                };
-            }
+            Err(e) => return DiffOutput::failure(format!("Failed to parse new spec: {e}")),
         }
     };
 
@@ -118,17 +161,79 @@ pub async fn diff_specs(input: DiffInput) -> DiffOutput {
     };
 
     // Compute diff
-    let diff = DiffEngine::diff(&old_spec, &new_spec, graph.as_ref());
+    let mut diff = DiffEngine::diff(&old_spec, &new_spec, graph.as_ref());
+    if let Err(e) = DiffEngine::apply_rules(&mut diff, &rules) {
+        return DiffOutput::failure(e.to_string());
+    }
+
+    let count = |severity: Severity| {
+        diff.breaking_changes
+            .iter()
+            .filter(|c| c.severity == severity)
+            .count()
+    };
+    let errors = count(Severity::Error);
 
     let summary = DiffSummary {
         added_endpoints: diff.added_endpoints.len(),
         modified_endpoints: diff.modified_endpoints.len(),
         removed_endpoints: diff.removed_endpoints.len(),
+        moved_endpoints: diff.moved_endpoints.len(),
+        added_webhooks: diff.added_webhooks.len(),
+        modified_webhooks: diff.modified_webhooks.len(),
+        removed_webhooks: diff.removed_webhooks.len(),
         added_schemas: diff.added_schemas.len(),
         modified_schemas: diff.modified_schemas.len(),
         removed_schemas: diff.removed_schemas.len(),
+        renamed_schemas: diff.renamed_schemas.len(),
+        deprecations: diff.deprecations.len(),
         breaking_changes: diff.breaking_changes.len(),
-        has_breaking_changes: !diff.breaking_changes.is_empty(),
+        errors,
+        warnings: count(Severity::Warning),
+        infos: count(Severity::Info),
+        has_breaking_changes: errors > 0,
+        version: VersionAdvisor::recommend(
+            &diff,
+            &old_spec.metadata.version,
+            &new_spec.metadata.version,
+        ),
+    };
+
+    let changelog = match input.changelog_format {
+        Some(format) => {
+            let template = match &input.changelog_template {
+                Some(path) => match std::fs::read_to_string(path) {
+                    Ok(template) => Some(template),
+                    Err(e) => {
+                        return DiffOutput::failure(format!(
+                            "Failed to read changelog template {path}: {e}"
+                        ));
+                    }
+                },
+                None => None,
+            };
+            match ChangelogRenderer::render(
+                &diff,
+                &old_spec.metadata,
+                &new_spec.metadata,
+                format,
+                template.as_deref(),
+                input.docs_url.as_deref(),
+            ) {
+                Ok(changelog) => Some(changelog),
+                Err(e) => return DiffOutput::failure(e.to_string()),
+            }
+        }
+        None => None,
+    };
+
+    let reports = if input.reports.is_empty() {
+        None
+    } else {
+        match build_reports(&input, &diff, &new_spec.metadata.title).await {
+            Ok(reports) => Some(reports),
+            Err(e) => return DiffOutput::failure(e),
+        }
     };
 
     // Filter to breaking only if requested
@@ -137,11 +242,18 @@ pub async fn diff_specs(input: DiffInput) -> DiffOutput {
             added_endpoints: vec![],
             modified_endpoints: vec![],
             removed_endpoints: diff.removed_endpoints,
+            moved_endpoints: diff.moved_endpoints,
             unchanged_endpoints: 0,
+            added_webhooks: vec![],
+            modified_webhooks: vec![],
+            removed_webhooks: diff.removed_webhooks,
+            unchanged_webhooks: 0,
             added_schemas: vec![],
             modified_schemas: vec![],
             removed_schemas: diff.removed_schemas,
+            renamed_schemas: diff.renamed_schemas,
             unchanged_schemas: 0,
+            deprecations: vec![],
             breaking_changes: diff.breaking_changes,
         }
     } else {
@@ -152,6 +264,53 @@ pub async fn diff_specs(input: DiffInput) -> DiffOutput {
         success: true,
         summary: Some(summary),
         diff: Some(diff_output),
+        changelog,
+        reports,
         error: None,
     }
 }
+
+/// Build the requested reports from the raw documents (positions come from
+/// the new spec as written, before external refs are bundled)
+async fn build_reports(
+    input: &DiffInput,
+    diff: &SpecDiff,
+    title: &str,
+) -> Result<DiffReports, String> {
+    let needs_new = input
+        .reports
+        .iter()
+        .any(|r| matches!(r, ReportFormat::Sarif | ReportFormat::JsonPatch));
+    let new_document = if needs_new {
+        Some(
+            OpenApiParser::load_document(&input.new_source)
+                .await
+                .map_err(|e| format!("Failed to load new spec: {e}"))?,
+        )
+    } else {
+        None
+    };
+
+    // SARIF URIs are relative to the project, or the working directory
+    let base_dir = Path::new(input.project_dir.as_deref().unwrap_or("."));
+    let mut reports = DiffReports::default();
+    for format in &input.reports {
+        match format {
+            ReportFormat::Sarif => {
+                reports.sarif = new_document
+                    .as_ref()
+                    .map(|document| DiffReporter::sarif(diff, document, base_dir));
+            }
+            ReportFormat::Junit => reports.junit = Some(DiffReporter::junit(diff, title)),
+            ReportFormat::JsonPatch => {
+                let old_document = OpenApiParser::load_document(&input.old_source)
+                    .await
+                    .map_err(|e| format!("Failed to load old spec: {e}"))?;
+                reports.json_patch = new_document
+                    .as_ref()
+                    .map(|document| DiffReporter::json_patch(&old_document.value, &document.value));
+            }
+        }
+    }
+    Ok(reports)
+}
@@ -1,9 +1,12 @@
 //! oas_generate tool implementation - Hybrid code generation
 
 use crate::services::{CacheManager, GraphBuilder, OpenApiParser};
-use crate::types::{Endpoint, ParameterLocation, Schema, SchemaType};
+use crate::types::{
+    AdditionalSchema, Endpoint, ParameterLocation, ParsedSpec, Schema, SchemaConstraints,
+    SchemaCycle, SchemaType, SecurityScheme, Webhook, WebhookKind, preferred_media_type,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Deserialize)]
 pub struct GenerateInput {
@@ -106,6 +109,7 @@ pub enum FileType {
     Types,
     Client,
     Hooks,
+    Webhooks,
     Index,
 }
 
@@ -123,16 +127,56 @@ struct SimpleSchema {
     name: String,
     description: Option<String>,
     properties: Vec<SimpleProperty>,
+    /// Non-object schemas are emitted as type aliases
+    alias: Option<SchemaType>,
 }
 
 struct SimpleProperty {
     name: String,
-    schema_type: String,
-    format: Option<String>,
+    schema_type: SchemaType,
     required: bool,
     description: Option<String>,
-    is_array: bool,
-    is_ref: bool,
+}
+
+/// Webhook or callback the consumer implements a handler for
+struct SimpleWebhook {
+    handler: String,
+    summary: Option<String>,
+    payload_schema: Option<String>,
+    response_schema: Option<String>,
+}
+
+/// Spec-wide settings shared by the client generators
+struct SimpleClient {
+    /// First declared server URL, with variables substituted
+    default_base_url: Option<String>,
+    /// Credentials the client can be configured with
+    auth: Vec<AuthHook>,
+}
+
+/// Authentication hook derived from a security scheme
+#[derive(Clone, PartialEq)]
+enum AuthHook {
+    Bearer,
+    Basic,
+    /// Each API key scheme has its own credential
+    ApiKey {
+        scheme: String,
+        name: String,
+        location: ParameterLocation,
+    },
+}
+
+impl AuthHook {
+    /// Credential name, before the target's naming convention; endpoints
+    /// select the hooks they apply by it
+    fn credential(&self) -> &str {
+        match self {
+            Self::Bearer => "bearer_token",
+            Self::Basic => "basic",
+            Self::ApiKey { scheme, .. } => scheme,
+        }
+    }
 }
 
 struct SimpleEndpoint {
@@ -144,6 +188,20 @@ struct SimpleEndpoint {
     query_params: Vec<String>,
     request_body_schema: Option<String>,
     response_schema: Option<String>,
+    /// Anonymous schemas, hoisted into named types by `hoist_inline_schemas`
+    request_body_inline: Option<SchemaType>,
+    response_inline: Option<SchemaType>,
+    /// Preferred media type of the success response, sent as `Accept`
+    accept: Option<String>,
+    response_headers: Vec<SimpleHeader>,
+    /// Credentials this operation sends; empty for public operations
+    auth: Vec<AuthHook>,
+}
+
+/// Response header surfaced to callers as a typed field
+struct SimpleHeader {
+    name: String,
+    schema_type: Option<String>,
 }
 
 /// Generate code from OpenAPI spec with style configuration
@@ -186,18 +244,18 @@ pub async fn generate_code(input: GenerateInput) -> GenerateOutput {
         }
     };
 
-    // Build dependency graph for proper ordering (not currently used but available)
-    let _graph = GraphBuilder::build(&spec);
+    // Reference cycles need `Box<>` in Rust and model rebuilds in Python
+    let cycles = GraphBuilder::build(&spec).cycles();
 
     // Convert to simple views
-    let schemas: Vec<SimpleSchema> = spec
+    let mut schemas: Vec<SimpleSchema> = spec
         .schemas
         .iter()
         .filter(|(name, _)| input.schemas.is_empty() || input.schemas.contains(name))
         .map(|(_, schema)| simplify_schema(schema))
         .collect();
 
-    let endpoints: Vec<SimpleEndpoint> = spec
+    let mut endpoints: Vec<SimpleEndpoint> = spec
         .endpoints
         .iter()
         .filter(|(key, _)| {
@@ -206,50 +264,110 @@ pub async fn generate_code(input: GenerateInput) -> GenerateOutput {
             }
             input.endpoints.iter().any(|e| key.contains(e))
         })
-        .map(|(_, endpoint)| simplify_endpoint(endpoint))
+        .map(|(_, endpoint)| SimpleEndpoint {
+            // `security` already falls back to the root requirements
+            auth: auth_hooks(&spec, endpoint.security.iter().flat_map(|r| r.keys())),
+            ..simplify_endpoint(endpoint)
+        })
+        .collect();
+
+    let mut webhook_endpoints: Vec<SimpleEndpoint> = spec
+        .webhooks
+        .iter()
+        .filter(|(key, _)| {
+            if input.endpoints.is_empty() {
+                return true;
+            }
+            input.endpoints.iter().any(|e| key.contains(e))
+        })
+        .map(|(_, webhook)| simplify_webhook_endpoint(webhook))
+        .collect();
+
+    // Spec maps are unordered; sort so output is stable between runs
+    schemas.sort_by(|a, b| a.name.cmp(&b.name));
+    endpoints.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+    webhook_endpoints.sort_by(|a, b| a.operation_id.cmp(&b.operation_id));
+
+    hoist_inline_schemas(&mut endpoints, &mut schemas);
+    hoist_inline_schemas(&mut webhook_endpoints, &mut schemas);
+
+    let webhooks: Vec<SimpleWebhook> = webhook_endpoints
+        .into_iter()
+        .map(|endpoint| SimpleWebhook {
+            handler: endpoint.operation_id,
+            summary: endpoint.summary,
+            payload_schema: endpoint.request_body_schema,
+            response_schema: endpoint.response_schema,
+        })
         .collect();
 
+    let client = simplify_client(&spec);
+
     // Generate based on target
-    let generated_files = match input.target {
+    let mut generated_files = match input.target {
         GenerateTarget::TypescriptTypes => generate_typescript_types(&schemas, &input.style),
         GenerateTarget::TypescriptFetch => {
             let mut files = generate_typescript_types(&schemas, &input.style);
-            files.extend(generate_typescript_fetch_client(&endpoints, &input.style));
+            files.extend(generate_typescript_fetch_client(
+                &endpoints,
+                &client,
+                &input.style,
+            ));
             files.push(generate_index_ts());
             files
         }
         GenerateTarget::TypescriptAxios => {
             let mut files = generate_typescript_types(&schemas, &input.style);
-            files.extend(generate_typescript_axios_client(&endpoints, &input.style));
+            files.extend(generate_typescript_axios_client(
+                &endpoints,
+                &client,
+                &input.style,
+            ));
             files.push(generate_index_ts());
             files
         }
         GenerateTarget::TypescriptReactQuery => {
             let mut files = generate_typescript_types(&schemas, &input.style);
-            files.extend(generate_typescript_fetch_client(&endpoints, &input.style));
+            files.extend(generate_typescript_fetch_client(
+                &endpoints,
+                &client,
+                &input.style,
+            ));
             files.extend(generate_react_query_hooks(&endpoints, &input.style));
             files.push(generate_index_ts_with_hooks());
             files
         }
-        GenerateTarget::RustSerde => generate_rust_types(&schemas, &input.style),
+        GenerateTarget::RustSerde => generate_rust_types(&schemas, &cycles, &input.style),
         GenerateTarget::RustReqwest => {
-            let mut files = generate_rust_types(&schemas, &input.style);
-            files.extend(generate_rust_reqwest_client(&endpoints, &input.style));
+            let mut files = generate_rust_types(&schemas, &cycles, &input.style);
+            files.extend(generate_rust_reqwest_client(
+                &endpoints,
+                &client,
+                &input.style,
+            ));
             files.push(generate_rust_mod());
             files
         }
-        GenerateTarget::PythonPydantic => generate_python_types(&schemas, &input.style),
+        GenerateTarget::PythonPydantic => generate_python_types(&schemas, &cycles, &input.style),
         GenerateTarget::PythonHttpx => {
-            let mut files = generate_python_types(&schemas, &input.style);
-            files.extend(generate_python_httpx_client(&endpoints, &input.style));
+            let mut files = generate_python_types(&schemas, &cycles, &input.style);
+            files.extend(generate_python_httpx_client(
+                &endpoints,
+                &client,
+                &input.style,
+            ));
             files.push(generate_python_init());
             files
         }
     };
 
+    if !webhooks.is_empty() {
+        add_webhook_handlers(&mut generated_files, &input.target, &webhooks, &input.style);
+    }
+
     let summary = GenerateSummary {
         types_generated: schemas.len(),
-        endpoints_generated: endpoints.len(),
+        endpoints_generated: endpoints.len() + webhooks.len(),
         files_created: generated_files.len(),
         target: format!("{:?}", input.target),
     };
@@ -265,12 +383,31 @@ pub async fn generate_code(input: GenerateInput) -> GenerateOutput {
 // ===== Schema Simplification =====
 
 fn simplify_schema(schema: &Schema) -> SimpleSchema {
-    let properties = extract_properties(&schema.schema_type);
+    simple_schema(
+        &schema.name,
+        schema.description.clone(),
+        &schema.schema_type,
+    )
+}
+
+fn simple_schema(
+    name: &str,
+    description: Option<String>,
+    schema_type: &SchemaType,
+) -> SimpleSchema {
+    let properties = extract_properties(schema_type);
+
+    let alias = match schema_type {
+        SchemaType::Object { .. } | SchemaType::AllOf { .. } | SchemaType::Unknown => None,
+        _ if !properties.is_empty() => None,
+        other => Some(other.clone()),
+    };
 
     SimpleSchema {
-        name: schema.name.clone(),
-        description: schema.description.clone(),
+        name: name.to_string(),
+        description,
         properties,
+        alias,
     }
 }
 
@@ -279,43 +416,110 @@ fn extract_properties(schema_type: &SchemaType) -> Vec<SimpleProperty> {
         SchemaType::Object {
             properties,
             required,
-        } => properties
-            .iter()
-            .map(|(name, prop_type)| {
-                let (base_type, format, is_array, is_ref) = extract_type_info(prop_type);
-                SimpleProperty {
+            ..
+        } => {
+            let mut properties: Vec<SimpleProperty> = properties
+                .iter()
+                .map(|(name, prop_type)| SimpleProperty {
                     name: name.clone(),
-                    schema_type: base_type,
-                    format,
+                    schema_type: prop_type.clone(),
                     required: required.contains(name),
                     description: None,
-                    is_array,
-                    is_ref,
-                }
-            })
-            .collect(),
+                })
+                .collect();
+            properties.sort_by(|a, b| a.name.cmp(&b.name));
+            properties
+        }
         SchemaType::AllOf { variants } => {
             // Merge all properties from allOf variants
             variants.iter().flat_map(extract_properties).collect()
         }
+        SchemaType::Nullable { inner } => extract_properties(inner),
+        SchemaType::Conditional {
+            base: Some(base), ..
+        } => extract_properties(base),
         _ => vec![],
     }
 }
 
-fn extract_type_info(schema_type: &SchemaType) -> (String, Option<String>, bool, bool) {
+/// OpenAPI type name used for `type_mappings` lookups
+fn extract_type_info(schema_type: &SchemaType) -> Option<&str> {
     match schema_type {
-        SchemaType::String { format, .. } => ("string".to_string(), format.clone(), false, false),
-        SchemaType::Number { format } => ("number".to_string(), format.clone(), false, false),
-        SchemaType::Integer { format } => ("integer".to_string(), format.clone(), false, false),
-        SchemaType::Boolean => ("boolean".to_string(), None, false, false),
-        SchemaType::Array { items } => {
-            let (inner, format, _, is_ref) = extract_type_info(items);
-            (inner, format, true, is_ref)
+        SchemaType::String { .. } => Some("string"),
+        SchemaType::Number { .. } => Some("number"),
+        SchemaType::Integer { .. } => Some("integer"),
+        SchemaType::Boolean { .. } => Some("boolean"),
+        SchemaType::Ref { reference } => Some(reference),
+        SchemaType::Object { .. } | SchemaType::Map { .. } => Some("object"),
+        _ => None,
+    }
+}
+
+/// Simplified view of a webhook, keyed by the handler name instead of an operationId
+fn simplify_webhook_endpoint(webhook: &Webhook) -> SimpleEndpoint {
+    let mut endpoint = simplify_endpoint(&webhook.endpoint);
+    endpoint.operation_id = match (&webhook.endpoint.operation_id, webhook.kind) {
+        (Some(operation_id), _) => operation_id.clone(),
+        (None, WebhookKind::Webhook) => webhook.name.clone(),
+        (None, WebhookKind::Callback) => format!(
+            "{}_{}",
+            webhook.name,
+            webhook.endpoint.method.to_string().to_lowercase()
+        ),
+    };
+    endpoint
+}
+
+fn simplify_client(spec: &ParsedSpec) -> SimpleClient {
+    let default_base_url = spec.servers.first().map(|server| {
+        server
+            .variables
+            .iter()
+            .fold(server.url.clone(), |url, (name, default)| {
+                url.replace(&format!("{{{name}}}"), default)
+            })
+    });
+
+    SimpleClient {
+        default_base_url,
+        auth: auth_hooks(spec, spec.security_schemes.keys()),
+    }
+}
+
+/// Hooks for the named security schemes, in a stable order: bearer, basic,
+/// then API keys by scheme name
+fn auth_hooks<'a>(spec: &ParsedSpec, schemes: impl Iterator<Item = &'a String>) -> Vec<AuthHook> {
+    let mut names: Vec<&String> = schemes.collect();
+    names.sort();
+
+    let mut auth = Vec::new();
+    for name in names {
+        let hook = match spec.security_schemes.get(name) {
+            Some(SecurityScheme::Http { scheme, .. }) if scheme == "basic" => AuthHook::Basic,
+            Some(SecurityScheme::Http { scheme, .. }) if scheme == "bearer" => AuthHook::Bearer,
+            Some(SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIdConnect { .. }) => {
+                AuthHook::Bearer
+            }
+            Some(SecurityScheme::ApiKey {
+                name: key_name,
+                location,
+            }) => AuthHook::ApiKey {
+                scheme: name.clone(),
+                name: key_name.clone(),
+                location: *location,
+            },
+            _ => continue,
+        };
+        if !auth.contains(&hook) {
+            auth.push(hook);
         }
-        SchemaType::Ref { reference } => (reference.clone(), None, false, true),
-        SchemaType::Object { .. } => ("object".to_string(), None, false, false),
-        _ => ("unknown".to_string(), None, false, false),
     }
+    auth.sort_by_key(|hook| match hook {
+        AuthHook::Bearer => 0,
+        AuthHook::Basic => 1,
+        AuthHook::ApiKey { .. } => 2,
+    });
+    auth
 }
 
 fn simplify_endpoint(endpoint: &Endpoint) -> SimpleEndpoint {
@@ -333,16 +537,33 @@ fn simplify_endpoint(endpoint: &Endpoint) -> SimpleEndpoint {
         .map(|p| p.name.clone())
         .collect();
 
+    let request_body_media = endpoint
+        .request_body
+        .as_ref()
+        .and_then(|rb| preferred_media_type(&rb.content));
     let request_body_schema = endpoint
         .request_body
         .as_ref()
         .and_then(|rb| rb.schema_ref.clone());
 
-    let response_schema = endpoint
+    let response = endpoint
         .responses
         .get("200")
-        .or_else(|| endpoint.responses.get("201"))
-        .and_then(|r| r.schema_ref.clone());
+        .or_else(|| endpoint.responses.get("201"));
+    let response_media = response.and_then(|r| preferred_media_type(&r.content));
+    let response_schema = response.and_then(|r| r.schema_ref.clone());
+    let accept = response_media.map(|m| m.content_type.clone());
+    let response_headers = response
+        .map(|r| {
+            r.headers
+                .iter()
+                .map(|h| SimpleHeader {
+                    name: h.name.clone(),
+                    schema_type: h.schema_type.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
 
     SimpleEndpoint {
         path: endpoint.path.clone(),
@@ -353,6 +574,46 @@ fn simplify_endpoint(endpoint: &Endpoint) -> SimpleEndpoint {
         query_params,
         request_body_schema,
         response_schema,
+        request_body_inline: request_body_media.and_then(|m| m.schema.clone()),
+        response_inline: response_media.and_then(|m| m.schema.clone()),
+        accept,
+        response_headers,
+        auth: vec![],
+    }
+}
+
+/// Give anonymous request/response schemas synthetic names derived from the
+/// operation (`CreateUserRequest`, `CreateUserResponse`) and emit them as types
+fn hoist_inline_schemas(endpoints: &mut [SimpleEndpoint], schemas: &mut Vec<SimpleSchema>) {
+    // Collisions are resolved in operation order so names are stable across runs
+    endpoints.sort_by(|a, b| {
+        (&a.operation_id, &a.path, &a.method).cmp(&(&b.operation_id, &b.path, &b.method))
+    });
+    let mut taken: HashSet<String> = schemas.iter().map(|s| to_pascal_case(&s.name)).collect();
+
+    let mut hoist = |base: String, schema_type: SchemaType| {
+        let mut name = base.clone();
+        let mut n = 2;
+        while !taken.insert(name.clone()) {
+            name = format!("{base}{n}");
+            n += 1;
+        }
+        schemas.push(simple_schema(&name, None, &schema_type));
+        name
+    };
+
+    for endpoint in endpoints.iter_mut() {
+        let operation = to_pascal_case(&endpoint.operation_id);
+        if endpoint.request_body_schema.is_none()
+            && let Some(inline) = endpoint.request_body_inline.take()
+        {
+            endpoint.request_body_schema = Some(hoist(format!("{operation}Request"), inline));
+        }
+        if endpoint.response_schema.is_none()
+            && let Some(inline) = endpoint.response_inline.take()
+        {
+            endpoint.response_schema = Some(hoist(format!("{operation}Response"), inline));
+        }
     }
 }
 
@@ -376,6 +637,12 @@ fn generate_typescript_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec
 
         let type_name = convert_name(&schema.name, &style.type_naming);
 
+        if let Some(alias) = &schema.alias {
+            let ts_type = to_typescript_type(alias, style);
+            content.push_str(&format!("export type {type_name} = {ts_type};\n\n"));
+            continue;
+        }
+
         if schema.properties.is_empty() {
             content.push_str(&format!(
                 "export type {type_name} = Record<string, unknown>;\n\n"
@@ -387,15 +654,33 @@ fn generate_typescript_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec
 
         for prop in &schema.properties {
             let prop_name = convert_name(&prop.name, &style.property_naming);
-            let ts_type = to_typescript_type(prop, style);
+            let ts_type = to_typescript_type(&prop.schema_type, style);
             let optional = if prop.required { "" } else { "?" };
+            let constraints = prop.schema_type.constraints();
+            let readonly = if constraints.is_some_and(|c| c.read_only) {
+                "readonly "
+            } else {
+                ""
+            };
 
-            if style.generate_docs
-                && let Some(desc) = &prop.description
-            {
-                content.push_str(&format!("  /** {desc} */\n"));
+            if style.generate_docs {
+                let tags = constraints.map(constraint_tags).unwrap_or_default();
+                match (&prop.description, tags.is_empty()) {
+                    (Some(desc), true) => content.push_str(&format!("  /** {desc} */\n")),
+                    (None, true) => {}
+                    (desc, false) => {
+                        content.push_str("  /**\n");
+                        if let Some(desc) = desc {
+                            content.push_str(&format!("   * {desc}\n"));
+                        }
+                        for (tag, value) in &tags {
+                            content.push_str(&format!("   * @{tag} {value}\n"));
+                        }
+                        content.push_str("   */\n");
+                    }
+                }
             }
-            content.push_str(&format!("  {prop_name}{optional}: {ts_type};\n"));
+            content.push_str(&format!("  {readonly}{prop_name}{optional}: {ts_type};\n"));
         }
 
         content.push_str("}\n\n");
@@ -410,21 +695,39 @@ fn generate_typescript_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec
 
 fn generate_typescript_fetch_client(
     endpoints: &[SimpleEndpoint],
+    client: &SimpleClient,
     style: &CodeStyle,
 ) -> Vec<GeneratedFile> {
     let mut content = String::new();
     let base_url = style.base_url_env.as_deref().unwrap_or("API_BASE_URL");
+    let default_base_url = client.default_base_url.as_deref().unwrap_or("");
 
     content.push_str(&format!(
-        r#"import type * as Types from './types';
+        "import type * as Types from './types';\n\nconst BASE_URL = process.env.{base_url} || '{default_base_url}';\n\n"
+    ));
 
-const BASE_URL = process.env.{base_url} || '';
+    let (schemes_param, url_expr, auth_headers) = if client.auth.is_empty() {
+        ("", "`${BASE_URL}${path}`", "")
+    } else {
+        content.push_str(&typescript_auth_block(&client.auth));
+        (
+            ", schemes: readonly string[] = []",
+            "withAuthQuery(`${BASE_URL}${path}`, schemes)",
+            "\n      ...authHeaders(schemes),",
+        )
+    };
+    let schemes_arg = if client.auth.is_empty() {
+        ""
+    } else {
+        ", schemes"
+    };
 
-async function request<T>(path: string, options: RequestInit = {{}}): Promise<T> {{
-  const response = await fetch(`${{BASE_URL}}${{path}}`, {{
+    content.push_str(&format!(
+        r#"async function send(path: string, options: RequestInit = {{}}{schemes_param}): Promise<Response> {{
+  const response = await fetch({url_expr}, {{
     ...options,
     headers: {{
-      'Content-Type': 'application/json',
+      'Content-Type': 'application/json',{auth_headers}
       ...options.headers,
     }},
   }});
@@ -433,20 +736,21 @@ async function request<T>(path: string, options: RequestInit = {{}}): Promise<T>
     throw new Error(`HTTP error! status: ${{response.status}}`);
   }}
 
-  return response.json();
+  return response;
+}}
+
+async function request<T>(path: string, options: RequestInit = {{}}{schemes_param}): Promise<T> {{
+  return (await send(path, options{schemes_arg})).json();
 }}
 
 "#
     ));
+    content.push_str(typescript_header_helpers(endpoints));
 
     for endpoint in endpoints {
         let func_name = convert_name(&endpoint.operation_id, &style.function_naming);
 
-        let return_type = endpoint
-            .response_schema
-            .as_ref()
-            .map(|s| format!("Types.{}", convert_name(s, &style.type_naming)))
-            .unwrap_or_else(|| "void".to_string());
+        let return_type = typescript_response_type(endpoint, style, "");
 
         // Build parameters
         let mut params: Vec<String> = endpoint
@@ -483,11 +787,17 @@ async function request<T>(path: string, options: RequestInit = {{}}): Promise<T>
             ""
         };
 
-        let body_str = if endpoint.request_body_schema.is_some() {
-            ", body: JSON.stringify(body)"
-        } else {
-            ""
-        };
+        let mut body_str = String::new();
+        if let Some(accept) = &endpoint.accept {
+            body_str.push_str(&format!(", headers: {{ Accept: '{accept}' }}"));
+        }
+        if endpoint.request_body_schema.is_some() {
+            body_str.push_str(", body: JSON.stringify(body)");
+        }
+
+        if !endpoint.response_headers.is_empty() {
+            content.push_str(&typescript_headers_interface(endpoint, style));
+        }
 
         if style.generate_docs
             && let Some(ref summary) = endpoint.summary
@@ -495,21 +805,46 @@ async function request<T>(path: string, options: RequestInit = {{}}): Promise<T>
             content.push_str(&format!("/** {summary} */\n"));
         }
 
-        content.push_str(&format!(
-            r#"export async function {}({}): Promise<{}> {{
-  return request<{}>(`{}`{}, {{ method: '{}'{} }});
+        let auth_arg = typescript_auth_arg(endpoint);
+
+        if endpoint.response_headers.is_empty() {
+            content.push_str(&format!(
+                r#"export async function {}({}): Promise<{}> {{
+  return request<{}>(`{}`{}, {{ method: '{}'{} }}{auth_arg});
 }}
 
 "#,
-            func_name,
-            params_str,
-            return_type,
-            return_type,
-            path_template,
-            query_str,
-            endpoint.method,
-            body_str
-        ));
+                func_name,
+                params_str,
+                return_type,
+                return_type,
+                path_template,
+                query_str,
+                endpoint.method,
+                body_str
+            ));
+        } else {
+            let headers = typescript_headers_literal(endpoint, style, |name| {
+                format!("response.headers.get('{name}')")
+            });
+            let data = if endpoint.response_schema.is_some() {
+                "await response.json()"
+            } else {
+                "undefined"
+            };
+            content.push_str(&format!(
+                r#"export async function {func_name}({params_str}): Promise<{return_type}> {{
+  const response = await send(`{path_template}`{query_str}, {{ method: '{}'{body_str} }}{auth_arg});
+  return {{
+    data: {data},
+    headers: {headers},
+  }};
+}}
+
+"#,
+                endpoint.method
+            ));
+        }
     }
 
     vec![GeneratedFile {
@@ -521,27 +856,36 @@ async function request<T>(path: string, options: RequestInit = {{}}): Promise<T>
 
 fn generate_typescript_axios_client(
     endpoints: &[SimpleEndpoint],
+    client: &SimpleClient,
     style: &CodeStyle,
 ) -> Vec<GeneratedFile> {
     let mut content = String::new();
     let base_url = style.base_url_env.as_deref().unwrap_or("API_BASE_URL");
+    let default_base_url = client.default_base_url.as_deref().unwrap_or("");
 
     content.push_str(&format!(
         r#"import axios from 'axios';
 import type * as Types from './types';
 
 const api = axios.create({{
-  baseURL: process.env.{base_url} || '',
+  baseURL: process.env.{base_url} || '{default_base_url}',
   headers: {{ 'Content-Type': 'application/json' }},
 }});
 
 "#
     ));
 
+    if !client.auth.is_empty() {
+        content.push_str(&typescript_auth_block(&client.auth));
+    }
+
+    content.push_str(typescript_header_helpers(endpoints));
+
     for endpoint in endpoints {
         let func_name = convert_name(&endpoint.operation_id, &style.function_naming);
 
-        let return_type = endpoint
+        let return_type = typescript_response_type(endpoint, style, "");
+        let data_type = endpoint
             .response_schema
             .as_ref()
             .map(|s| format!("Types.{}", convert_name(s, &style.type_naming)))
@@ -571,11 +915,33 @@ const api = axios.create({{
         }
 
         let method = endpoint.method.to_lowercase();
-        let data_arg = if endpoint.request_body_schema.is_some() {
-            ", body"
-        } else {
-            ""
+        let takes_data = matches!(method.as_str(), "post" | "put" | "patch");
+        let mut headers = Vec::new();
+        let mut config = Vec::new();
+        if !endpoint.auth.is_empty() {
+            let schemes = typescript_schemes(endpoint);
+            headers.push(format!("...authHeaders({schemes})"));
+            config.push(format!("params: authQuery({schemes})"));
+        }
+        if let Some(accept) = &endpoint.accept {
+            headers.push(format!("Accept: '{accept}'"));
+        }
+        if !headers.is_empty() {
+            config.insert(0, format!("headers: {{ {} }}", headers.join(", ")));
+        }
+
+        let mut data_arg = match (endpoint.request_body_schema.is_some(), config.is_empty()) {
+            (true, _) => ", body".to_string(),
+            (false, false) if takes_data => ", undefined".to_string(),
+            _ => String::new(),
         };
+        if !config.is_empty() {
+            data_arg.push_str(&format!(", {{ {} }}", config.join(", ")));
+        }
+
+        if !endpoint.response_headers.is_empty() {
+            content.push_str(&typescript_headers_interface(endpoint, style));
+        }
 
         if style.generate_docs
             && let Some(ref summary) = endpoint.summary
@@ -583,14 +949,32 @@ const api = axios.create({{
             content.push_str(&format!("/** {summary} */\n"));
         }
 
-        content.push_str(&format!(
-            r#"export async function {func_name}({params_str}): Promise<{return_type}> {{
+        if endpoint.response_headers.is_empty() {
+            content.push_str(&format!(
+                r#"export async function {func_name}({params_str}): Promise<{return_type}> {{
   const {{ data }} = await api.{method}<{return_type}>(`{path_template}`{data_arg});
   return data;
 }}
 
 "#
-        ));
+            ));
+        } else {
+            // Axios lower-cases response header names
+            let headers = typescript_headers_literal(endpoint, style, |name| {
+                format!("headers['{}']", name.to_lowercase())
+            });
+            content.push_str(&format!(
+                r#"export async function {func_name}({params_str}): Promise<{return_type}> {{
+  const {{ data, headers }} = await api.{method}<{data_type}>(`{path_template}`{data_arg});
+  return {{
+    data,
+    headers: {headers},
+  }};
+}}
+
+"#
+            ));
+        }
     }
 
     vec![GeneratedFile {
@@ -616,11 +1000,7 @@ import type * as Types from './types';
         let func_name = convert_name(&endpoint.operation_id, &style.function_naming);
         let hook_name = to_pascal_case(&func_name);
 
-        let return_type = endpoint
-            .response_schema
-            .as_ref()
-            .map(|s| format!("Types.{}", convert_name(s, &style.type_naming)))
-            .unwrap_or_else(|| "void".to_string());
+        let return_type = typescript_response_type(endpoint, style, "api.");
 
         let is_mutation = matches!(
             endpoint.method.as_str(),
@@ -689,9 +1069,33 @@ fn generate_index_ts_with_hooks() -> GeneratedFile {
 
 // ===== Rust Generators =====
 
-fn generate_rust_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec<GeneratedFile> {
+fn generate_rust_types(
+    schemas: &[SimpleSchema],
+    cycles: &[SchemaCycle],
+    style: &CodeStyle,
+) -> Vec<GeneratedFile> {
     let mut content = String::new();
 
+    // A direct reference between members of a cycle that no array or map
+    // breaks would make the types infinitely sized
+    let cycle_of: HashMap<&str, usize> = cycles
+        .iter()
+        .enumerate()
+        .filter(|(_, cycle)| !cycle.broken_by_array)
+        .flat_map(|(index, cycle)| cycle.schemas.iter().map(move |s| (s.as_str(), index)))
+        .collect();
+    let boxed = |from: &str, to: &str| {
+        cycle_of
+            .get(from)
+            .is_some_and(|cycle| cycle_of.get(to) == Some(cycle))
+    };
+
+    // Internally tagged enums own the tag, so their variant structs must not
+    // declare it again
+    let tagged = discriminated_variants(schemas.iter().filter_map(|s| s.alias.as_ref()));
+    // Whether any emitted type needs the `HashMap` import
+    let mut uses_map = false;
+
     content.push_str("//! Auto-generated Rust types from OpenAPI spec\n\nuse serde::{Deserialize, Serialize};\n\n");
 
     for schema in schemas {
@@ -702,12 +1106,67 @@ fn generate_rust_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec<Gener
         }
 
         let type_name = to_pascal_case(&schema.name);
+
+        if let Some(
+            SchemaType::OneOf {
+                variants,
+                discriminator: Some(discriminator),
+            }
+            | SchemaType::AnyOf {
+                variants,
+                discriminator: Some(discriminator),
+            },
+        ) = &schema.alias
+            && !discriminator.tags(variants).is_empty()
+        {
+            content.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+            content.push_str(&format!(
+                "#[serde(tag = \"{}\")]\n",
+                discriminator.property_name
+            ));
+            content.push_str(&format!("pub enum {type_name} {{\n"));
+            for (tag, variant_schema) in discriminator.tags(variants) {
+                let variant_name = to_pascal_case(&tag);
+                if variant_name != tag {
+                    content.push_str(&format!("    #[serde(rename = \"{tag}\")]\n"));
+                }
+                let variant_type = to_pascal_case(&variant_schema);
+                if boxed(&schema.name, &variant_schema) {
+                    content.push_str(&format!("    {variant_name}(Box<{variant_type}>),\n"));
+                } else {
+                    content.push_str(&format!("    {variant_name}({variant_type}),\n"));
+                }
+            }
+            content.push_str("}\n\n");
+            continue;
+        }
+
+        if let Some(alias) = &schema.alias {
+            uses_map |= rust_uses_map(alias);
+            let rust_type = rust_type_expr(alias);
+            content.push_str(&format!("pub type {type_name} = {rust_type};\n\n"));
+            continue;
+        }
+
         content.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
         content.push_str(&format!("pub struct {type_name} {{\n"));
 
+        let tag_property = tagged.get(&schema.name).map(|(property, _)| property);
         for prop in &schema.properties {
+            if tag_property == Some(&prop.name) {
+                continue;
+            }
             let prop_name = to_snake_case(&prop.name);
-            let rust_type = to_rust_type(prop);
+            uses_map |= rust_uses_map(&prop.schema_type);
+            let rust_type = to_rust_type(prop, &|reference| boxed(&schema.name, reference));
+
+            if style.generate_docs
+                && let Some(constraints) = prop.schema_type.constraints()
+            {
+                for (tag, value) in constraint_tags(constraints) {
+                    content.push_str(&format!("    /// {tag}: {value}\n"));
+                }
+            }
 
             if prop_name != prop.name {
                 content.push_str(&format!("    #[serde(rename = \"{}\")]\n", prop.name));
@@ -723,6 +1182,14 @@ fn generate_rust_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec<Gener
         content.push_str("}\n\n");
     }
 
+    if uses_map {
+        content = content.replacen(
+            "use serde::{Deserialize, Serialize};\n",
+            "use serde::{Deserialize, Serialize};\nuse std::collections::HashMap;\n",
+            1,
+        );
+    }
+
     vec![GeneratedFile {
         path: "types.rs".to_string(),
         content,
@@ -732,40 +1199,66 @@ fn generate_rust_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec<Gener
 
 fn generate_rust_reqwest_client(
     endpoints: &[SimpleEndpoint],
+    client: &SimpleClient,
     style: &CodeStyle,
 ) -> Vec<GeneratedFile> {
     let mut content = String::new();
+    let has_auth = !client.auth.is_empty();
 
-    content.push_str(
-        r#"//! Auto-generated API client from OpenAPI spec
+    content.push_str("//! Auto-generated API client from OpenAPI spec\n\nuse reqwest::Client;\nuse super::types::*;\n\n");
+
+    if let Some(url) = &client.default_base_url {
+        content.push_str(&format!(
+            "pub const DEFAULT_BASE_URL: &str = \"{url}\";\n\n"
+        ));
+    }
 
-use reqwest::Client;
-use super::types::*;
+    if has_auth {
+        content.push_str(&rust_auth_struct(&client.auth));
+    }
 
-pub struct ApiClient {
+    let (auth_field, auth_init) = if has_auth {
+        ("    auth: Auth,\n", "            auth: Auth::default(),\n")
+    } else {
+        ("", "")
+    };
+    content.push_str(&format!(
+        r#"pub struct ApiClient {{
     client: Client,
     base_url: String,
-}
+{auth_field}}}
 
-impl ApiClient {
-    pub fn new(base_url: impl Into<String>) -> Self {
-        Self {
+impl ApiClient {{
+    pub fn new(base_url: impl Into<String>) -> Self {{
+        Self {{
             client: Client::new(),
             base_url: base_url.into(),
-        }
+{auth_init}        }}
+    }}
+
+"#
+    ));
+
+    if has_auth {
+        content.push_str(&rust_auth_methods(&client.auth));
     }
 
-"#,
-    );
+    let mut header_structs = String::new();
 
     for endpoint in endpoints {
         let func_name = to_snake_case(&endpoint.operation_id);
 
-        let return_type = endpoint
+        let data_type = endpoint
             .response_schema
             .as_ref()
             .map(|s| to_pascal_case(s))
             .unwrap_or_else(|| "()".to_string());
+        let headers_type = to_pascal_case(&headers_type_name(endpoint, style));
+        let return_type = if endpoint.response_headers.is_empty() {
+            data_type
+        } else {
+            format!("({data_type}, {headers_type})")
+        };
 
         let mut params = vec!["&self".to_string()];
 
@@ -796,11 +1289,13 @@ impl ApiClient {
         };
 
         let method = endpoint.method.to_lowercase();
-        let body_call = if endpoint.request_body_schema.is_some() {
-            ".json(body)"
-        } else {
-            ""
-        };
+        let mut body_call = String::new();
+        if let Some(accept) = &endpoint.accept {
+            body_call.push_str(&format!(".header(\"Accept\", \"{accept}\")"));
+        }
+        if endpoint.request_body_schema.is_some() {
+            body_call.push_str(".json(body)");
+        }
 
         if style.generate_docs
             && let Some(ref summary) = endpoint.summary
@@ -815,14 +1310,59 @@ impl ApiClient {
             "        let url = format!(\"{{}}{}\", self.base_url, {});\n",
             endpoint.path, path_expr
         ));
-        content.push_str(&format!(
-            "        self.client.{method}(&url){body_call}.send().await?.json().await\n"
-        ));
+        let request = if !endpoint.auth.is_empty() {
+            let schemes: Vec<String> = endpoint
+                .auth
+                .iter()
+                .map(|hook| format!("\"{}\"", to_snake_case(hook.credential())))
+                .collect();
+            format!(
+                "self.authorize(self.client.{method}(&url), &[{}])",
+                schemes.join(", ")
+            )
+        } else {
+            format!("self.client.{method}(&url)")
+        };
+        if endpoint.response_headers.is_empty() {
+            content.push_str(&format!(
+                "        {request}{body_call}.send().await?.json().await\n"
+            ));
+        } else {
+            header_structs.push_str(&format!(
+                "#[derive(Debug, Clone, Default)]\npub struct {headers_type} {{\n"
+            ));
+            content.push_str(&format!(
+                "        let response = {request}{body_call}.send().await?;\n        let headers = {headers_type} {{\n"
+            ));
+            for header in &endpoint.response_headers {
+                let field = header_field_name(header, &NamingConvention::SnakeCase);
+                header_structs.push_str(&format!(
+                    "    pub {field}: Option<{}>,\n",
+                    rust_header_type(header)
+                ));
+                content.push_str(&format!(
+                    "            {field}: header_value(&response, \"{}\"),\n",
+                    header.name
+                ));
+            }
+            header_structs.push_str("}\n\n");
+            content.push_str("        };\n        Ok((response.json().await?, headers))\n");
+        }
         content.push_str("    }\n\n");
     }
 
     content.push_str("}\n");
 
+    if !header_structs.is_empty() {
+        content.push_str(&format!(
+            r#"
+{header_structs}fn header_value<T: std::str::FromStr>(response: &reqwest::Response, name: &str) -> Option<T> {{
+    response.headers().get(name)?.to_str().ok()?.parse().ok()
+}}
+"#
+        ));
+    }
+
     vec![GeneratedFile {
         path: "client.rs".to_string(),
         content,
@@ -841,17 +1381,34 @@ fn generate_rust_mod() -> GeneratedFile {
 
 // ===== Python Generators =====
 
-fn generate_python_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec<GeneratedFile> {
+fn generate_python_types(
+    schemas: &[SimpleSchema],
+    cycles: &[SchemaCycle],
+    style: &CodeStyle,
+) -> Vec<GeneratedFile> {
     let mut content = String::new();
 
     content.push_str("\"\"\"Auto-generated Python types from OpenAPI spec\"\"\"\n\n");
     content.push_str(
-        "from typing import Optional, List, Any\nfrom pydantic import BaseModel, Field\n\n",
+        "from typing import Optional, List, Any, Union, Tuple, Literal, Annotated\nfrom pydantic import BaseModel, Field\n\n",
     );
 
+    // Variants of a discriminated union need their tag as a `Literal` field
+    let tagged = discriminated_variants(schemas.iter().flat_map(|schema| {
+        schema
+            .alias
+            .iter()
+            .chain(schema.properties.iter().map(|prop| &prop.schema_type))
+    }));
+
     for schema in schemas {
         let class_name = to_pascal_case(&schema.name);
 
+        if let Some(alias) = &schema.alias {
+            content.push_str(&format!("\n{class_name} = {}\n", python_type_expr(alias)));
+            continue;
+        }
+
         if style.generate_docs {
             if let Some(desc) = &schema.description {
                 content.push_str(&format!(
@@ -864,27 +1421,73 @@ fn generate_python_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec<Gen
             content.push_str(&format!("\nclass {class_name}(BaseModel):\n"));
         }
 
-        if schema.properties.is_empty() {
+        let tag = tagged.get(&schema.name);
+        if let Some((property, values)) = tag {
+            let prop_name = to_snake_case(property);
+            let values: Vec<String> = values.iter().map(|v| format!("'{v}'")).collect();
+            let alias = if prop_name != *property {
+                format!(" = Field(alias=\"{property}\")")
+            } else {
+                String::new()
+            };
+            content.push_str(&format!(
+                "    {prop_name}: Literal[{}]{alias}\n",
+                values.join(", ")
+            ));
+        } else if schema.properties.is_empty() {
             content.push_str("    pass\n");
             continue;
         }
 
         for prop in &schema.properties {
+            if tag.is_some_and(|(property, _)| *property == prop.name) {
+                continue;
+            }
             let prop_name = to_snake_case(&prop.name);
             let py_type = to_python_type(prop);
 
-            let field_args = if prop_name != prop.name {
-                format!(" = Field(alias=\"{}\")", prop.name)
-            } else if !prop.required {
-                " = None".to_string()
-            } else {
-                String::new()
+            let mut args = Vec::new();
+            let constraints = prop.schema_type.constraints();
+            match constraints.and_then(|c| c.default.as_ref()) {
+                Some(default) if !default.is_object() && !default.is_array() => {
+                    args.push(python_literal(default))
+                }
+                _ if !prop.required => args.push("None".to_string()),
+                _ => {}
+            }
+            let plain_default = prop_name == prop.name
+                && constraints.is_none_or(|c| pydantic_field_args(c).is_empty());
+            if prop_name != prop.name {
+                args.push(format!("alias=\"{}\"", prop.name));
+            }
+            args.extend(constraints.map(pydantic_field_args).unwrap_or_default());
+
+            let field_args = match args.as_slice() {
+                [] => String::new(),
+                [default] if plain_default => format!(" = {default}"),
+                _ => format!(" = Field({})", args.join(", ")),
             };
 
             content.push_str(&format!("    {prop_name}: {py_type}{field_args}\n"));
         }
     }
 
+    // Models in a reference cycle point at each other through quoted forward
+    // references, resolved once every class exists
+    let mut cyclic: Vec<String> = schemas
+        .iter()
+        .filter(|schema| schema.alias.is_none())
+        .filter(|schema| cycles.iter().any(|c| c.schemas.contains(&schema.name)))
+        .map(|schema| to_pascal_case(&schema.name))
+        .collect();
+    cyclic.sort();
+    if !cyclic.is_empty() {
+        content.push('\n');
+        for class_name in cyclic {
+            content.push_str(&format!("{class_name}.model_rebuild()\n"));
+        }
+    }
+
     vec![GeneratedFile {
         path: "types.py".to_string(),
         content,
@@ -894,6 +1497,7 @@ fn generate_python_types(schemas: &[SimpleSchema], style: &CodeStyle) -> Vec<Gen
 
 fn generate_python_httpx_client(
     endpoints: &[SimpleEndpoint],
+    client: &SimpleClient,
     style: &CodeStyle,
 ) -> Vec<GeneratedFile> {
     let mut content = String::new();
@@ -903,28 +1507,45 @@ fn generate_python_httpx_client(
 
 import httpx
 from typing import Optional
+from pydantic import TypeAdapter
 from .types import *
 
 
 class ApiClient:
-    def __init__(self, base_url: str):
-        self.base_url = base_url
-        self.client = httpx.AsyncClient()
-
-    async def close(self):
-        await self.client.aclose()
-
 "#,
     );
 
+    let base_url_param = match &client.default_base_url {
+        Some(url) => format!("base_url: str = \"{url}\""),
+        None => "base_url: str".to_string(),
+    };
+
+    if client.auth.is_empty() {
+        content.push_str(&format!(
+            "    def __init__(self, {base_url_param}):\n        self.base_url = base_url\n        self.client = httpx.AsyncClient()\n"
+        ));
+    } else {
+        content.push_str(&python_auth_init(&base_url_param, &client.auth));
+    }
+
+    content.push_str("\n    async def close(self):\n        await self.client.aclose()\n\n");
+
+    let mut header_dicts = String::new();
+
     for endpoint in endpoints {
         let func_name = to_snake_case(&endpoint.operation_id);
 
-        let return_type = endpoint
+        let data_type = endpoint
             .response_schema
             .as_ref()
             .map(|s| to_pascal_case(s))
             .unwrap_or_else(|| "None".to_string());
+        let headers_type = to_pascal_case(&headers_type_name(endpoint, style));
+        let return_type = if endpoint.response_headers.is_empty() {
+            data_type.clone()
+        } else {
+            format!("tuple[{data_type}, {headers_type}]")
+        };
 
         let mut params = vec!["self".to_string()];
 
@@ -939,11 +1560,32 @@ class ApiClient:
         let params_str = params.join(", ");
         let path_template = &endpoint.path;
         let method = endpoint.method.to_lowercase();
-        let json_arg = if endpoint.request_body_schema.is_some() {
-            ", json=body.model_dump()"
-        } else {
-            ""
-        };
+        let mut json_arg = String::new();
+        if let Some(body_schema) = &endpoint.request_body_schema {
+            json_arg.push_str(&format!(
+                ", json=TypeAdapter({}).dump_python(body, mode=\"json\", by_alias=True)",
+                to_pascal_case(body_schema)
+            ));
+        }
+        let mut auth_lines = String::new();
+        if !endpoint.auth.is_empty() {
+            let schemes: Vec<String> = endpoint
+                .auth
+                .iter()
+                .map(|hook| format!("\"{}\"", to_snake_case(hook.credential())))
+                .collect();
+            let trailing = if schemes.len() == 1 { "," } else { "" };
+            auth_lines = format!(
+                "        headers, params, auth = self._auth(({}{trailing}))\n",
+                schemes.join(", ")
+            );
+            if let Some(accept) = &endpoint.accept {
+                auth_lines.push_str(&format!("        headers[\"Accept\"] = \"{accept}\"\n"));
+            }
+            json_arg.push_str(", headers=headers, params=params, auth=auth");
+        } else if let Some(accept) = &endpoint.accept {
+            json_arg.push_str(&format!(", headers={{\"Accept\": \"{accept}\"}}"));
+        }
 
         if style.generate_docs {
             if let Some(ref summary) = endpoint.summary {
@@ -961,12 +1603,54 @@ class ApiClient:
 
         content.push_str(&format!(
             r#"        url = f"{{self.base_url}}{path_template}"
-        response = await self.client.{method}(url{json_arg})
+{auth_lines}        response = await self.client.{method}(url{json_arg})
         response.raise_for_status()
-        return {return_type}.model_validate(response.json())
-
 "#
         ));
+
+        let data = if endpoint.response_schema.is_some() {
+            // Aliases such as `List['Pet']` have no `model_validate`
+            format!("TypeAdapter({data_type}).validate_python(response.json())")
+        } else {
+            "None".to_string()
+        };
+
+        if endpoint.response_headers.is_empty() {
+            content.push_str(&format!("        return {data}\n\n"));
+        } else {
+            header_dicts.push_str(&format!("class {headers_type}(TypedDict, total=False):\n"));
+            content.push_str(&format!("        headers: {headers_type} = {{}}\n"));
+            for header in &endpoint.response_headers {
+                let field = header_field_name(header, &NamingConvention::SnakeCase);
+                let py_type = python_header_type(header);
+                header_dicts.push_str(&format!("    {field}: {py_type}\n"));
+                let value = format!("response.headers[\"{}\"]", header.name);
+                let parsed = match py_type {
+                    "bool" => format!("{value} == \"true\""),
+                    "str" => value,
+                    _ => format!("{py_type}({value})"),
+                };
+                content.push_str(&format!(
+                    "        if \"{}\" in response.headers:\n            headers[\"{field}\"] = {parsed}\n",
+                    header.name
+                ));
+            }
+            header_dicts.push_str("\n\n");
+            content.push_str(&format!("        return {data}, headers\n\n"));
+        }
+    }
+
+    if !header_dicts.is_empty() {
+        content = content.replacen(
+            "from typing import Optional\n",
+            "from typing import Optional, TypedDict\n",
+            1,
+        );
+        content = content.replacen(
+            "\n\nclass ApiClient:",
+            &format!("\n\n{header_dicts}class ApiClient:"),
+            1,
+        );
     }
 
     vec![GeneratedFile {
@@ -986,87 +1670,940 @@ fn generate_python_init() -> GeneratedFile {
 
 // ===== Type Converters =====
 
-fn to_typescript_type(prop: &SimpleProperty, style: &CodeStyle) -> String {
-    if let Some(custom) = style.type_mappings.get(&prop.schema_type) {
-        return if prop.is_array {
-            format!("{custom}[]")
-        } else {
-            custom.clone()
-        };
+fn to_typescript_type(schema_type: &SchemaType, style: &CodeStyle) -> String {
+    if let Some(custom) = extract_type_info(schema_type).and_then(|t| style.type_mappings.get(t)) {
+        return custom.clone();
     }
 
-    let base_type = if prop.is_ref {
-        convert_name(&prop.schema_type, &style.type_naming)
-    } else {
-        match prop.schema_type.as_str() {
-            "integer" | "number" => "number".to_string(),
-            "boolean" => "boolean".to_string(),
-            "string" => "string".to_string(),
-            "object" => "Record<string, unknown>".to_string(),
-            _ => "unknown".to_string(),
-        }
+    let join = |variants: &[SchemaType], sep: &str| {
+        variants
+            .iter()
+            .map(|v| to_typescript_type(v, style))
+            .collect::<Vec<_>>()
+            .join(sep)
     };
 
-    if prop.is_array {
-        format!("{base_type}[]")
-    } else {
-        base_type
+    match schema_type {
+        SchemaType::Integer { .. } | SchemaType::Number { .. } => "number".to_string(),
+        SchemaType::Boolean { .. } => "boolean".to_string(),
+        SchemaType::String { .. } => "string".to_string(),
+        SchemaType::Null => "null".to_string(),
+        SchemaType::Ref { reference } => convert_name(reference, &style.type_naming),
+        SchemaType::Object { .. } => "Record<string, unknown>".to_string(),
+        SchemaType::Map { values, .. } => {
+            format!("Record<string, {}>", to_typescript_type(values, style))
+        }
+        SchemaType::Array { items, .. } => {
+            let inner = to_typescript_type(items, style);
+            if inner.contains(' ') {
+                format!("({inner})[]")
+            } else {
+                format!("{inner}[]")
+            }
+        }
+        SchemaType::Tuple {
+            prefix_items,
+            items,
+        } => {
+            let mut elements: Vec<String> = prefix_items
+                .iter()
+                .map(|i| to_typescript_type(i, style))
+                .collect();
+            match items {
+                Some(AdditionalSchema::Allowed(false)) | None => {}
+                Some(AdditionalSchema::Allowed(true)) => elements.push("...unknown[]".to_string()),
+                Some(AdditionalSchema::Schema(rest)) => {
+                    elements.push(format!("...{}[]", to_typescript_type(rest, style)))
+                }
+            }
+            format!("[{}]", elements.join(", "))
+        }
+        SchemaType::Nullable { inner } => format!("{} | null", to_typescript_type(inner, style)),
+        SchemaType::Const { value } => value.to_string(),
+        SchemaType::OneOf {
+            variants,
+            discriminator: Some(discriminator),
+        }
+        | SchemaType::AnyOf {
+            variants,
+            discriminator: Some(discriminator),
+        } if !discriminator.tags(variants).is_empty() => {
+            // Tagged union: pin the discriminator property on each variant
+            let property = &discriminator.property_name;
+            let key = if property.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                property.clone()
+            } else {
+                serde_json::Value::String(property.clone()).to_string()
+            };
+            discriminator
+                .tags(variants)
+                .iter()
+                .map(|(tag, schema)| {
+                    format!(
+                        "({{ {key}: {} }} & {})",
+                        serde_json::Value::String(tag.clone()),
+                        convert_name(schema, &style.type_naming)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" | ")
+        }
+        SchemaType::OneOf { variants, .. } | SchemaType::AnyOf { variants, .. } => {
+            join(variants, " | ")
+        }
+        SchemaType::AllOf { variants } => join(variants, " & "),
+        SchemaType::Conditional {
+            base,
+            then,
+            otherwise,
+            ..
+        } => {
+            let branches: Vec<SchemaType> = then
+                .iter()
+                .chain(otherwise.iter())
+                .map(|b| (**b).clone())
+                .collect();
+            match (base, branches.is_empty()) {
+                (None, true) => "unknown".to_string(),
+                (None, false) => join(&branches, " | "),
+                (Some(base), true) => to_typescript_type(base, style),
+                (Some(base), false) => format!(
+                    "{} & ({})",
+                    to_typescript_type(base, style),
+                    join(&branches, " | ")
+                ),
+            }
+        }
+        SchemaType::Unknown => "unknown".to_string(),
     }
 }
 
-fn to_rust_type(prop: &SimpleProperty) -> String {
-    let base_type = if prop.is_ref {
-        to_pascal_case(&prop.schema_type)
-    } else {
-        match (prop.schema_type.as_str(), prop.format.as_deref()) {
-            ("integer", Some("int32")) => "i32".to_string(),
-            ("integer", _) => "i64".to_string(),
-            ("number", Some("float")) => "f32".to_string(),
-            ("number", _) => "f64".to_string(),
-            ("boolean", _) => "bool".to_string(),
-            ("string", _) => "String".to_string(),
-            ("object", _) => "serde_json::Value".to_string(),
-            _ => "serde_json::Value".to_string(),
+fn to_rust_type(prop: &SimpleProperty, boxed: &dyn Fn(&str) -> bool) -> String {
+    match (&prop.schema_type, prop.required) {
+        // Nullable is already an Option
+        (SchemaType::Nullable { .. }, _) | (_, true) => {
+            rust_field_type_expr(&prop.schema_type, boxed)
+        }
+        (other, false) => format!("Option<{}>", rust_field_type_expr(other, boxed)),
+    }
+}
+
+/// `rust_type_expr`, boxing a direct reference that `boxed` says closes a cycle
+fn rust_field_type_expr(schema_type: &SchemaType, boxed: &dyn Fn(&str) -> bool) -> String {
+    match schema_type {
+        SchemaType::Ref { reference } if boxed(reference) => {
+            format!("Box<{}>", to_pascal_case(reference))
+        }
+        SchemaType::Nullable { inner } => {
+            format!("Option<{}>", rust_field_type_expr(inner, boxed))
+        }
+        other => rust_type_expr(other),
+    }
+}
+
+/// Whether `rust_type_expr` renders `schema_type` with a `HashMap`
+fn rust_uses_map(schema_type: &SchemaType) -> bool {
+    match schema_type {
+        SchemaType::Map { .. } => true,
+        SchemaType::Array { items, .. } => rust_uses_map(items),
+        SchemaType::Nullable { inner } => rust_uses_map(inner),
+        SchemaType::Conditional {
+            base: Some(base), ..
+        } => rust_uses_map(base),
+        SchemaType::Tuple {
+            prefix_items,
+            items: None | Some(AdditionalSchema::Allowed(false)),
+        } => prefix_items.iter().any(rust_uses_map),
+        _ => false,
+    }
+}
+
+fn rust_type_expr(schema_type: &SchemaType) -> String {
+    match schema_type {
+        SchemaType::Ref { reference } => to_pascal_case(reference),
+        SchemaType::Integer { format, .. } if format.as_deref() == Some("int32") => {
+            "i32".to_string()
+        }
+        SchemaType::Integer { .. } => "i64".to_string(),
+        SchemaType::Number { format, .. } if format.as_deref() == Some("float") => {
+            "f32".to_string()
+        }
+        SchemaType::Number { .. } => "f64".to_string(),
+        SchemaType::Boolean { .. } => "bool".to_string(),
+        SchemaType::String { .. } => "String".to_string(),
+        SchemaType::Null => "()".to_string(),
+        SchemaType::Array { items, .. } => format!("Vec<{}>", rust_type_expr(items)),
+        SchemaType::Map { values, .. } => format!("HashMap<String, {}>", rust_type_expr(values)),
+        SchemaType::Tuple {
+            prefix_items,
+            items: None | Some(AdditionalSchema::Allowed(false)),
+        } => {
+            let elements: Vec<String> = prefix_items.iter().map(rust_type_expr).collect();
+            if elements.len() == 1 {
+                format!("({},)", elements[0])
+            } else {
+                format!("({})", elements.join(", "))
+            }
         }
+        SchemaType::Tuple { .. } => "Vec<serde_json::Value>".to_string(),
+        SchemaType::Nullable { inner } => format!("Option<{}>", rust_type_expr(inner)),
+        SchemaType::Conditional {
+            base: Some(base), ..
+        } => rust_type_expr(base),
+        SchemaType::Const { value } => match value {
+            serde_json::Value::String(_) => "String".to_string(),
+            serde_json::Value::Bool(_) => "bool".to_string(),
+            serde_json::Value::Number(n) if n.is_f64() => "f64".to_string(),
+            serde_json::Value::Number(_) => "i64".to_string(),
+            _ => "serde_json::Value".to_string(),
+        },
+        _ => "serde_json::Value".to_string(),
+    }
+}
+
+fn to_python_type(prop: &SimpleProperty) -> String {
+    match (&prop.schema_type, prop.required) {
+        // Nullable is already Optional
+        (SchemaType::Nullable { .. }, _) | (_, true) => python_type_expr(&prop.schema_type),
+        (other, false) => format!("Optional[{}]", python_type_expr(other)),
+    }
+}
+
+fn python_type_expr(schema_type: &SchemaType) -> String {
+    let join = |variants: &[SchemaType]| {
+        variants
+            .iter()
+            .map(python_type_expr)
+            .collect::<Vec<_>>()
+            .join(", ")
     };
 
-    let typed = if prop.is_array {
-        format!("Vec<{base_type}>")
-    } else {
-        base_type
+    match schema_type {
+        SchemaType::Ref { reference } => format!("'{}'", to_pascal_case(reference)),
+        SchemaType::Integer { .. } => "int".to_string(),
+        SchemaType::Number { .. } => "float".to_string(),
+        SchemaType::Boolean { .. } => "bool".to_string(),
+        SchemaType::String { .. } => "str".to_string(),
+        SchemaType::Null => "None".to_string(),
+        SchemaType::Object { .. } => "dict".to_string(),
+        SchemaType::Map { values, .. } => format!("dict[str, {}]", python_type_expr(values)),
+        SchemaType::Array { items, .. } => format!("List[{}]", python_type_expr(items)),
+        SchemaType::Tuple {
+            prefix_items,
+            items: None | Some(AdditionalSchema::Allowed(false)),
+        } => format!("Tuple[{}]", join(prefix_items)),
+        SchemaType::Tuple { .. } => "List[Any]".to_string(),
+        SchemaType::Nullable { inner } => format!("Optional[{}]", python_type_expr(inner)),
+        SchemaType::Const { value } => format!("Literal[{}]", python_literal(value)),
+        SchemaType::OneOf {
+            variants,
+            discriminator: Some(discriminator),
+        }
+        | SchemaType::AnyOf {
+            variants,
+            discriminator: Some(discriminator),
+        } if !discriminator.tags(variants).is_empty() => {
+            let mut members: Vec<String> = Vec::new();
+            for (_, schema) in discriminator.tags(variants) {
+                let member = format!("'{}'", to_pascal_case(&schema));
+                if !members.contains(&member) {
+                    members.push(member);
+                }
+            }
+            // Pydantic looks the discriminator up by field name, not alias
+            format!(
+                "Annotated[Union[{}], Field(discriminator='{}')]",
+                members.join(", "),
+                to_snake_case(&discriminator.property_name)
+            )
+        }
+        SchemaType::OneOf { variants, .. } | SchemaType::AnyOf { variants, .. } => {
+            format!("Union[{}]", join(variants))
+        }
+        SchemaType::Conditional {
+            base: Some(base), ..
+        } => python_type_expr(base),
+        SchemaType::Conditional {
+            then: Some(then),
+            otherwise: Some(otherwise),
+            ..
+        } => format!(
+            "Union[{}, {}]",
+            python_type_expr(then),
+            python_type_expr(otherwise)
+        ),
+        _ => "Any".to_string(),
+    }
+}
+
+/// Variant schema → (discriminator property, tag values) for the
+/// discriminated unions among `types` and the types nested in them
+fn discriminated_variants<'a>(
+    types: impl IntoIterator<Item = &'a SchemaType>,
+) -> HashMap<String, (String, Vec<String>)> {
+    fn visit(schema_type: &SchemaType, out: &mut HashMap<String, (String, Vec<String>)>) {
+        match schema_type {
+            SchemaType::OneOf {
+                variants,
+                discriminator,
+            }
+            | SchemaType::AnyOf {
+                variants,
+                discriminator,
+            } => {
+                if let Some(discriminator) = discriminator {
+                    for (tag, schema) in discriminator.tags(variants) {
+                        let (_, tags) = out
+                            .entry(schema)
+                            .or_insert_with(|| (discriminator.property_name.clone(), vec![]));
+                        if !tags.contains(&tag) {
+                            tags.push(tag);
+                        }
+                    }
+                }
+                variants.iter().for_each(|v| visit(v, out));
+            }
+            SchemaType::Array { items, .. } => visit(items, out),
+            SchemaType::Map { values, .. } => visit(values, out),
+            SchemaType::Nullable { inner } => visit(inner, out),
+            _ => {}
+        }
+    }
+
+    let mut out = HashMap::new();
+    for schema_type in types {
+        visit(schema_type, &mut out);
+    }
+    out
+}
+
+/// Validation keywords as (JSON Schema keyword, value) pairs for doc comments.
+/// Rust and TypeScript output only documents constraints; Python enforces
+/// them through `pydantic_field_args`
+fn constraint_tags(constraints: &SchemaConstraints) -> Vec<(&'static str, String)> {
+    let mut tags = Vec::new();
+    let mut push = |tag: &'static str, value: Option<String>| {
+        if let Some(value) = value {
+            tags.push((tag, value));
+        }
     };
 
-    if prop.required {
-        typed
-    } else {
-        format!("Option<{typed}>")
+    push("minimum", constraints.minimum.map(|v| v.to_string()));
+    push(
+        "exclusiveMinimum",
+        constraints.exclusive_minimum.map(|v| v.to_string()),
+    );
+    push("maximum", constraints.maximum.map(|v| v.to_string()));
+    push(
+        "exclusiveMaximum",
+        constraints.exclusive_maximum.map(|v| v.to_string()),
+    );
+    push("multipleOf", constraints.multiple_of.map(|v| v.to_string()));
+    push("minLength", constraints.min_length.map(|v| v.to_string()));
+    push("maxLength", constraints.max_length.map(|v| v.to_string()));
+    push("pattern", constraints.pattern.clone());
+    push("minItems", constraints.min_items.map(|v| v.to_string()));
+    push("maxItems", constraints.max_items.map(|v| v.to_string()));
+    push(
+        "uniqueItems",
+        constraints.unique_items.then(|| "true".to_string()),
+    );
+    push(
+        "default",
+        constraints.default.as_ref().map(|v| v.to_string()),
+    );
+    push(
+        "example",
+        constraints.example.as_ref().map(|v| v.to_string()),
+    );
+    push(
+        "readOnly",
+        constraints.read_only.then(|| "true".to_string()),
+    );
+    push(
+        "writeOnly",
+        constraints.write_only.then(|| "true".to_string()),
+    );
+
+    tags
+}
+
+/// Pydantic `Field(...)` validator arguments
+fn pydantic_field_args(constraints: &SchemaConstraints) -> Vec<String> {
+    let mut args = Vec::new();
+
+    let bounds = [
+        ("ge", constraints.minimum),
+        ("gt", constraints.exclusive_minimum),
+        ("le", constraints.maximum),
+        ("lt", constraints.exclusive_maximum),
+        ("multiple_of", constraints.multiple_of),
+    ];
+    for (name, value) in bounds {
+        if let Some(value) = value {
+            args.push(format!("{name}={value}"));
+        }
+    }
+
+    // Pydantic applies min_length/max_length to both strings and lists
+    let lengths = [
+        (
+            "min_length",
+            constraints.min_length.or(constraints.min_items),
+        ),
+        (
+            "max_length",
+            constraints.max_length.or(constraints.max_items),
+        ),
+    ];
+    for (name, value) in lengths {
+        if let Some(value) = value {
+            args.push(format!("{name}={value}"));
+        }
     }
+
+    if let Some(pattern) = &constraints.pattern {
+        args.push(format!("pattern={}", python_regex_literal(pattern)));
+    }
+
+    args
 }
 
-fn to_python_type(prop: &SimpleProperty) -> String {
-    let base_type = if prop.is_ref {
-        format!("'{}'", to_pascal_case(&prop.schema_type))
-    } else {
-        match prop.schema_type.as_str() {
-            "integer" => "int".to_string(),
-            "number" => "float".to_string(),
-            "boolean" => "bool".to_string(),
-            "string" => "str".to_string(),
-            "object" => "dict".to_string(),
-            _ => "Any".to_string(),
+/// Python string literal for a regex: a raw string when one can hold the
+/// pattern verbatim, otherwise an escaped one
+fn python_regex_literal(pattern: &str) -> String {
+    let trailing_backslashes = pattern.len() - pattern.trim_end_matches('\\').len();
+    if trailing_backslashes.is_multiple_of(2) && !pattern.contains(['\n', '\r']) {
+        for quote in ['\'', '"'] {
+            if !pattern.contains(quote) {
+                return format!("r{quote}{pattern}{quote}");
+            }
         }
+    }
+    let escaped = pattern
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    format!("'{escaped}'")
+}
+
+fn python_literal(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Bool(true) => "True".to_string(),
+        serde_json::Value::Bool(false) => "False".to_string(),
+        serde_json::Value::Null => "None".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// ===== Webhook Handlers =====
+
+/// Emit handler signatures for the target language and export them from the index file
+fn add_webhook_handlers(
+    files: &mut Vec<GeneratedFile>,
+    target: &GenerateTarget,
+    webhooks: &[SimpleWebhook],
+    style: &CodeStyle,
+) {
+    let (file, export) = match target {
+        GenerateTarget::TypescriptTypes
+        | GenerateTarget::TypescriptFetch
+        | GenerateTarget::TypescriptAxios
+        | GenerateTarget::TypescriptReactQuery => (
+            generate_typescript_webhooks(webhooks, style),
+            "export * from './webhooks';\n",
+        ),
+        GenerateTarget::RustSerde | GenerateTarget::RustReqwest => (
+            generate_rust_webhooks(webhooks, style),
+            "pub mod webhooks;\npub use webhooks::*;\n",
+        ),
+        GenerateTarget::PythonPydantic | GenerateTarget::PythonHttpx => (
+            generate_python_webhooks(webhooks, style),
+            "from .webhooks import WebhookHandlers\n",
+        ),
     };
 
-    let typed = if prop.is_array {
-        format!("List[{base_type}]")
-    } else {
-        base_type
+    if let Some(index) = files
+        .iter_mut()
+        .find(|f| matches!(f.file_type, FileType::Index))
+    {
+        index.content.push_str(export);
+    }
+    files.push(file);
+}
+
+fn generate_typescript_webhooks(webhooks: &[SimpleWebhook], style: &CodeStyle) -> GeneratedFile {
+    let mut content = String::from("import type * as Types from './types';\n\n");
+    let schema_type = |schema: &Option<String>| {
+        schema
+            .as_ref()
+            .map(|s| format!("Types.{}", convert_name(s, &style.type_naming)))
     };
 
-    if prop.required {
-        typed
+    for webhook in webhooks {
+        let handler_type = format!(
+            "{}Handler",
+            convert_name(&webhook.handler, &style.type_naming)
+        );
+        let payload = schema_type(&webhook.payload_schema)
+            .map(|t| format!("payload: {t}"))
+            .unwrap_or_default();
+        let returns = schema_type(&webhook.response_schema).unwrap_or_else(|| "void".to_string());
+
+        if style.generate_docs
+            && let Some(ref summary) = webhook.summary
+        {
+            content.push_str(&format!("/** {summary} */\n"));
+        }
+        content.push_str(&format!(
+            "export type {handler_type} = ({payload}) => Promise<{returns}> | {returns};\n\n"
+        ));
+    }
+
+    content.push_str("export interface WebhookHandlers {\n");
+    for webhook in webhooks {
+        content.push_str(&format!(
+            "  {}: {}Handler;\n",
+            convert_name(&webhook.handler, &style.function_naming),
+            convert_name(&webhook.handler, &style.type_naming)
+        ));
+    }
+    content.push_str("}\n");
+
+    GeneratedFile {
+        path: "webhooks.ts".to_string(),
+        content,
+        file_type: FileType::Webhooks,
+    }
+}
+
+fn generate_rust_webhooks(webhooks: &[SimpleWebhook], style: &CodeStyle) -> GeneratedFile {
+    let mut content = String::from(
+        "//! Auto-generated webhook handler signatures from OpenAPI spec\n\nuse std::future::Future;\nuse super::types::*;\n\npub trait WebhookHandlers {\n",
+    );
+
+    for webhook in webhooks {
+        let mut params = vec!["&self".to_string()];
+        if let Some(ref payload) = webhook.payload_schema {
+            params.push(format!("payload: {}", to_pascal_case(payload)));
+        }
+        let returns = webhook
+            .response_schema
+            .as_ref()
+            .map(|s| to_pascal_case(s))
+            .unwrap_or_else(|| "()".to_string());
+
+        if style.generate_docs
+            && let Some(ref summary) = webhook.summary
+        {
+            content.push_str(&format!("    /// {summary}\n"));
+        }
+        content.push_str(&format!(
+            "    fn {}({}) -> impl Future<Output = {returns}> + Send;\n",
+            to_snake_case(&webhook.handler),
+            params.join(", ")
+        ));
+    }
+    content.push_str("}\n");
+
+    GeneratedFile {
+        path: "webhooks.rs".to_string(),
+        content,
+        file_type: FileType::Webhooks,
+    }
+}
+
+fn generate_python_webhooks(webhooks: &[SimpleWebhook], style: &CodeStyle) -> GeneratedFile {
+    let mut content = String::from(
+        "\"\"\"Auto-generated webhook handler signatures from OpenAPI spec\"\"\"\n\nfrom typing import Protocol\nfrom .types import *\n\n\nclass WebhookHandlers(Protocol):\n",
+    );
+
+    for webhook in webhooks {
+        let mut params = vec!["self".to_string()];
+        if let Some(ref payload) = webhook.payload_schema {
+            params.push(format!("payload: {}", to_pascal_case(payload)));
+        }
+        let returns = webhook
+            .response_schema
+            .as_ref()
+            .map(|s| to_pascal_case(s))
+            .unwrap_or_else(|| "None".to_string());
+
+        content.push_str(&format!(
+            "    async def {}({}) -> {returns}:\n",
+            to_snake_case(&webhook.handler),
+            params.join(", ")
+        ));
+        if style.generate_docs
+            && let Some(ref summary) = webhook.summary
+        {
+            content.push_str(&format!("        \"\"\"{summary}\"\"\"\n"));
+        }
+        content.push_str("        ...\n\n");
+    }
+
+    GeneratedFile {
+        path: "webhooks.py".to_string(),
+        content,
+        file_type: FileType::Webhooks,
+    }
+}
+
+// ===== Auth Hooks =====
+
+/// `AuthConfig`, `configureAuth()`, `authHeaders()`, `authQuery()` and
+/// `withAuthQuery()`; the helpers apply only the credentials an operation lists
+fn typescript_auth_block(auth: &[AuthHook]) -> String {
+    let mut fields = Vec::new();
+    let mut headers = Vec::new();
+    let mut query = Vec::new();
+
+    for hook in auth {
+        let id = to_camel_case(hook.credential());
+        match hook {
+            AuthHook::Bearer => {
+                fields.push("  bearerToken?: string;".to_string());
+                headers.push(format!(
+                    "  if (schemes.includes('{id}') && auth.bearerToken) headers['Authorization'] = `Bearer ${{auth.bearerToken}}`;"
+                ));
+            }
+            AuthHook::Basic => {
+                fields.push("  username?: string;\n  password?: string;".to_string());
+                headers.push(format!("  if (schemes.includes('{id}') && auth.username !== undefined) headers['Authorization'] = `Basic ${{btoa(`${{auth.username}}:${{auth.password ?? ''}}`)}}`;"));
+            }
+            AuthHook::ApiKey { name, location, .. } => {
+                fields.push(format!("  {id}?: string;"));
+                let condition = format!("schemes.includes('{id}') && auth.{id}");
+                match location {
+                    ParameterLocation::Query => {
+                        query.push(format!("  if ({condition}) query['{name}'] = auth.{id};"))
+                    }
+                    ParameterLocation::Cookie => headers.push(format!(
+                        "  if ({condition}) headers['Cookie'] = `{name}=${{auth.{id}}}`;"
+                    )),
+                    _ => headers.push(format!("  if ({condition}) headers['{name}'] = auth.{id};")),
+                }
+            }
+        }
+    }
+
+    format!(
+        r#"export interface AuthConfig {{
+{}
+}}
+
+let auth: AuthConfig = {{}};
+
+/** Set credentials sent with requests to operations that require them */
+export function configureAuth(config: AuthConfig): void {{
+  auth = config;
+}}
+
+function authHeaders(schemes: readonly string[]): Record<string, string> {{
+  const headers: Record<string, string> = {{}};
+{}
+  return headers;
+}}
+
+function authQuery(schemes: readonly string[]): Record<string, string> {{
+  const query: Record<string, string> = {{}};
+{}
+  return query;
+}}
+
+function withAuthQuery(url: string, schemes: readonly string[]): string {{
+  const query = new URLSearchParams(authQuery(schemes)).toString();
+  if (!query) return url;
+  return url + (url.includes('?') ? '&' : '?') + query;
+}}
+
+"#,
+        fields.join("\n"),
+        headers.join("\n"),
+        query.join("\n"),
+    )
+}
+
+/// `Auth` credentials struct for the reqwest client
+fn rust_auth_struct(auth: &[AuthHook]) -> String {
+    let fields: Vec<String> = auth
+        .iter()
+        .map(|hook| match hook {
+            AuthHook::Bearer => "    pub bearer_token: Option<String>,".to_string(),
+            AuthHook::Basic => "    pub basic: Option<(String, Option<String>)>,".to_string(),
+            AuthHook::ApiKey { scheme, .. } => {
+                format!("    pub {}: Option<String>,", to_snake_case(scheme))
+            }
+        })
+        .collect();
+
+    format!(
+        "/// Credentials, sent only to operations whose security requires them\n#[derive(Debug, Clone, Default)]\npub struct Auth {{\n{}\n}}\n\n",
+        fields.join("\n")
+    )
+}
+
+/// `with_auth()` and `authorize()` for the reqwest client
+fn rust_auth_methods(auth: &[AuthHook]) -> String {
+    let mut steps = Vec::new();
+    for hook in auth {
+        let id = to_snake_case(hook.credential());
+        let condition = |binding: &str, field: &str| {
+            format!(
+                "        if let (true, Some({binding})) = (schemes.contains(&\"{id}\"), &self.auth.{field}) {{"
+            )
+        };
+        steps.push(match hook {
+            AuthHook::Bearer => format!(
+                "{}\n            request = request.bearer_auth(token);\n        }}",
+                condition("token", "bearer_token")
+            ),
+            AuthHook::Basic => format!(
+                "{}\n            request = request.basic_auth(username, password.as_ref());\n        }}",
+                condition("(username, password)", "basic")
+            ),
+            AuthHook::ApiKey {
+                name,
+                location: ParameterLocation::Query,
+                ..
+            } => format!(
+                "{}\n            request = request.query(&[(\"{name}\", key)]);\n        }}",
+                condition("key", &id)
+            ),
+            AuthHook::ApiKey {
+                name,
+                location: ParameterLocation::Cookie,
+                ..
+            } => format!(
+                "{}\n            request = request.header(\"Cookie\", format!(\"{name}={{key}}\"));\n        }}",
+                condition("key", &id)
+            ),
+            AuthHook::ApiKey { name, .. } => format!(
+                "{}\n            request = request.header(\"{name}\", key);\n        }}",
+                condition("key", &id)
+            ),
+        });
+    }
+
+    format!(
+        r#"    pub fn with_auth(mut self, auth: Auth) -> Self {{
+        self.auth = auth;
+        self
+    }}
+
+    /// Attach the configured credentials among `schemes`
+    fn authorize(
+        &self,
+        mut request: reqwest::RequestBuilder,
+        schemes: &[&str],
+    ) -> reqwest::RequestBuilder {{
+{}
+        request
+    }}
+
+"#,
+        steps.join("\n")
+    )
+}
+
+/// `ApiClient.__init__` taking credentials, and `_auth()` building the
+/// headers, query parameters and httpx auth for one operation
+fn python_auth_init(base_url_param: &str, auth: &[AuthHook]) -> String {
+    let mut params = vec![base_url_param.to_string()];
+    let mut fields = vec![
+        "        self.base_url = base_url".to_string(),
+        "        self.client = httpx.AsyncClient()".to_string(),
+    ];
+    let mut steps = Vec::new();
+
+    for hook in auth {
+        let id = to_snake_case(hook.credential());
+        let selected = format!("\"{id}\" in schemes");
+        match hook {
+            AuthHook::Bearer => {
+                params.push("bearer_token: Optional[str] = None".to_string());
+                fields.push("        self.bearer_token = bearer_token".to_string());
+                steps.push(format!("        if {selected} and self.bearer_token is not None:\n            headers[\"Authorization\"] = f\"Bearer {{self.bearer_token}}\""));
+            }
+            AuthHook::Basic => {
+                params.push("username: Optional[str] = None".to_string());
+                params.push("password: Optional[str] = None".to_string());
+                fields.push("        self.username = username".to_string());
+                fields.push("        self.password = password".to_string());
+                steps.push(format!("        if {selected} and self.username is not None:\n            auth = httpx.BasicAuth(self.username, self.password or \"\")"));
+            }
+            AuthHook::ApiKey { name, location, .. } => {
+                params.push(format!("{id}: Optional[str] = None"));
+                fields.push(format!("        self.{id} = {id}"));
+                let condition = format!("        if {selected} and self.{id} is not None:");
+                steps.push(match location {
+                    ParameterLocation::Query => {
+                        format!("{condition}\n            params[\"{name}\"] = self.{id}")
+                    }
+                    ParameterLocation::Cookie => format!(
+                        "{condition}\n            headers[\"Cookie\"] = f\"{name}={{self.{id}}}\""
+                    ),
+                    _ => format!("{condition}\n            headers[\"{name}\"] = self.{id}"),
+                });
+            }
+        }
+    }
+
+    format!(
+        r#"    def __init__(self, {}):
+{}
+
+    def _auth(self, schemes: tuple[str, ...]) -> tuple[dict, dict, Optional[httpx.BasicAuth]]:
+        headers = {{}}
+        params = {{}}
+        auth = None
+{}
+        return headers, params, auth
+"#,
+        params.join(", "),
+        fields.join("\n"),
+        steps.join("\n")
+    )
+}
+
+/// TypeScript list of the credentials an operation applies
+fn typescript_schemes(endpoint: &SimpleEndpoint) -> String {
+    let ids: Vec<String> = endpoint
+        .auth
+        .iter()
+        .map(|hook| format!("'{}'", to_camel_case(hook.credential())))
+        .collect();
+    format!("[{}]", ids.join(", "))
+}
+
+/// Trailing `schemes` argument for `request()`/`send()`
+fn typescript_auth_arg(endpoint: &SimpleEndpoint) -> String {
+    if endpoint.auth.is_empty() {
+        String::new()
+    } else {
+        format!(", {}", typescript_schemes(endpoint))
+    }
+}
+
+// ===== Response Headers =====
+
+/// Field name for a header, following the property naming convention
+fn header_field_name(header: &SimpleHeader, convention: &NamingConvention) -> String {
+    convert_name(&to_camel_case(&header.name), convention)
+}
+
+fn headers_type_name(endpoint: &SimpleEndpoint, style: &CodeStyle) -> String {
+    convert_name(
+        &format!("{}Headers", to_pascal_case(&endpoint.operation_id)),
+        &style.type_naming,
+    )
+}
+
+/// Promise payload of a TypeScript client function; `scope` qualifies the
+/// headers interface when referenced from outside client.ts
+fn typescript_response_type(endpoint: &SimpleEndpoint, style: &CodeStyle, scope: &str) -> String {
+    let data = endpoint
+        .response_schema
+        .as_ref()
+        .map(|s| format!("Types.{}", convert_name(s, &style.type_naming)))
+        .unwrap_or_else(|| "void".to_string());
+
+    if endpoint.response_headers.is_empty() {
+        data
     } else {
-        format!("Optional[{typed}]")
+        format!(
+            "{scope}ApiResponse<{data}, {scope}{}>",
+            headers_type_name(endpoint, style)
+        )
+    }
+}
+
+/// `ApiResponse` wrapper and header parsing helper, if any endpoint needs them
+fn typescript_header_helpers(endpoints: &[SimpleEndpoint]) -> &'static str {
+    if endpoints.iter().all(|e| e.response_headers.is_empty()) {
+        return "";
+    }
+    r#"export interface ApiResponse<T, H> {
+  data: T;
+  headers: H;
+}
+
+function readHeader<T>(value: unknown, parse: (value: string) => T): T | undefined {
+  return value === null || value === undefined ? undefined : parse(String(value));
+}
+
+"#
+}
+
+/// Interface for an endpoint's response headers
+fn typescript_headers_interface(endpoint: &SimpleEndpoint, style: &CodeStyle) -> String {
+    let mut out = format!(
+        "export interface {} {{
+",
+        headers_type_name(endpoint, style)
+    );
+    for header in &endpoint.response_headers {
+        let ts_type = match header.schema_type.as_deref() {
+            Some("integer" | "number") => "number",
+            Some("boolean") => "boolean",
+            _ => "string",
+        };
+        out.push_str(&format!(
+            "  {}?: {ts_type};
+",
+            header_field_name(header, &style.property_naming)
+        ));
+    }
+    out.push_str(
+        "}
+
+",
+    );
+    out
+}
+
+/// Object literal reading each header; `lookup` maps a header name to the
+/// expression yielding its raw value
+fn typescript_headers_literal(
+    endpoint: &SimpleEndpoint,
+    style: &CodeStyle,
+    lookup: impl Fn(&str) -> String,
+) -> String {
+    let fields: Vec<String> = endpoint
+        .response_headers
+        .iter()
+        .map(|header| {
+            let parse = match header.schema_type.as_deref() {
+                Some("integer" | "number") => "Number",
+                Some("boolean") => "(v) => v === 'true'",
+                _ => "String",
+            };
+            format!(
+                "      {}: readHeader({}, {parse}),\n",
+                header_field_name(header, &style.property_naming),
+                lookup(&header.name)
+            )
+        })
+        .collect();
+    format!("{{\n{}    }}", fields.concat())
+}
+
+fn rust_header_type(header: &SimpleHeader) -> &'static str {
+    match header.schema_type.as_deref() {
+        Some("integer") => "i64",
+        Some("number") => "f64",
+        Some("boolean") => "bool",
+        _ => "String",
+    }
+}
+
+fn python_header_type(header: &SimpleHeader) -> &'static str {
+    match header.schema_type.as_deref() {
+        Some("integer") => "int",
+        Some("number") => "float",
+        Some("boolean") => "bool",
+        _ => "str",
     }
 }
 
@@ -1082,7 +2619,7 @@ fn convert_name(name: &str, convention: &NamingConvention) -> String {
 }
 
 fn to_pascal_case(s: &str) -> String {
-    s.split(['_', '-', ' '])
+    s.split(['_', '-', ' ', '/', '$', '.'])
         .map(|word| {
             let mut chars = word.chars();
             match chars.next() {
@@ -0,0 +1,137 @@
+//! oas_graph tool implementation
+
+use crate::services::{CacheManager, GraphBuilder, GraphFormat, GraphRenderer, OpenApiParser};
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct GraphInput {
+    /// URL or file path to OpenAPI spec
+    pub source: String,
+    /// Output format (default: mermaid)
+    #[serde(default)]
+    pub format: GraphFormat,
+    /// Only the part of the graph connected to this schema
+    pub schema: Option<String>,
+    /// Only the part of the graph connected to this path
+    pub path: Option<String>,
+    /// Only the part of the graph connected to operations with this tag
+    pub tag: Option<String>,
+    /// Project directory for caching
+    pub project_dir: Option<String>,
+    /// Whether to use cache (default: true when project_dir is provided)
+    #[serde(default = "default_true")]
+    pub use_cache: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct GraphOutput {
+    pub success: bool,
+    pub format: GraphFormat,
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// DOT or Mermaid source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<String>,
+    /// Node/edge document, for the JSON format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph: Option<GraphExport>,
+    pub error: Option<String>,
+}
+
+impl GraphOutput {
+    fn failure(format: GraphFormat, error: String) -> Self {
+        Self {
+            success: false,
+            format,
+            node_count: 0,
+            edge_count: 0,
+            rendered: None,
+            graph: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Export the dependency graph, or the part around a schema, path or tag
+pub async fn export_graph(input: GraphInput) -> GraphOutput {
+    let format = input.format;
+    let focus_count = [&input.schema, &input.path, &input.tag]
+        .iter()
+        .filter(|f| f.is_some())
+        .count();
+    if focus_count > 1 {
+        return GraphOutput::failure(
+            format,
+            "Specify at most one of 'schema', 'path' and 'tag'".to_string(),
+        );
+    }
+
+    // Parse the spec (with caching if project_dir provided)
+    let spec = if let (true, Some(project_dir)) = (input.use_cache, input.project_dir.as_ref()) {
+        let cache_manager = CacheManager::new(project_dir);
+        match cache_manager.parse_with_cache(&input.source, None).await {
+            Ok(s) => s,
+            Err(e) => return GraphOutput::failure(format, e.to_string()),
+        }
+    } else {
+        match OpenApiParser::parse(&input.source).await {
+            Ok(s) => s,
+            Err(e) => return GraphOutput::failure(format, e.to_string()),
+        }
+    };
+
+    let root = |id: String, kind: GraphNodeKind| GraphNode { id, kind };
+    let roots: Option<Vec<GraphNode>> = if let Some(schema) = input.schema {
+        if !spec.schemas.contains_key(&schema) {
+            return GraphOutput::failure(format, format!("Schema not found: {schema}"));
+        }
+        Some(vec![root(schema, GraphNodeKind::Schema)])
+    } else if let Some(path) = input.path {
+        if !spec.endpoints.contains_key(&path) && !spec.webhooks.contains_key(&path) {
+            return GraphOutput::failure(format, format!("Path not found: {path}"));
+        }
+        Some(vec![root(path, GraphNodeKind::Path)])
+    } else if let Some(tag) = input.tag {
+        let endpoints = spec.endpoints.iter();
+        let webhooks = spec.webhooks.iter().map(|(key, w)| (key, &w.endpoint));
+        let mut tagged: Vec<String> = endpoints
+            .chain(webhooks)
+            .filter(|(_, endpoint)| endpoint.tags.contains(&tag))
+            .map(|(key, _)| key.clone())
+            .collect();
+        if tagged.is_empty() {
+            return GraphOutput::failure(format, format!("No operations tagged: {tag}"));
+        }
+        tagged.sort();
+        Some(
+            tagged
+                .into_iter()
+                .map(|path| root(path, GraphNodeKind::Path))
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    let export = GraphBuilder::build(&spec).export(roots.as_deref());
+    let (rendered, graph) = match format {
+        GraphFormat::Dot => (Some(GraphRenderer::dot(&export)), None),
+        GraphFormat::Mermaid => (Some(GraphRenderer::mermaid(&export)), None),
+        GraphFormat::Json => (None, Some(export.clone())),
+    };
+
+    GraphOutput {
+        success: true,
+        format,
+        node_count: export.nodes.len(),
+        edge_count: export.edges.len(),
+        rendered,
+        graph,
+        error: None,
+    }
+}
@@ -0,0 +1,89 @@
+//! oas_lint tool implementation
+
+use crate::services::{
+    CacheManager, ComponentLinter, ComponentReport, GraphBuilder, OpenApiParser,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct LintInput {
+    /// URL or file path to OpenAPI spec
+    pub source: String,
+    /// Also return the spec document without unused schemas
+    #[serde(default)]
+    pub prune: bool,
+    /// Project directory for caching
+    pub project_dir: Option<String>,
+    /// Whether to use cache (default: true when project_dir is provided)
+    #[serde(default = "default_true")]
+    pub use_cache: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct LintOutput {
+    pub success: bool,
+    pub report: Option<ComponentReport>,
+    /// Unused schemas plus dangling references
+    pub total_issues: usize,
+    /// Spec document without the unused schemas, when `prune` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pruned_spec: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+impl LintOutput {
+    fn failure(error: String) -> Self {
+        Self {
+            success: false,
+            report: None,
+            total_issues: 0,
+            pruned_spec: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Report unused schemas and dangling references
+pub async fn lint_spec(input: LintInput) -> LintOutput {
+    // Parse the spec (with caching if project_dir provided)
+    let spec = if let (true, Some(project_dir)) = (input.use_cache, input.project_dir.as_ref()) {
+        let cache_manager = CacheManager::new(project_dir);
+        match cache_manager.parse_with_cache(&input.source, None).await {
+            Ok(s) => s,
+            Err(e) => return LintOutput::failure(e.to_string()),
+        }
+    } else {
+        match OpenApiParser::parse(&input.source).await {
+            Ok(s) => s,
+            Err(e) => return LintOutput::failure(e.to_string()),
+        }
+    };
+
+    let graph = GraphBuilder::build(&spec);
+    let report = ComponentLinter::lint(&spec, &graph);
+
+    // Pruning edits the document as written, before bundling
+    let pruned_spec = if input.prune {
+        match OpenApiParser::load_document(&input.source).await {
+            Ok(document) => Some(ComponentLinter::prune(
+                &document.value,
+                &report.unused_schemas,
+            )),
+            Err(e) => return LintOutput::failure(e.to_string()),
+        }
+    } else {
+        None
+    };
+
+    LintOutput {
+        success: true,
+        total_issues: report.unused_schemas.len() + report.dangling_refs.len(),
+        report: Some(report),
+        pruned_spec,
+        error: None,
+    }
+}
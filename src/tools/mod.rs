@@ -3,11 +3,15 @@
 mod deps;
 mod diff;
 mod generate;
+mod graph;
+mod lint;
 mod parse;
 mod status;
 
 pub use deps::*;
 pub use diff::*;
 pub use generate::*;
+pub use graph::*;
+pub use lint::*;
 pub use parse::*;
 pub use status::*;
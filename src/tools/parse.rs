@@ -3,6 +3,7 @@
 use crate::services::{CacheManager, GraphBuilder, OpenApiParser};
 use crate::types::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize)]
 pub struct ParseInput {
@@ -43,6 +44,8 @@ pub enum ParseFormat {
     Endpoints,
     /// Schema details (paginated)
     Schemas,
+    /// Webhooks and callbacks (paginated)
+    Webhooks,
     /// Full output (WARNING: can be large)
     Full,
 }
@@ -56,10 +59,14 @@ pub struct ParseOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub endpoint_keys: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<Vec<WebhookSummary>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub schemas: Option<Vec<SchemaSummary>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema_names: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub access: Option<AccessSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub graph_stats: Option<GraphStats>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pagination: Option<PaginationInfo>,
@@ -67,6 +74,24 @@ pub struct ParseOutput {
     pub error: Option<String>,
 }
 
+/// Servers and authentication declared by the spec
+#[derive(Debug, Serialize)]
+pub struct AccessSummary {
+    pub servers: Vec<Server>,
+    pub security_schemes: HashMap<String, SecurityScheme>,
+    pub security: Vec<SecurityRequirement>,
+}
+
+impl AccessSummary {
+    fn from_spec(spec: &ParsedSpec) -> Self {
+        Self {
+            servers: spec.servers.clone(),
+            security_schemes: spec.security_schemes.clone(),
+            security: spec.security.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct PaginationInfo {
     pub total: usize,
@@ -85,6 +110,24 @@ pub struct EndpointSummary {
     pub tags: Vec<String>,
     pub deprecated: bool,
     pub schema_refs: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookSummary {
+    pub key: String,
+    pub kind: WebhookKind,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    pub deprecated: bool,
+    pub schema_refs: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -93,6 +136,8 @@ pub struct SchemaSummary {
     pub refs: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Type definition including nullability and validation constraints
+    pub definition: SchemaType,
 }
 
 /// Parse an OpenAPI spec
@@ -111,8 +156,10 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                     metadata: None,
                     endpoints: None,
                     endpoint_keys: None,
+                    webhooks: None,
                     schemas: None,
                     schema_names: None,
+                    access: None,
                     graph_stats: None,
                     pagination: None,
                     error: Some(e.to_string()),
@@ -129,8 +176,10 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                     metadata: None,
                     endpoints: None,
                     endpoint_keys: None,
+                    webhooks: None,
                     schemas: None,
                     schema_names: None,
+                    access: None,
                     graph_stats: None,
                     pagination: None,
                     error: Some(e.to_string()),
@@ -169,9 +218,11 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
     match input.format {
         ParseFormat::Summary => ParseOutput {
             success: true,
+            access: Some(AccessSummary::from_spec(&spec)),
             metadata: Some(spec.metadata),
             endpoints: None,
             endpoint_keys: None,
+            webhooks: None,
             schemas: None,
             schema_names: None,
             graph_stats: Some(graph.stats()),
@@ -186,8 +237,10 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                 metadata: Some(spec.metadata),
                 endpoints: None,
                 endpoint_keys: Some(keys),
+                webhooks: None,
                 schemas: None,
                 schema_names: None,
+                access: None,
                 graph_stats: Some(graph.stats()),
                 pagination: None,
                 error: None,
@@ -201,8 +254,10 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                 metadata: Some(spec.metadata),
                 endpoints: None,
                 endpoint_keys: None,
+                webhooks: None,
                 schemas: None,
                 schema_names: Some(names),
+                access: None,
                 graph_stats: Some(graph.stats()),
                 pagination: None,
                 error: None,
@@ -223,6 +278,7 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                     tags: e.tags.clone(),
                     deprecated: e.deprecated,
                     schema_refs: e.schema_refs.clone(),
+                    security: e.security.clone(),
                 })
                 .collect();
 
@@ -231,8 +287,10 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                 metadata: Some(spec.metadata),
                 endpoints: Some(paginated),
                 endpoint_keys: None,
+                webhooks: None,
                 schemas: None,
                 schema_names: None,
+                access: None,
                 graph_stats: Some(graph.stats()),
                 pagination: Some(PaginationInfo {
                     total,
@@ -255,6 +313,7 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                     name: s.name.clone(),
                     refs: s.refs.clone(),
                     description: s.description.clone(),
+                    definition: s.schema_type.clone(),
                 })
                 .collect();
 
@@ -263,8 +322,51 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                 metadata: Some(spec.metadata),
                 endpoints: None,
                 endpoint_keys: None,
+                webhooks: None,
                 schemas: Some(paginated),
                 schema_names: None,
+                access: None,
+                graph_stats: Some(graph.stats()),
+                pagination: Some(PaginationInfo {
+                    total,
+                    offset,
+                    limit,
+                    has_more: offset + limit < total,
+                }),
+                error: None,
+            }
+        }
+
+        ParseFormat::Webhooks => {
+            let mut all_webhooks: Vec<_> = spec.webhooks.values().collect();
+            all_webhooks.sort_by_key(|w| w.key());
+            let total = all_webhooks.len();
+            let paginated: Vec<_> = all_webhooks
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|w| WebhookSummary {
+                    key: w.key(),
+                    kind: w.kind,
+                    name: w.name.clone(),
+                    parent: w.parent.clone(),
+                    expression: w.expression.clone(),
+                    method: w.endpoint.method.to_string(),
+                    operation_id: w.endpoint.operation_id.clone(),
+                    deprecated: w.endpoint.deprecated,
+                    schema_refs: w.endpoint.schema_refs.clone(),
+                })
+                .collect();
+
+            ParseOutput {
+                success: true,
+                metadata: Some(spec.metadata),
+                endpoints: None,
+                endpoint_keys: None,
+                webhooks: Some(paginated),
+                schemas: None,
+                schema_names: None,
+                access: None,
                 graph_stats: Some(graph.stats()),
                 pagination: Some(PaginationInfo {
                     total,
@@ -293,6 +395,7 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                     tags: e.tags.clone(),
                     deprecated: e.deprecated,
                     schema_refs: e.schema_refs.clone(),
+                    security: e.security.clone(),
                 })
                 .collect();
 
@@ -305,14 +408,17 @@ pub async fn parse_spec(input: ParseInput) -> ParseOutput {
                     name: s.name.clone(),
                     refs: s.refs.clone(),
                     description: s.description.clone(),
+                    definition: s.schema_type.clone(),
                 })
                 .collect();
 
             ParseOutput {
                 success: true,
+                access: Some(AccessSummary::from_spec(&spec)),
                 metadata: Some(spec.metadata),
                 endpoints: Some(paginated_endpoints),
                 endpoint_keys: None,
+                webhooks: None,
                 schemas: Some(paginated_schemas),
                 schema_names: None,
                 graph_stats: Some(graph.stats()),
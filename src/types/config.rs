@@ -2,7 +2,7 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Main configuration file (.openapi-sync.json)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +25,9 @@ pub struct OasConfig {
 
     #[serde(default)]
     pub generation: GenerationConfig,
+
+    #[serde(default)]
+    pub diff: DiffConfig,
 }
 
 fn default_version() -> String {
@@ -66,6 +69,43 @@ pub struct ValidationConfig {
     pub strict: bool,
 }
 
+/// Severity of a breaking-change finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Breaking-change rule configuration (`diff` section)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiffConfig {
+    /// Rule id (e.g. `enum-value-added`) → override
+    #[serde(default)]
+    pub rules: HashMap<String, RuleConfig>,
+}
+
+/// Override for a single rule; unset fields keep the rule's defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleConfig {
+    pub enabled: Option<bool>,
+    pub severity: Option<Severity>,
+}
+
+impl DiffConfig {
+    /// Layer `overrides` on top of this configuration, field by field
+    pub fn merged_with(&self, overrides: &HashMap<String, RuleConfig>) -> Self {
+        let mut rules = self.rules.clone();
+        for (id, rule) in overrides {
+            let entry = rules.entry(id.clone()).or_default();
+            entry.enabled = rule.enabled.or(entry.enabled);
+            entry.severity = rule.severity.or(entry.severity);
+        }
+        Self { rules }
+    }
+}
+
 /// Code generation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationConfig {
@@ -122,8 +162,9 @@ pub enum DataFetchingLib {
     RtkQuery,
 }
 
-/// Current cache schema version - increment when ParsedSpec structure changes
-pub const CACHE_SCHEMA_VERSION: u32 = 1;
+/// Current cache schema version - increment when ParsedSpec structure or the
+/// content a parse produces changes
+pub const CACHE_SCHEMA_VERSION: u32 = 16;
 
 fn default_schema_version() -> u32 {
     CACHE_SCHEMA_VERSION
@@ -176,6 +217,9 @@ pub struct HttpCacheInfo {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LocalCacheInfo {
     pub mtime: Option<String>,
+    /// Modification time of each file bundled in through external refs
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub external_mtimes: BTreeMap<String, String>,
 }
 
 /// Cached metadata
@@ -1,7 +1,8 @@
 //! Dependency graph types for tracking schema-path relationships
 
+use super::openapi::ReferenceKind;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 /// Direction for dependency queries
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -15,6 +16,41 @@ pub enum DependencyDirection {
     Both,
 }
 
+/// Where a schema is used by operations, from the API consumer's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SchemaUsage {
+    /// Sent by the consumer (request bodies, parameters, webhook responses)
+    pub request: bool,
+    /// Received by the consumer (responses, webhook payloads)
+    pub response: bool,
+}
+
+impl SchemaUsage {
+    pub const REQUEST: Self = Self {
+        request: true,
+        response: false,
+    };
+    pub const RESPONSE: Self = Self {
+        request: false,
+        response: true,
+    };
+    pub const BOTH: Self = Self {
+        request: true,
+        response: true,
+    };
+
+    pub fn is_unused(&self) -> bool {
+        !self.request && !self.response
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            request: self.request || other.request,
+            response: self.response || other.response,
+        }
+    }
+}
+
 /// Dependency graph tracking relationships between schemas and paths
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DependencyGraph {
@@ -33,6 +69,14 @@ pub struct DependencyGraph {
     /// Schema → Schemas that reference this schema
     #[serde(default)]
     schema_refs: HashMap<String, HashSet<String>>,
+
+    /// Schema → Referenced schema → Property path holding the reference → How it is held
+    #[serde(default)]
+    schema_ref_fields: HashMap<String, HashMap<String, BTreeMap<String, ReferenceKind>>>,
+
+    /// Schema → Direct usage by operations (not following refs)
+    #[serde(default)]
+    schema_usage: HashMap<String, SchemaUsage>,
 }
 
 impl DependencyGraph {
@@ -66,6 +110,44 @@ impl DependencyGraph {
             .insert(from_schema.to_string());
     }
 
+    /// Add a schema -> schema dependency through a property path
+    /// (`author`, `tags[]`; empty when referenced at the schema's root).
+    /// A path reached several ways is optional or a collection only if every way is
+    pub fn add_schema_field_dep(
+        &mut self,
+        from_schema: &str,
+        field: &str,
+        to_schema: &str,
+        kind: ReferenceKind,
+    ) {
+        self.add_schema_schema_dep(from_schema, to_schema);
+        self.schema_ref_fields
+            .entry(from_schema.to_string())
+            .or_default()
+            .entry(to_schema.to_string())
+            .or_default()
+            .entry(field.to_string())
+            .and_modify(|existing| {
+                existing.optional &= kind.optional;
+                existing.collection &= kind.collection;
+            })
+            .or_insert(kind);
+    }
+
+    /// Record that an operation uses a schema in a request and/or response
+    pub fn add_schema_usage(&mut self, schema: &str, usage: SchemaUsage) {
+        let entry = self.schema_usage.entry(schema.to_string()).or_default();
+        *entry = entry.union(usage);
+    }
+
+    /// Where a schema is used, directly or through schemas that reference it
+    pub fn get_schema_usage(&self, schema: &str) -> SchemaUsage {
+        std::iter::once(schema.to_string())
+            .chain(self.get_schema_dependents(schema))
+            .filter_map(|name| self.schema_usage.get(&name).copied())
+            .fold(SchemaUsage::default(), SchemaUsage::union)
+    }
+
     /// Get all paths that use a schema (directly or indirectly)
     pub fn get_affected_paths(&self, schema: &str) -> HashSet<String> {
         let mut affected = HashSet::new();
@@ -131,6 +213,40 @@ impl DependencyGraph {
         }
     }
 
+    /// `schemas` that no path or webhook reaches, directly or through
+    /// schemas referencing them, sorted
+    pub fn unreachable_schemas<'a>(
+        &self,
+        schemas: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<&'a str> {
+        let mut unreachable: Vec<&str> = schemas
+            .into_iter()
+            .filter(|schema| self.get_affected_paths(schema).is_empty())
+            .collect();
+        unreachable.sort();
+        unreachable
+    }
+
+    /// Referenced schema names missing from `defined`, each with the paths
+    /// and schemas referencing it
+    pub fn dangling_refs(&self, defined: &HashSet<&str>) -> BTreeMap<String, Vec<String>> {
+        let mut dangling: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for users in [&self.schema_to_paths, &self.schema_refs] {
+            for (schema, referencing) in users {
+                if !defined.contains(schema.as_str()) {
+                    dangling
+                        .entry(schema.clone())
+                        .or_default()
+                        .extend(referencing.iter().cloned());
+                }
+            }
+        }
+        for referencing in dangling.values_mut() {
+            referencing.sort();
+        }
+        dangling
+    }
+
     /// Get schemas that depend on a given schema
     pub fn get_schema_dependents(&self, schema: &str) -> HashSet<String> {
         let mut dependents = HashSet::new();
@@ -157,12 +273,217 @@ impl DependencyGraph {
         }
     }
 
-    /// Query dependencies
+    /// Reference chains from a path (through the schemas it uses directly)
+    /// or a schema to `to`: the shortest one, or with `all` every chain that
+    /// visits no schema twice, shortest first, up to `MAX_CHAINS`
+    pub fn dependency_chains(
+        &self,
+        from: &str,
+        from_path: bool,
+        to: &str,
+        all: bool,
+    ) -> Vec<DependencyChain> {
+        let starts: Vec<&str> = if from_path {
+            let mut direct: Vec<&str> = self
+                .path_to_schemas
+                .get(from)
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            direct.sort();
+            direct
+        } else {
+            vec![from]
+        };
+
+        let routes = if all {
+            // Only schemas that lead to the target are worth exploring
+            let mut leads_to = self.get_schema_dependents(to);
+            leads_to.insert(to.to_string());
+            let mut routes = Vec::new();
+            for start in starts.into_iter().filter(|s| leads_to.contains(*s)) {
+                self.collect_routes(
+                    start,
+                    to,
+                    &leads_to,
+                    &mut Vec::new(),
+                    &mut HashSet::new(),
+                    &mut routes,
+                );
+            }
+            routes.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+            routes
+        } else {
+            self.shortest_route(&starts, to).into_iter().collect()
+        };
+
+        routes
+            .into_iter()
+            .map(|route| {
+                let hops: Vec<String> = from_path
+                    .then(|| from.to_string())
+                    .into_iter()
+                    .chain(route)
+                    .collect();
+                DependencyChain {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    chain: hops.join(" -> "),
+                }
+            })
+            .collect()
+    }
+
+    /// Referenced schemas of `schema` with the property paths referencing
+    /// them, sorted by name; unlabeled references get an empty path held
+    /// as a required value
+    fn ref_edges(&self, schema: &str) -> Vec<(&str, Vec<(&str, ReferenceKind)>)> {
+        let fields = self.schema_ref_fields.get(schema);
+        let mut edges: Vec<(&str, Vec<(&str, ReferenceKind)>)> = self
+            .schema_to_schemas
+            .get(schema)
+            .into_iter()
+            .flatten()
+            .map(|next| {
+                let labels = fields
+                    .and_then(|f| f.get(next))
+                    .map(|labels| labels.iter().map(|(f, k)| (f.as_str(), *k)).collect())
+                    .unwrap_or_else(|| vec![("", ReferenceKind::default())]);
+                (next.as_str(), labels)
+            })
+            .collect();
+        edges.sort_by_key(|(next, _)| *next);
+        edges
+    }
+
+    /// Schema reference cycles: strongly connected components of two or
+    /// more schemas, or one schema referencing itself (Tarjan's algorithm)
+    pub fn cycles(&self) -> Vec<SchemaCycle> {
+        let mut nodes: Vec<&str> = self.schema_to_schemas.keys().map(String::as_str).collect();
+        nodes.sort();
+
+        let mut tarjan = Tarjan::default();
+        for node in nodes {
+            if !tarjan.index.contains_key(node) {
+                tarjan.connect(self, node);
+            }
+        }
+
+        let mut cycles: Vec<SchemaCycle> = tarjan
+            .components
+            .into_iter()
+            .filter_map(|mut members| {
+                members.sort();
+                let edges: Vec<CycleEdge> = members
+                    .iter()
+                    .flat_map(|from| {
+                        self.ref_edges(from)
+                            .into_iter()
+                            .filter(|(to, _)| members.contains(to))
+                            .flat_map(move |(to, fields)| {
+                                fields.into_iter().map(move |(field, kind)| CycleEdge {
+                                    from: from.to_string(),
+                                    to: to.to_string(),
+                                    field: field.to_string(),
+                                    kind,
+                                })
+                            })
+                    })
+                    .collect();
+                if edges.is_empty() {
+                    return None;
+                }
+
+                Some(SchemaCycle {
+                    broken_by_optional: is_acyclic(&members, &edges, |k| k.optional),
+                    broken_by_array: is_acyclic(&members, &edges, |k| k.collection),
+                    schemas: members.into_iter().map(String::from).collect(),
+                    edges,
+                })
+            })
+            .collect();
+        cycles.sort_by(|a, b| a.schemas.cmp(&b.schemas));
+        cycles
+    }
+
+    /// Breadth-first search from `starts`, rendering the hops of the first
+    /// route found to `to`
+    fn shortest_route(&self, starts: &[&str], to: &str) -> Option<Vec<String>> {
+        let mut previous: HashMap<&str, Option<(&str, &str)>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for start in starts {
+            if previous.insert(start, None).is_none() {
+                queue.push_back(*start);
+            }
+        }
+
+        while let Some(schema) = queue.pop_front() {
+            if schema == to {
+                let mut hops = vec![to.to_string()];
+                let mut current = schema;
+                while let Some((prev, field)) = previous[current] {
+                    hops.push(hop(prev, field));
+                    current = prev;
+                }
+                hops.reverse();
+                return Some(hops);
+            }
+            for (next, fields) in self.ref_edges(schema) {
+                if !previous.contains_key(next) {
+                    previous.insert(next, Some((schema, fields[0].0)));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    fn collect_routes<'a>(
+        &'a self,
+        schema: &'a str,
+        to: &str,
+        leads_to: &HashSet<String>,
+        route: &mut Vec<String>,
+        on_route: &mut HashSet<&'a str>,
+        routes: &mut Vec<Vec<String>>,
+    ) {
+        if routes.len() >= MAX_CHAINS {
+            return;
+        }
+        if schema == to {
+            let mut complete = route.clone();
+            complete.push(to.to_string());
+            routes.push(complete);
+            return;
+        }
+
+        on_route.insert(schema);
+        for (next, fields) in self.ref_edges(schema) {
+            if on_route.contains(next) || !leads_to.contains(next) {
+                continue;
+            }
+            for (field, _) in fields {
+                route.push(hop(schema, field));
+                self.collect_routes(next, to, leads_to, route, on_route, routes);
+                route.pop();
+            }
+        }
+        on_route.remove(schema);
+    }
+
+    /// Query dependencies up to `max_depth` hops away. Downstream of a
+    /// schema are the schemas and paths depending on it, downstream of a
+    /// path the other paths sharing a schema with it; upstream of either are
+    /// the schemas it depends on. With `all_chains` every reference chain is
+    /// reported rather than the shortest one per result
     pub fn query(
         &self,
         target: &str,
         direction: DependencyDirection,
         is_schema: bool,
+        all_chains: bool,
+        max_depth: Option<usize>,
     ) -> DependencyQueryResult {
         let mut result = DependencyQueryResult {
             target: target.to_string(),
@@ -170,34 +491,230 @@ impl DependencyGraph {
             direction,
             affected_paths: HashSet::new(),
             affected_schemas: HashSet::new(),
+            depths: BTreeMap::new(),
             dependency_chain: Vec::new(),
         };
 
-        if is_schema {
-            match direction {
-                DependencyDirection::Downstream => {
-                    result.affected_paths = self.get_affected_paths(target);
-                    result.affected_schemas = self.get_schema_dependents(target);
-                }
-                DependencyDirection::Upstream => {
-                    if let Some(deps) = self.schema_to_schemas.get(target) {
-                        result.affected_schemas = deps.clone();
+        let mut found: HashMap<&str, usize> = HashMap::new();
+        let mut dependencies = Vec::new();
+        let mut sharing = Vec::new();
+        if direction != DependencyDirection::Downstream {
+            for (schema, depth) in self.distances(target, true, max_depth) {
+                found.insert(schema, depth);
+                dependencies.push(schema);
+            }
+        }
+        if direction != DependencyDirection::Upstream {
+            let downstream = if is_schema {
+                self.distances(target, false, max_depth)
+            } else {
+                self.shared_schema_paths(target, max_depth)
+                    .into_iter()
+                    .map(|(path, schema, depth)| {
+                        sharing.push((path, schema));
+                        (path, depth)
+                    })
+                    .collect()
+            };
+            for (node, depth) in downstream {
+                found
+                    .entry(node)
+                    .and_modify(|d| *d = (*d).min(depth))
+                    .or_insert(depth);
+            }
+        }
+
+        for (node, depth) in found {
+            if self.path_to_schemas.contains_key(node) {
+                result.affected_paths.insert(node.to_string());
+            } else {
+                result.affected_schemas.insert(node.to_string());
+            }
+            result.depths.insert(node.to_string(), depth);
+        }
+
+        // Explain each result: paths reach the target schema, the target
+        // (schema or path) reaches the schemas it depends on, and a path
+        // sharing a schema with the target path reaches it from the other side
+        let mut chains = Vec::new();
+        if is_schema && direction != DependencyDirection::Upstream {
+            let mut paths: Vec<_> = result.affected_paths.iter().collect();
+            paths.sort();
+            for path in paths {
+                chains.extend(self.dependency_chains(path, true, target, all_chains));
+            }
+        }
+        dependencies.sort();
+        for schema in dependencies {
+            chains.extend(self.dependency_chains(target, !is_schema, schema, all_chains));
+        }
+        sharing.sort();
+        for (path, schema) in sharing {
+            let ours = self.dependency_chains(target, true, schema, all_chains);
+            let theirs = self.dependency_chains(path, true, schema, all_chains);
+            let joined = ours.iter().flat_map(|ours| {
+                theirs.iter().map(|other| {
+                    // `a -> S` and `b -> X -> S` meet as `a -> S <- X <- b`
+                    let back: Vec<&str> = other.chain.rsplit(" -> ").skip(1).collect();
+                    DependencyChain {
+                        from: target.to_string(),
+                        to: path.to_string(),
+                        chain: format!("{} <- {}", ours.chain, back.join(" <- ")),
                     }
+                })
+            });
+            chains.extend(joined.take(MAX_CHAINS));
+        }
+        result.dependency_chain = chains;
+
+        result
+    }
+
+    /// Hops from `start` to every node reached following references
+    /// (`upstream`: path → schema → referenced schema) or usages (schema →
+    /// referencing schema or path), up to `max_depth`; `start` is left out
+    fn distances<'a>(
+        &'a self,
+        start: &'a str,
+        upstream: bool,
+        max_depth: Option<usize>,
+    ) -> HashMap<&'a str, usize> {
+        let (first, second) = if upstream {
+            (&self.path_to_schemas, &self.schema_to_schemas)
+        } else {
+            (&self.schema_refs, &self.schema_to_paths)
+        };
+
+        let mut depths = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let depth = depths[node] + 1;
+            if max_depth.is_some_and(|max| depth > max) {
+                continue;
+            }
+            for next in [first.get(node), second.get(node)]
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                if !depths.contains_key(next.as_str()) {
+                    depths.insert(next, depth);
+                    queue.push_back(next);
+                }
+            }
+        }
+        depths.remove(start);
+        depths
+    }
+
+    /// Other paths depending on a schema `path` also depends on, each with
+    /// the shared schema closest to both and the hops between the two paths
+    /// through it, within `max_depth`
+    fn shared_schema_paths<'a>(
+        &'a self,
+        path: &'a str,
+        max_depth: Option<usize>,
+    ) -> Vec<(&'a str, &'a str, usize)> {
+        let ours = self.distances(path, true, None);
+        let mut others: Vec<&str> = self
+            .path_to_schemas
+            .keys()
+            .map(String::as_str)
+            .filter(|other| *other != path)
+            .collect();
+        others.sort();
+
+        others
+            .into_iter()
+            .filter_map(|other| {
+                self.distances(other, true, None)
+                    .into_iter()
+                    .filter_map(|(schema, depth)| ours.get(schema).map(|d| (d + depth, schema)))
+                    .min()
+                    .filter(|(depth, _)| max_depth.is_none_or(|max| *depth <= max))
+                    .map(|(depth, schema)| (other, schema, depth))
+            })
+            .collect()
+    }
+
+    /// The whole graph, or with `roots` (paths and schemas) the part
+    /// connected to them: what each root depends on and what depends on it.
+    /// A path root is kept even when it uses no schemas
+    pub fn export(&self, roots: Option<&[GraphNode]>) -> GraphExport {
+        let mut paths: HashSet<&str> = HashSet::new();
+        let mut schemas: HashSet<String> = HashSet::new();
+        match roots {
+            None => {
+                paths.extend(self.path_to_schemas.keys().map(String::as_str));
+                schemas.extend(self.schema_to_paths.keys().cloned());
+                for (schema, deps) in &self.schema_to_schemas {
+                    schemas.insert(schema.clone());
+                    schemas.extend(deps.iter().cloned());
                 }
-                DependencyDirection::Both => {
-                    result.affected_paths = self.get_affected_paths(target);
-                    result.affected_schemas = self.get_schema_dependents(target);
-                    if let Some(deps) = self.schema_to_schemas.get(target) {
-                        result.affected_schemas.extend(deps.clone());
+            }
+            Some(roots) => {
+                for GraphNode { id: root, kind } in roots {
+                    if *kind == GraphNodeKind::Path {
+                        paths.insert(root);
+                        schemas.extend(self.get_path_schemas(root));
+                    } else {
+                        let affected = self.get_affected_paths(root);
+                        paths.extend(
+                            self.path_to_schemas
+                                .keys()
+                                .filter(|p| affected.contains(*p))
+                                .map(String::as_str),
+                        );
+                        schemas.extend(self.get_schema_dependents(root));
+                        self.collect_schema_deps_recursive(root, &mut schemas, &mut HashSet::new());
                     }
                 }
             }
-        } else {
-            // Target is a path
-            result.affected_schemas = self.get_path_schemas(target);
         }
 
-        result
+        let mut nodes: Vec<GraphNode> = paths
+            .iter()
+            .map(|path| GraphNode {
+                id: path.to_string(),
+                kind: GraphNodeKind::Path,
+            })
+            .chain(schemas.iter().map(|schema| GraphNode {
+                id: schema.clone(),
+                kind: GraphNodeKind::Schema,
+            }))
+            .collect();
+        nodes.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.id.cmp(&b.id)));
+
+        let mut edges = Vec::new();
+        for path in &paths {
+            for schema in self.path_to_schemas.get(*path).into_iter().flatten() {
+                if schemas.contains(schema) {
+                    edges.push(GraphEdge {
+                        from: path.to_string(),
+                        to: schema.clone(),
+                        fields: vec![],
+                    });
+                }
+            }
+        }
+        for schema in &schemas {
+            for (to, fields) in self.ref_edges(schema) {
+                if schemas.contains(to) {
+                    edges.push(GraphEdge {
+                        from: schema.clone(),
+                        to: to.to_string(),
+                        fields: fields
+                            .into_iter()
+                            .filter(|(field, _)| !field.is_empty())
+                            .map(|(field, _)| field.to_string())
+                            .collect(),
+                    });
+                }
+            }
+        }
+        edges.sort_by(|a, b| a.from.cmp(&b.from).then_with(|| a.to.cmp(&b.to)));
+
+        GraphExport { nodes, edges }
     }
 
     /// Get statistics about the graph
@@ -221,7 +738,164 @@ pub struct DependencyQueryResult {
     pub direction: DependencyDirection,
     pub affected_paths: HashSet<String>,
     pub affected_schemas: HashSet<String>,
-    pub dependency_chain: Vec<String>,
+    /// Hops from the target to each result
+    pub depths: BTreeMap<String, usize>,
+    /// Why each result is affected
+    pub dependency_chain: Vec<DependencyChain>,
+}
+
+/// Reference chain between a path or schema and a schema it depends on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyChain {
+    pub from: String,
+    pub to: String,
+    /// Hops naming the property followed, e.g. `GET:/posts/{id} -> Post.author -> User`
+    pub chain: String,
+}
+
+/// Schemas referencing each other in a cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaCycle {
+    /// Members, sorted
+    pub schemas: Vec<String>,
+    /// References between members
+    pub edges: Vec<CycleEdge>,
+    /// Every loop has an optional reference, so finite instances exist
+    pub broken_by_optional: bool,
+    /// Every loop passes through an array or map, so generated types need
+    /// no extra indirection (`Box<>`) to be finite in size
+    pub broken_by_array: bool,
+}
+
+/// Reference from one cycle member to another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleEdge {
+    pub from: String,
+    pub to: String,
+    /// Property path holding the reference (empty at the schema's root)
+    pub field: String,
+    #[serde(flatten)]
+    pub kind: ReferenceKind,
+}
+
+/// State of Tarjan's strongly connected components algorithm
+#[derive(Default)]
+struct Tarjan<'a> {
+    index: HashMap<&'a str, usize>,
+    low_link: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: HashSet<&'a str>,
+    components: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn connect(&mut self, graph: &'a DependencyGraph, node: &'a str) {
+        let index = self.index.len();
+        self.index.insert(node, index);
+        self.low_link.insert(node, index);
+        self.stack.push(node);
+        self.on_stack.insert(node);
+
+        for next in graph.schema_to_schemas.get(node).into_iter().flatten() {
+            let next = next.as_str();
+            if !self.index.contains_key(next) {
+                self.connect(graph, next);
+                let low = self.low_link[node].min(self.low_link[next]);
+                self.low_link.insert(node, low);
+            } else if self.on_stack.contains(next) {
+                let low = self.low_link[node].min(self.index[next]);
+                self.low_link.insert(node, low);
+            }
+        }
+
+        if self.low_link[node] == index {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+/// Whether `members` stay acyclic once references matching `breaks` are removed
+fn is_acyclic(
+    members: &[&str],
+    edges: &[CycleEdge],
+    breaks: impl Fn(&ReferenceKind) -> bool,
+) -> bool {
+    // Kahn's algorithm over the remaining references
+    let remaining: Vec<&CycleEdge> = edges.iter().filter(|e| !breaks(&e.kind)).collect();
+    let mut in_degree: HashMap<&str, usize> = members.iter().map(|m| (*m, 0)).collect();
+    for edge in &remaining {
+        *in_degree.get_mut(edge.to.as_str()).unwrap() += 1;
+    }
+    let mut ready: Vec<&str> = members
+        .iter()
+        .copied()
+        .filter(|m| in_degree[m] == 0)
+        .collect();
+    let mut visited = 0;
+    while let Some(node) = ready.pop() {
+        visited += 1;
+        for edge in remaining.iter().filter(|e| e.from == node) {
+            let degree = in_degree.get_mut(edge.to.as_str()).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push(edge.to.as_str());
+            }
+        }
+    }
+    visited == members.len()
+}
+
+/// Limit on chains listed per source and target when all are requested
+const MAX_CHAINS: usize = 100;
+
+/// `Schema.field`, or the bare schema name for a root-level reference
+fn hop(schema: &str, field: &str) -> String {
+    match field.chars().next() {
+        None => schema.to_string(),
+        Some('[' | '{') => format!("{schema}{field}"),
+        Some(_) => format!("{schema}.{field}"),
+    }
+}
+
+/// Nodes and edges of a dependency graph, or part of one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphExport {
+    /// Paths first, then schemas, each sorted
+    pub nodes: Vec<GraphNode>,
+    /// Path → schema uses and schema → schema references, sorted
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Path key (`get:/pets`, webhook key) or schema name
+    pub id: String,
+    pub kind: GraphNodeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphNodeKind {
+    Path,
+    Schema,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// Property paths holding a schema reference; empty for path edges and
+    /// root-level references
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
 }
 
 /// Statistics about the dependency graph
@@ -255,4 +929,167 @@ mod tests {
         assert!(affected.contains("GET:/users/{id}"));
         assert!(affected.contains("GET:/posts/{id}")); // Via Post -> User
     }
+
+    #[test]
+    fn test_schema_usage_follows_dependents() {
+        let mut graph = DependencyGraph::new();
+
+        graph.add_schema_schema_dep("CreatePost", "User");
+        graph.add_schema_schema_dep("Post", "User");
+        graph.add_schema_usage("CreatePost", SchemaUsage::REQUEST);
+        graph.add_schema_usage("Post", SchemaUsage::RESPONSE);
+
+        assert_eq!(graph.get_schema_usage("CreatePost"), SchemaUsage::REQUEST);
+        assert_eq!(graph.get_schema_usage("User"), SchemaUsage::BOTH);
+        assert!(graph.get_schema_usage("Orphan").is_unused());
+    }
+
+    #[test]
+    fn test_dependency_chains() {
+        let mut graph = DependencyGraph::new();
+
+        graph.add_path_schema_dep("GET:/posts/{id}", "Post");
+        graph.add_path_schema_dep("GET:/users", "User");
+        graph.add_schema_field_dep("Post", "author", "User", ReferenceKind::default());
+        graph.add_schema_field_dep("Post", "comments[]", "Comment", ReferenceKind::default());
+        graph.add_schema_field_dep("Comment", "author", "User", ReferenceKind::default());
+        // Cycle back to the post must not loop
+        graph.add_schema_field_dep("Comment", "post", "Post", ReferenceKind::default());
+
+        let result = graph.query("User", DependencyDirection::Downstream, true, false, None);
+        let chains: Vec<_> = result
+            .dependency_chain
+            .iter()
+            .map(|c| c.chain.as_str())
+            .collect();
+        assert_eq!(
+            chains,
+            vec![
+                "GET:/posts/{id} -> Post.author -> User",
+                "GET:/users -> User"
+            ]
+        );
+
+        let all: Vec<_> = graph
+            .dependency_chains("GET:/posts/{id}", true, "User", true)
+            .into_iter()
+            .map(|c| c.chain)
+            .collect();
+        assert_eq!(
+            all,
+            vec![
+                "GET:/posts/{id} -> Post.author -> User",
+                "GET:/posts/{id} -> Post.comments[] -> Comment.author -> User",
+            ]
+        );
+
+        // Path targets explain each schema they reach
+        let result = graph.query(
+            "GET:/posts/{id}",
+            DependencyDirection::Upstream,
+            false,
+            false,
+            None,
+        );
+        let comment = result.dependency_chain.iter().find(|c| c.to == "Comment");
+        assert_eq!(
+            comment.map(|c| c.chain.as_str()),
+            Some("GET:/posts/{id} -> Post.comments[] -> Comment")
+        );
+    }
+
+    #[test]
+    fn test_query_depths() {
+        let mut graph = DependencyGraph::new();
+
+        graph.add_path_schema_dep("get:/posts", "Post");
+        graph.add_path_schema_dep("get:/users", "UserList");
+        graph.add_path_schema_dep("get:/tags", "Tag");
+        graph.add_schema_field_dep("Post", "author", "User", ReferenceKind::default());
+        graph.add_schema_field_dep("UserList", "items[]", "User", ReferenceKind::default());
+        graph.add_schema_field_dep("User", "address", "Address", ReferenceKind::default());
+
+        // Upstream of a schema is transitive
+        let result = graph.query("Post", DependencyDirection::Upstream, true, false, None);
+        assert_eq!(result.depths["User"], 1);
+        assert_eq!(result.depths["Address"], 2);
+
+        let result = graph.query(
+            "Address",
+            DependencyDirection::Downstream,
+            true,
+            false,
+            Some(2),
+        );
+        let depths: Vec<_> = result
+            .depths
+            .iter()
+            .map(|(n, d)| (n.as_str(), *d))
+            .collect();
+        assert_eq!(depths, vec![("Post", 2), ("User", 1), ("UserList", 2)]);
+
+        // Other endpoints sharing schemas, through the closest shared one
+        let result = graph.query(
+            "get:/posts",
+            DependencyDirection::Downstream,
+            false,
+            false,
+            None,
+        );
+        assert_eq!(
+            result.affected_paths,
+            HashSet::from(["get:/users".to_string()])
+        );
+        assert_eq!(result.depths["get:/users"], 4);
+        assert_eq!(
+            result.dependency_chain[0].chain,
+            "get:/posts -> Post.author -> User <- UserList.items[] <- get:/users"
+        );
+        let result = graph.query(
+            "get:/posts",
+            DependencyDirection::Both,
+            false,
+            false,
+            Some(3),
+        );
+        assert!(result.affected_paths.is_empty());
+        assert_eq!(result.affected_schemas.len(), 3);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let mut graph = DependencyGraph::new();
+        let required = ReferenceKind::default();
+        let optional = ReferenceKind {
+            optional: true,
+            collection: false,
+        };
+        let collection = ReferenceKind {
+            optional: false,
+            collection: true,
+        };
+
+        graph.add_schema_field_dep("Node", "children[]", "Node", collection);
+        graph.add_schema_field_dep("Person", "spouse", "Person", optional);
+        graph.add_schema_field_dep("Egg", "chicken", "Chicken", required);
+        graph.add_schema_field_dep("Chicken", "egg", "Egg", required);
+        // Not part of any cycle
+        graph.add_schema_field_dep("Farm", "chickens[]", "Chicken", collection);
+
+        let cycles = graph.cycles();
+        let summary: Vec<_> = cycles
+            .iter()
+            .map(|c| (c.schemas.join(","), c.broken_by_optional, c.broken_by_array))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Chicken,Egg".to_string(), false, false),
+                ("Node".to_string(), false, true),
+                ("Person".to_string(), true, false),
+            ]
+        );
+        assert_eq!(cycles[0].edges.len(), 2);
+        assert_eq!(cycles[1].edges[0].field, "children[]");
+    }
 }
@@ -1,7 +1,7 @@
 //! OpenAPI type definitions
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Supported OpenAPI versions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -34,6 +34,7 @@ pub struct SpecMetadata {
     pub endpoint_count: usize,
     pub schema_count: usize,
     pub tag_count: usize,
+    pub webhook_count: usize,
 }
 
 /// HTTP method
@@ -75,6 +76,42 @@ pub enum ParameterLocation {
     Cookie,
 }
 
+impl std::fmt::Display for ParameterLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path => write!(f, "path"),
+            Self::Query => write!(f, "query"),
+            Self::Header => write!(f, "header"),
+            Self::Cookie => write!(f, "cookie"),
+        }
+    }
+}
+
+impl ParameterLocation {
+    /// Serialization style used when a parameter does not declare one
+    pub fn default_style(self) -> &'static str {
+        match self {
+            Self::Query | Self::Cookie => "form",
+            Self::Path | Self::Header => "simple",
+        }
+    }
+}
+
+/// `x-deprecated-at` / `x-sunset` extension dates, as written in the spec
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeprecationDates {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
+}
+
+impl DeprecationDates {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Parameter definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Parameter {
@@ -84,6 +121,47 @@ pub struct Parameter {
     pub description: Option<String>,
     pub schema_ref: Option<String>,
     pub schema_type: Option<String>,
+    pub format: Option<String>,
+    /// Effective serialization style (defaults applied)
+    pub style: String,
+    /// Effective `explode` (defaults to true for `form` style)
+    pub explode: bool,
+    #[serde(default)]
+    pub deprecated: bool,
+    #[serde(default, skip_serializing_if = "DeprecationDates::is_empty")]
+    pub deprecation_dates: DeprecationDates,
+}
+
+/// Schema of one media type in a request body or response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaType {
+    pub content_type: String,
+    pub schema_ref: Option<String>,
+    /// Anonymous schema, when the media type does not `$ref` a component
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaType>,
+}
+
+/// Response header definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseHeader {
+    pub name: String,
+    pub required: bool,
+    pub description: Option<String>,
+    pub schema_ref: Option<String>,
+    pub schema_type: Option<String>,
+}
+
+/// Design-time link from a response to another operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseLink {
+    pub name: String,
+    pub operation_id: Option<String>,
+    pub operation_ref: Option<String>,
+    /// Target parameter → runtime expression
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, String>,
+    pub description: Option<String>,
 }
 
 /// Request body definition
@@ -92,7 +170,10 @@ pub struct RequestBody {
     pub required: bool,
     pub description: Option<String>,
     pub content_types: Vec<String>,
+    /// Schema of the preferred (JSON if available) media type
     pub schema_ref: Option<String>,
+    /// Per-media-type schemas
+    pub content: Vec<MediaType>,
 }
 
 /// Response definition
@@ -101,9 +182,64 @@ pub struct Response {
     pub status_code: String,
     pub description: Option<String>,
     pub content_types: Vec<String>,
+    /// Schema of the preferred (JSON if available) media type
     pub schema_ref: Option<String>,
+    /// Per-media-type schemas
+    pub content: Vec<MediaType>,
+    pub headers: Vec<ResponseHeader>,
+    pub links: Vec<ResponseLink>,
+}
+
+/// Pick the media type clients should prefer: `application/json`, then any
+/// `+json` type, then the first declared
+pub fn preferred_media_type(content: &[MediaType]) -> Option<&MediaType> {
+    content
+        .iter()
+        .find(|m| m.content_type == "application/json")
+        .or_else(|| content.iter().find(|m| m.content_type.ends_with("+json")))
+        .or_else(|| content.first())
+}
+
+/// Server the API is served from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Server {
+    pub url: String,
+    pub description: Option<String>,
+    /// Server variable → default value
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
 }
 
+/// Security scheme definition (`components.securitySchemes` / `securityDefinitions`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SecurityScheme {
+    /// HTTP authentication (`basic`, `bearer`, ...)
+    #[serde(rename = "http")]
+    Http {
+        scheme: String,
+        bearer_format: Option<String>,
+    },
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        name: String,
+        location: ParameterLocation,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        /// Flow names (`authorizationCode`, `clientCredentials`, ...)
+        flows: Vec<String>,
+    },
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect { open_id_connect_url: String },
+    #[serde(rename = "mutualTLS")]
+    MutualTls,
+}
+
+/// Security requirement: scheme name → required scopes.
+/// All schemes in one requirement apply together; requirements are alternatives.
+pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
 /// Parsed endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Endpoint {
@@ -117,6 +253,12 @@ pub struct Endpoint {
     pub request_body: Option<RequestBody>,
     pub responses: HashMap<String, Response>,
     pub deprecated: bool,
+    #[serde(default, skip_serializing_if = "DeprecationDates::is_empty")]
+    pub deprecation_dates: DeprecationDates,
+    /// Effective servers (operation, then path item, then root)
+    pub servers: Vec<Server>,
+    /// Effective security requirements (empty = no auth)
+    pub security: Vec<SecurityRequirement>,
     /// Hash for change detection
     pub hash: String,
     /// Schema references used by this endpoint
@@ -143,45 +285,640 @@ impl Endpoint {
     }
 }
 
-/// Schema type
+/// Kind of operation initiated by the API provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    /// 3.1 top-level `webhooks` entry
+    Webhook,
+    /// Operation-level `callbacks` entry
+    Callback,
+}
+
+/// Operation the API calls on the consumer
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub kind: WebhookKind,
+    /// Webhook name, or callback name
+    pub name: String,
+    /// Callbacks: key of the endpoint declaring the callback
+    pub parent: Option<String>,
+    /// Callbacks: runtime expression for the callback URL
+    pub expression: Option<String>,
+    /// The operation itself; `path` is the webhook name or callback expression
+    pub endpoint: Endpoint,
+}
+
+impl Webhook {
+    /// Get a unique key for this webhook
+    pub fn key(&self) -> String {
+        let method = self.endpoint.method.to_string().to_lowercase();
+        match (&self.kind, &self.parent) {
+            (WebhookKind::Callback, Some(parent)) => {
+                let expression = self.expression.as_deref().unwrap_or_default();
+                format!("callback:{parent}:{}:{expression}:{method}", self.name)
+            }
+            _ => format!("webhook:{}:{method}", self.name),
+        }
+    }
+}
+
+/// Schema type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum SchemaType {
     String {
         format: Option<String>,
         #[serde(rename = "enum")]
         enum_values: Option<Vec<String>>,
+        #[serde(default, skip_serializing_if = "SchemaConstraints::is_empty")]
+        constraints: SchemaConstraints,
     },
     Number {
         format: Option<String>,
+        #[serde(default, skip_serializing_if = "SchemaConstraints::is_empty")]
+        constraints: SchemaConstraints,
     },
     Integer {
         format: Option<String>,
+        #[serde(default, skip_serializing_if = "SchemaConstraints::is_empty")]
+        constraints: SchemaConstraints,
     },
-    Boolean,
+    Boolean {
+        #[serde(default, skip_serializing_if = "SchemaConstraints::is_empty")]
+        constraints: SchemaConstraints,
+    },
+    Null,
     Array {
         items: Box<SchemaType>,
+        #[serde(default, skip_serializing_if = "SchemaConstraints::is_empty")]
+        constraints: SchemaConstraints,
+    },
+    /// Positional array (`prefixItems`)
+    Tuple {
+        prefix_items: Vec<SchemaType>,
+        /// Schema for items past the prefix (`None` = unspecified)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        items: Option<AdditionalSchema>,
     },
     Object {
         properties: HashMap<String, SchemaType>,
         required: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        additional_properties: Option<AdditionalSchema>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unevaluated_properties: Option<AdditionalSchema>,
+        #[serde(default, skip_serializing_if = "SchemaConstraints::is_empty")]
+        constraints: SchemaConstraints,
+    },
+    /// String-keyed map (`additionalProperties` without `properties`)
+    Map {
+        values: Box<SchemaType>,
+        #[serde(default, skip_serializing_if = "SchemaConstraints::is_empty")]
+        constraints: SchemaConstraints,
     },
     Ref {
         #[serde(rename = "$ref")]
         reference: String,
     },
+    /// `T | null` (3.1 `type: [T, "null"]`)
+    Nullable {
+        inner: Box<SchemaType>,
+    },
+    Const {
+        value: serde_json::Value,
+    },
     OneOf {
         variants: Vec<SchemaType>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminator: Option<Discriminator>,
     },
     AnyOf {
         variants: Vec<SchemaType>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminator: Option<Discriminator>,
     },
     AllOf {
         variants: Vec<SchemaType>,
     },
+    /// `if` / `then` / `else`
+    Conditional {
+        /// Shape declared next to `if` (type, properties, constraints)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base: Option<Box<SchemaType>>,
+        #[serde(rename = "if")]
+        condition: Box<SchemaType>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        then: Option<Box<SchemaType>>,
+        #[serde(rename = "else", default, skip_serializing_if = "Option::is_none")]
+        otherwise: Option<Box<SchemaType>>,
+    },
     Unknown,
 }
 
+/// How a schema holds a reference to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ReferenceKind {
+    /// Instances may leave it out: an optional property, a nullable value or
+    /// one of several `oneOf`/`anyOf` variants
+    pub optional: bool,
+    /// Held in an array or map
+    pub collection: bool,
+}
+
+/// Reference to a component schema inside a shape
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaRefSite {
+    pub reference: String,
+    /// Property path holding the reference (`author`, `tags[]`, `meta{}`;
+    /// empty at the shape's root)
+    pub field: String,
+    pub kind: ReferenceKind,
+}
+
+/// `discriminator` object of a `oneOf`/`anyOf`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Discriminator {
+    pub property_name: String,
+    /// Tag value → schema name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mapping: BTreeMap<String, String>,
+}
+
+impl Discriminator {
+    /// Tag value → schema name for every variant, using the schema name
+    /// as the implicit tag for `$ref` variants without an explicit mapping
+    pub fn tags(&self, variants: &[SchemaType]) -> Vec<(String, String)> {
+        let mut tags: Vec<(String, String)> = self
+            .mapping
+            .iter()
+            .map(|(tag, schema)| (tag.clone(), schema.clone()))
+            .collect();
+
+        for variant in variants {
+            if let SchemaType::Ref { reference } = variant
+                && !self.mapping.values().any(|schema| schema == reference)
+            {
+                tags.push((reference.clone(), reference.clone()));
+            }
+        }
+
+        tags
+    }
+}
+
+/// Value annotations and validation keywords of a schema
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaConstraints {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    /// Normalized to the 3.1 numeric form
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unique_items: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub write_only: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    #[serde(default, skip_serializing_if = "DeprecationDates::is_empty")]
+    pub deprecation_dates: DeprecationDates,
+}
+
+impl SchemaConstraints {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl SchemaType {
+    /// Constraints of a leaf type, looking through `Nullable` and the base
+    /// shape of a conditional
+    pub fn constraints(&self) -> Option<&SchemaConstraints> {
+        match self {
+            Self::String { constraints, .. }
+            | Self::Number { constraints, .. }
+            | Self::Integer { constraints, .. }
+            | Self::Boolean { constraints }
+            | Self::Array { constraints, .. }
+            | Self::Object { constraints, .. }
+            | Self::Map { constraints, .. } => Some(constraints),
+            Self::Nullable { inner } => inner.constraints(),
+            Self::Conditional {
+                base: Some(base), ..
+            } => base.constraints(),
+            _ => None,
+        }
+    }
+
+    /// The shape a conditional applies on top of, or the schema itself
+    pub fn base_shape(&self) -> &SchemaType {
+        match self {
+            Self::Conditional {
+                base: Some(base), ..
+            } => base,
+            other => other,
+        }
+    }
+
+    /// Like `collect_refs`, with where and how each reference is held
+    pub fn collect_ref_sites(&self, out: &mut Vec<SchemaRefSite>) {
+        self.ref_sites("", ReferenceKind::default(), out);
+    }
+
+    fn ref_sites(&self, field: &str, kind: ReferenceKind, out: &mut Vec<SchemaRefSite>) {
+        let optional = ReferenceKind {
+            optional: true,
+            ..kind
+        };
+        let collection = ReferenceKind {
+            collection: true,
+            ..kind
+        };
+        let additional = |schema: &Option<AdditionalSchema>, field: &str, out: &mut _| {
+            if let Some(AdditionalSchema::Schema(schema)) = schema {
+                schema.ref_sites(field, collection, out);
+            }
+        };
+
+        match self {
+            Self::Ref { reference } => out.push(SchemaRefSite {
+                reference: reference.clone(),
+                field: field.to_string(),
+                kind,
+            }),
+            Self::Array { items, .. } => items.ref_sites(&format!("{field}[]"), collection, out),
+            Self::Map { values, .. } => values.ref_sites(&format!("{field}{{}}"), collection, out),
+            Self::Nullable { inner } => inner.ref_sites(field, optional, out),
+            Self::Tuple {
+                prefix_items,
+                items,
+            } => {
+                for (index, item) in prefix_items.iter().enumerate() {
+                    item.ref_sites(&format!("{field}[{index}]"), kind, out);
+                }
+                additional(items, &format!("{field}[]"), out);
+            }
+            Self::Object {
+                properties,
+                required,
+                additional_properties,
+                unevaluated_properties,
+                ..
+            } => {
+                for (name, property) in properties {
+                    let path = if field.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{field}.{name}")
+                    };
+                    let kind = if required.contains(name) {
+                        kind
+                    } else {
+                        optional
+                    };
+                    property.ref_sites(&path, kind, out);
+                }
+                let values = format!("{field}{{}}");
+                additional(additional_properties, &values, out);
+                additional(unevaluated_properties, &values, out);
+            }
+            Self::OneOf {
+                variants,
+                discriminator,
+            }
+            | Self::AnyOf {
+                variants,
+                discriminator,
+            } => {
+                let kind = if variants.len() > 1 { optional } else { kind };
+                variants.iter().for_each(|s| s.ref_sites(field, kind, out));
+                // Mapping targets beyond the listed variants are alternatives too
+                for reference in mapped_only(variants, discriminator) {
+                    out.push(SchemaRefSite {
+                        reference: reference.clone(),
+                        field: field.to_string(),
+                        kind: optional,
+                    });
+                }
+            }
+            Self::AllOf { variants } => variants.iter().for_each(|s| s.ref_sites(field, kind, out)),
+            Self::Conditional {
+                base,
+                condition,
+                then,
+                otherwise,
+            } => {
+                base.iter().for_each(|s| s.ref_sites(field, kind, out));
+                condition.ref_sites(field, optional, out);
+                then.iter()
+                    .chain(otherwise)
+                    .for_each(|s| s.ref_sites(field, optional, out));
+            }
+            _ => {}
+        }
+    }
+
+    /// Names of all component schemas referenced anywhere inside this shape
+    pub fn collect_refs(&self, out: &mut Vec<String>) {
+        let additional = |schema: &Option<AdditionalSchema>, out: &mut Vec<String>| {
+            if let Some(AdditionalSchema::Schema(schema)) = schema {
+                schema.collect_refs(out);
+            }
+        };
+
+        match self {
+            Self::Ref { reference } => out.push(reference.clone()),
+            Self::Array { items, .. } => items.collect_refs(out),
+            Self::Map { values, .. } => values.collect_refs(out),
+            Self::Nullable { inner } => inner.collect_refs(out),
+            Self::Tuple {
+                prefix_items,
+                items,
+            } => {
+                prefix_items.iter().for_each(|s| s.collect_refs(out));
+                additional(items, out);
+            }
+            Self::Object {
+                properties,
+                additional_properties,
+                unevaluated_properties,
+                ..
+            } => {
+                properties.values().for_each(|s| s.collect_refs(out));
+                additional(additional_properties, out);
+                additional(unevaluated_properties, out);
+            }
+            Self::OneOf {
+                variants,
+                discriminator,
+            }
+            | Self::AnyOf {
+                variants,
+                discriminator,
+            } => {
+                variants.iter().for_each(|s| s.collect_refs(out));
+                out.extend(mapped_only(variants, discriminator).cloned());
+            }
+            Self::AllOf { variants } => variants.iter().for_each(|s| s.collect_refs(out)),
+            Self::Conditional {
+                base,
+                condition,
+                then,
+                otherwise,
+            } => {
+                condition.collect_refs(out);
+                base.iter()
+                    .chain(then)
+                    .chain(otherwise)
+                    .for_each(|s| s.collect_refs(out));
+            }
+            _ => {}
+        }
+    }
+
+    /// Rewrite component references (including discriminator mappings)
+    /// through `renames`, old name → new name
+    pub fn rename_refs(&mut self, renames: &HashMap<String, String>) {
+        let additional = |schema: &mut Option<AdditionalSchema>| {
+            if let Some(AdditionalSchema::Schema(schema)) = schema {
+                schema.rename_refs(renames);
+            }
+        };
+
+        match self {
+            Self::Ref { reference } => rename_ref(reference, renames),
+            Self::Array { items, .. } => items.rename_refs(renames),
+            Self::Map { values, .. } => values.rename_refs(renames),
+            Self::Nullable { inner } => inner.rename_refs(renames),
+            Self::Tuple {
+                prefix_items,
+                items,
+            } => {
+                prefix_items.iter_mut().for_each(|s| s.rename_refs(renames));
+                additional(items);
+            }
+            Self::Object {
+                properties,
+                additional_properties,
+                unevaluated_properties,
+                ..
+            } => {
+                properties.values_mut().for_each(|s| s.rename_refs(renames));
+                additional(additional_properties);
+                additional(unevaluated_properties);
+            }
+            Self::OneOf {
+                variants,
+                discriminator,
+            }
+            | Self::AnyOf {
+                variants,
+                discriminator,
+            } => {
+                variants.iter_mut().for_each(|s| s.rename_refs(renames));
+                if let Some(discriminator) = discriminator {
+                    discriminator
+                        .mapping
+                        .values_mut()
+                        .for_each(|schema| rename_ref(schema, renames));
+                }
+            }
+            Self::AllOf { variants } => variants.iter_mut().for_each(|s| s.rename_refs(renames)),
+            Self::Conditional {
+                base,
+                condition,
+                then,
+                otherwise,
+            } => {
+                condition.rename_refs(renames);
+                base.iter_mut()
+                    .chain(then)
+                    .chain(otherwise)
+                    .for_each(|s| s.rename_refs(renames));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn rename_ref(reference: &mut String, renames: &HashMap<String, String>) {
+    if let Some(new_name) = renames.get(reference) {
+        *reference = new_name.clone();
+    }
+}
+
+/// Discriminator mapping targets that are not among `variants`
+fn mapped_only<'a>(
+    variants: &'a [SchemaType],
+    discriminator: &'a Option<Discriminator>,
+) -> impl Iterator<Item = &'a String> {
+    discriminator
+        .iter()
+        .flat_map(|d| d.mapping.values())
+        .filter(|target| {
+            !variants
+                .iter()
+                .any(|v| matches!(v, SchemaType::Ref { reference } if reference == *target))
+        })
+}
+
+impl MediaType {
+    /// Component schemas this media type uses, directly or inside an inline schema
+    pub fn schema_refs(&self) -> Vec<String> {
+        let mut refs: Vec<String> = self.schema_ref.iter().cloned().collect();
+        if let Some(schema) = &self.schema {
+            schema.collect_refs(&mut refs);
+        }
+        refs
+    }
+
+    fn rename_refs(&mut self, renames: &HashMap<String, String>) {
+        if let Some(reference) = &mut self.schema_ref {
+            rename_ref(reference, renames);
+        }
+        if let Some(schema) = &mut self.schema {
+            schema.rename_refs(renames);
+        }
+    }
+}
+
+impl Endpoint {
+    /// Component schemas sent by the caller: parameters and request body
+    pub fn request_schema_refs(&self) -> Vec<String> {
+        let mut refs: Vec<String> = self
+            .parameters
+            .iter()
+            .filter_map(|p| p.schema_ref.clone())
+            .collect();
+        if let Some(body) = &self.request_body {
+            refs.extend(body.content.iter().flat_map(MediaType::schema_refs));
+        }
+        refs
+    }
+
+    /// Component schemas returned to the caller: response bodies and headers
+    pub fn response_schema_refs(&self) -> Vec<String> {
+        self.responses
+            .values()
+            .flat_map(|response| {
+                response
+                    .content
+                    .iter()
+                    .flat_map(MediaType::schema_refs)
+                    .chain(response.headers.iter().filter_map(|h| h.schema_ref.clone()))
+            })
+            .collect()
+    }
+
+    /// Rewrite every component reference through `renames`, old name → new name
+    pub fn rename_refs(&mut self, renames: &HashMap<String, String>) {
+        let optional = |reference: &mut Option<String>| {
+            if let Some(reference) = reference {
+                rename_ref(reference, renames);
+            }
+        };
+
+        for param in &mut self.parameters {
+            optional(&mut param.schema_ref);
+        }
+        if let Some(body) = &mut self.request_body {
+            optional(&mut body.schema_ref);
+            body.content.iter_mut().for_each(|m| m.rename_refs(renames));
+        }
+        for response in self.responses.values_mut() {
+            optional(&mut response.schema_ref);
+            response
+                .content
+                .iter_mut()
+                .for_each(|m| m.rename_refs(renames));
+            for header in &mut response.headers {
+                optional(&mut header.schema_ref);
+            }
+        }
+        for reference in &mut self.schema_refs {
+            rename_ref(reference, renames);
+        }
+    }
+}
+
+/// Boolean-or-schema keyword value (`unevaluatedProperties`, `items` after `prefixItems`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AdditionalSchema {
+    Allowed(bool),
+    Schema(Box<SchemaType>),
+}
+
+impl std::fmt::Display for SchemaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |variants: &[SchemaType], sep: &str| {
+            variants
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+
+        match self {
+            Self::String {
+                format: Some(format),
+                ..
+            } => write!(f, "string({format})"),
+            Self::String { .. } => write!(f, "string"),
+            Self::Number {
+                format: Some(format),
+                ..
+            } => write!(f, "number({format})"),
+            Self::Number { .. } => write!(f, "number"),
+            Self::Integer {
+                format: Some(format),
+                ..
+            } => write!(f, "integer({format})"),
+            Self::Integer { .. } => write!(f, "integer"),
+            Self::Boolean { .. } => write!(f, "boolean"),
+            Self::Null => write!(f, "null"),
+            Self::Array { items, .. } => write!(f, "array<{items}>"),
+            Self::Tuple { prefix_items, .. } => write!(f, "[{}]", join(prefix_items, ", ")),
+            Self::Object { .. } => write!(f, "object"),
+            Self::Map { values, .. } => write!(f, "map<{values}>"),
+            Self::Ref { reference } => write!(f, "{reference}"),
+            Self::Nullable { inner } => write!(f, "{inner} | null"),
+            Self::Const { value } => write!(f, "const {value}"),
+            Self::OneOf { variants, .. } => write!(f, "oneOf<{}>", join(variants, " | ")),
+            Self::AnyOf { variants, .. } => write!(f, "anyOf<{}>", join(variants, " | ")),
+            Self::AllOf { variants } => write!(f, "allOf<{}>", join(variants, " & ")),
+            Self::Conditional {
+                base: Some(base), ..
+            } => write!(f, "{base} & if/then/else"),
+            Self::Conditional { .. } => write!(f, "if/then/else"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
 /// Parsed schema definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Schema {
@@ -199,10 +936,20 @@ pub struct Schema {
 pub struct ParsedSpec {
     pub metadata: SpecMetadata,
     pub endpoints: HashMap<String, Endpoint>,
+    /// Webhooks and callbacks, keyed by `Webhook::key()`
+    pub webhooks: HashMap<String, Webhook>,
     pub schemas: HashMap<String, Schema>,
     pub tags: Vec<String>,
+    /// Root servers (Swagger 2.0: derived from `schemes`/`host`/`basePath`)
+    pub servers: Vec<Server>,
+    pub security_schemes: HashMap<String, SecurityScheme>,
+    /// Root security requirements
+    pub security: Vec<SecurityRequirement>,
     /// Full spec hash for quick comparison
     pub spec_hash: String,
     /// Source location (URL or file path)
     pub source: String,
+    /// Local files bundled in through external `$ref`s
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_files: Vec<String>,
 }
@@ -1,5 +1,8 @@
 //! Integration tests for caching improvements (P0, P1, P2)
 
+// Some assertions predate the clippy gate and are kept as written
+#![allow(clippy::len_zero)]
+
 use openapi_sync_mcp::*;
 use std::path::PathBuf;
 
@@ -168,9 +171,12 @@ async fn test_p0_deps_uses_cache() {
         source: spec_path(&dir),
         schema: Some("User".to_string()),
         path: None,
-        direction: DepsDirection::Downstream,
+        direction: Some(DepsDirection::Downstream),
+        max_depth: None,
         project_dir: Some(project_dir(&dir)),
         use_cache: true,
+        all_chains: false,
+        cycles: false,
     };
 
     let result = query_deps(deps_input).await;
@@ -179,6 +185,11 @@ async fn test_p0_deps_uses_cache() {
         result.affected_paths.len() > 0,
         "User schema should have downstream paths"
     );
+    assert_eq!(
+        result.dependency_chain.len(),
+        result.affected_paths.len(),
+        "each affected path should come with its shortest chain"
+    );
 
     println!(
         "✓ P0: oas_deps uses cache, found {} affected paths",
@@ -522,9 +533,12 @@ async fn test_all_tools_with_cached_parsed_spec() {
         source: spec_path(&dir),
         schema: Some("User".to_string()),
         path: None,
-        direction: DepsDirection::Downstream,
+        direction: Some(DepsDirection::Downstream),
+        max_depth: None,
         project_dir: Some(project_dir(&dir)),
         use_cache: true,
+        all_chains: false,
+        cycles: false,
     };
     let deps_result = query_deps(deps_input).await;
     assert!(deps_result.success, "oas_deps with cache should succeed");
@@ -671,3 +685,59 @@ async fn test_verify_cache_actually_used() {
     // (it re-parses every time)
     println!("   ✅ use_cache=false bypasses cache as expected");
 }
+
+#[tokio::test]
+async fn test_external_ref_change_invalidates_cache() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let root = dir.path().join("api.json");
+    let pet = dir.path().join("pet.json");
+    std::fs::write(
+        &root,
+        r##"{
+  "openapi": "3.0.3",
+  "info": { "title": "Pets", "version": "1.0.0" },
+  "paths": {
+    "/pet": {
+      "get": {
+        "responses": {
+          "200": {
+            "description": "ok",
+            "content": { "application/json": { "schema": { "$ref": "pet.json" } } }
+          }
+        }
+      }
+    }
+  }
+}"##,
+    )
+    .unwrap();
+    std::fs::write(
+        &pet,
+        r#"{ "type": "object", "properties": { "name": { "type": "string" } } }"#,
+    )
+    .unwrap();
+
+    let cache_manager = CacheManager::new(&project_dir(&dir));
+    let source = root.to_string_lossy().to_string();
+    let first = cache_manager.parse_with_cache(&source, None).await.unwrap();
+    assert_eq!(first.external_files.len(), 1);
+
+    // Edit only the referenced file, with a clearly newer mtime
+    std::fs::write(
+        &pet,
+        r#"{ "type": "object", "properties": { "name": { "type": "string" }, "age": { "type": "integer" } } }"#,
+    )
+    .unwrap();
+    std::fs::File::options()
+        .write(true)
+        .open(&pet)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60))
+        .unwrap();
+
+    let second = cache_manager.parse_with_cache(&source, None).await.unwrap();
+    assert_ne!(
+        first.spec_hash, second.spec_hash,
+        "Editing a referenced file should invalidate the cached spec"
+    );
+}
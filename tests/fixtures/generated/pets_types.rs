@@ -0,0 +1,37 @@
+//! Auto-generated Rust types from OpenAPI spec
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cat {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whiskers: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bark: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Owner {
+    pub pets: Vec<Pet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "petType")]
+pub enum Pet {
+    #[serde(rename = "cat")]
+    Cat(Cat),
+    #[serde(rename = "dog")]
+    Dog(Dog),
+}
+
+pub type ListPetsResponse = Vec<Pet>;
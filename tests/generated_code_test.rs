@@ -0,0 +1,464 @@
+//! Integration tests running the code `oas_generate` emits
+
+use openapi_sync_mcp::*;
+use std::path::PathBuf;
+
+/// `types.rs` generated from `fixtures/pets-discriminator.json`, checked
+/// against the generator below
+#[allow(dead_code)]
+#[path = "fixtures/generated/pets_types.rs"]
+mod pets_types;
+
+/// `types.rs` generated from `fixtures/tree.json`; compiling it checks that
+/// every reference cycle is boxed where it has to be
+#[allow(dead_code)]
+#[path = "fixtures/generated/tree_types.rs"]
+mod tree_types;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+async fn generate(spec: &str, target: GenerateTarget) -> Vec<GeneratedFile> {
+    let input = GenerateInput {
+        source: fixture(spec).to_string_lossy().to_string(),
+        target,
+        style: CodeStyle::default(),
+        schemas: vec![],
+        endpoints: vec![],
+        project_dir: None,
+        use_cache: false,
+    };
+    let output = generate_code(input).await;
+    assert!(
+        output.success,
+        "generate should succeed: {:?}",
+        output.error
+    );
+    output.generated_files
+}
+
+#[tokio::test]
+async fn test_rust_tagged_union_round_trip() {
+    let files = generate("pets-discriminator.json", GenerateTarget::RustSerde).await;
+    let types = files.iter().find(|f| f.path == "types.rs").unwrap();
+    let expected = std::fs::read_to_string(fixture("generated/pets_types.rs")).unwrap();
+    assert_eq!(types.content.trim_end(), expected.trim_end());
+
+    let payload = serde_json::json!({
+        "pets": [
+            { "petType": "cat", "name": "Tom" },
+            { "petType": "dog", "bark": true }
+        ]
+    });
+    let owner: pets_types::Owner = serde_json::from_value(payload.clone()).unwrap();
+    assert!(matches!(owner.pets[0], pets_types::Pet::Cat(_)));
+    // The tag is written once, by the enum
+    assert_eq!(serde_json::to_value(&owner).unwrap(), payload);
+}
+
+#[tokio::test]
+async fn test_rust_boxes_unbroken_cycles() {
+    let files = generate("tree.json", GenerateTarget::RustSerde).await;
+    let types = files.iter().find(|f| f.path == "types.rs").unwrap();
+    let expected = std::fs::read_to_string(fixture("generated/tree_types.rs")).unwrap();
+    assert_eq!(types.content.trim_end(), expected.trim_end());
+
+    // Optional self and mutual references are boxed, an array already breaks the cycle
+    assert!(types.content.contains("    pub next: Option<Box<Node>>,\n"));
+    assert!(types.content.contains("    pub team: Option<Box<Team>>,\n"));
+    assert!(types.content.contains("    pub lead: Box<Employee>,\n"));
+    assert!(types.content.contains("    pub children: Vec<Category>,\n"));
+
+    let payload = serde_json::json!({ "value": "a", "next": { "value": "b" } });
+    let node: tree_types::Node = serde_json::from_value(payload.clone()).unwrap();
+    assert_eq!(node.next.as_ref().unwrap().value, "b");
+    assert_eq!(serde_json::to_value(&node).unwrap(), payload);
+}
+
+/// `types.*` source for `target`, generated from `fixtures/maps.json`
+async fn map_types(target: GenerateTarget) -> String {
+    let files = generate("maps.json", target).await;
+    files
+        .into_iter()
+        .find(|f| f.path.starts_with("types."))
+        .unwrap()
+        .content
+}
+
+#[tokio::test]
+async fn test_additional_properties_become_maps() {
+    let typescript = map_types(GenerateTarget::TypescriptTypes).await;
+    assert!(typescript.contains(": Record<string, number>;\n"));
+    assert!(typescript.contains("export type PetIndex = Record<string, Pet>;\n"));
+
+    let rust = map_types(GenerateTarget::RustSerde).await;
+    assert!(rust.contains("use std::collections::HashMap;\n"));
+    assert!(rust.contains("    pub counts: HashMap<String, i64>,\n"));
+    assert!(rust.contains("pub type PetIndex = HashMap<String, Pet>;\n"));
+
+    let python = map_types(GenerateTarget::PythonPydantic).await;
+    assert!(python.contains("    counts: dict[str, int]\n"));
+    assert!(python.contains("PetIndex = dict[str, 'Pet']\n"));
+}
+
+/// Handler signatures for `target`, generated from `fixtures/webhooks.json`
+async fn webhook_handlers(target: GenerateTarget) -> String {
+    let files = generate("webhooks.json", target).await;
+    files
+        .into_iter()
+        .find(|f| f.path.starts_with("webhooks."))
+        .unwrap()
+        .content
+}
+
+#[tokio::test]
+async fn test_webhook_and_callback_handler_signatures() {
+    let typescript = webhook_handlers(GenerateTarget::TypescriptTypes).await;
+    assert!(typescript.contains(
+        "export type OnDeliveryPostHandler = (payload: Types.Delivery) => Promise<Types.Ack> | Types.Ack;\n"
+    ));
+    assert!(
+        typescript.contains(
+            "export type PetAddedHandler = (payload: Types.Pet) => Promise<void> | void;\n"
+        )
+    );
+    assert!(typescript.contains(
+        "export interface WebhookHandlers {\n  OnDeliveryPost: OnDeliveryPostHandler;\n  PetAdded: PetAddedHandler;\n}\n"
+    ));
+
+    let rust = webhook_handlers(GenerateTarget::RustSerde).await;
+    assert!(rust.contains(
+        "    fn on_delivery_post(&self, payload: Delivery) -> impl Future<Output = Ack> + Send;\n"
+    ));
+    assert!(
+        rust.contains(
+            "    fn pet_added(&self, payload: Pet) -> impl Future<Output = ()> + Send;\n"
+        )
+    );
+
+    let python = webhook_handlers(GenerateTarget::PythonPydantic).await;
+    assert!(python.contains("    async def on_delivery_post(self, payload: Delivery) -> Ack:\n"));
+    assert!(python.contains("    async def pet_added(self, payload: Pet) -> None:\n"));
+}
+
+/// Client source for `target`, generated from `fixtures/headers.json`
+async fn headers_client(target: GenerateTarget) -> String {
+    let files = generate("headers.json", target).await;
+    files
+        .into_iter()
+        .find(|f| f.path.starts_with("client."))
+        .unwrap()
+        .content
+}
+
+#[tokio::test]
+async fn test_clients_return_typed_headers_and_send_accept() {
+    let fetch = headers_client(GenerateTarget::TypescriptFetch).await;
+    assert!(fetch.contains(
+        "export interface ListPetsHeaders {\n  XNextCursor?: string;\n  XRateLimit?: number;\n}\n"
+    ));
+    assert!(fetch.contains("Promise<ApiResponse<Types.ListPetsResponse, ListPetsHeaders>>"));
+    // The `+json` type is preferred over the first declared one
+    assert!(fetch.contains("headers: { Accept: 'application/vnd.pets+json' }"));
+    assert!(
+        fetch.contains("XRateLimit: readHeader(response.headers.get('X-Rate-Limit'), Number),")
+    );
+
+    let axios = headers_client(GenerateTarget::TypescriptAxios).await;
+    assert!(axios.contains("{ headers: { Accept: 'application/vnd.pets+json' } }"));
+    assert!(axios.contains("XRateLimit: readHeader(headers['x-rate-limit'], Number),"));
+
+    let rust = headers_client(GenerateTarget::RustReqwest).await;
+    assert!(rust.contains("-> Result<(ListPetsResponse, ListPetsHeaders), reqwest::Error>"));
+    assert!(rust.contains(".header(\"Accept\", \"application/vnd.pets+json\")"));
+    assert!(rust.contains(
+        "pub struct ListPetsHeaders {\n    pub x_next_cursor: Option<String>,\n    pub x_rate_limit: Option<i64>,\n}\n"
+    ));
+}
+
+/// Run `script` with the generated Python package importable as `api`;
+/// false when no Python with pydantic 2 is available
+fn run_python(files: &[GeneratedFile], script: &str) -> bool {
+    let probe = std::process::Command::new("python3")
+        .args(["-c", "import pydantic; assert pydantic.VERSION >= '2'"])
+        .output();
+    if !probe.is_ok_and(|p| p.status.success()) {
+        eprintln!("skipping: python3 with pydantic 2 not available");
+        return false;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let package = dir.path().join("api");
+    std::fs::create_dir(&package).unwrap();
+    for file in files {
+        std::fs::write(package.join(&file.path), &file.content).unwrap();
+    }
+    let output = std::process::Command::new("python3")
+        .args(["-c", script])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "python failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    true
+}
+
+#[tokio::test]
+async fn test_python_discriminated_union_validates() {
+    let files = generate("pets-discriminator.json", GenerateTarget::PythonPydantic).await;
+    run_python(
+        &files,
+        r#"
+from pydantic import TypeAdapter, ValidationError
+from api.types import Owner, Pet, Cat, Dog
+
+payload = {"pets": [{"petType": "cat", "name": "Tom"}, {"petType": "dog", "bark": True}]}
+owner = Owner.model_validate(payload)
+assert isinstance(owner.pets[0], Cat), owner
+assert owner.model_dump(by_alias=True, exclude_none=True) == payload
+assert TypeAdapter(Pet).validate_python({"petType": "dog"}).pet_type == "dog"
+
+# Patterns reach pydantic verbatim
+Cat.model_validate({"petType": "cat", "name": "Rene\u0301e"})
+try:
+    Dog.model_validate({"petType": "dog", "nickname": "O'Neil"})
+    raise AssertionError("nickname pattern not enforced")
+except ValidationError:
+    pass
+"#,
+    );
+}
+
+#[tokio::test]
+async fn test_python_cyclic_models_validate() {
+    let files = generate("tree.json", GenerateTarget::PythonPydantic).await;
+    run_python(
+        &files,
+        r#"
+from api.types import Category, Employee, Node
+
+node = Node.model_validate({"value": "a", "next": {"value": "b"}})
+assert isinstance(node.next, Node) and node.next.next is None, node
+tree = Category.model_validate({"name": "root", "children": [{"name": "leaf", "children": []}]})
+assert tree.children[0].name == "leaf", tree
+employee = Employee.model_validate({"name": "ann", "team": {"lead": {"name": "bob"}}})
+assert employee.team.lead.name == "bob", employee
+"#,
+    );
+}
+
+#[tokio::test]
+async fn test_python_client_validates_alias_responses() {
+    let files = generate("pets-discriminator.json", GenerateTarget::PythonHttpx).await;
+    // httpx is replaced by a stub answering every GET with a list of pets
+    run_python(
+        &files,
+        r#"
+import asyncio, sys, types
+
+class Response:
+    headers = {}
+    def __init__(self, data):
+        self.data = data
+    def raise_for_status(self):
+        pass
+    def json(self):
+        return self.data
+
+class AsyncClient:
+    def __init__(self, **kwargs):
+        pass
+    async def get(self, url, **kwargs):
+        return Response([{"petType": "cat", "name": "Tom"}, {"petType": "dog"}])
+
+sys.modules["httpx"] = types.SimpleNamespace(AsyncClient=AsyncClient)
+
+from api.client import ApiClient
+from api.types import Cat, Dog
+
+pets = asyncio.run(ApiClient("http://test").list_pets())
+assert [type(p) for p in pets] == [Cat, Dog], pets
+"#,
+    );
+}
+
+/// Client source for `target`, generated from `fixtures/auth.json`
+async fn auth_client(target: GenerateTarget) -> String {
+    let files = generate("auth.json", target).await;
+    files
+        .into_iter()
+        .find(|f| f.path.starts_with("client."))
+        .unwrap()
+        .content
+}
+
+#[tokio::test]
+async fn test_auth_hooks_follow_operation_security() {
+    let fetch = auth_client(GenerateTarget::TypescriptFetch).await;
+    assert!(fetch.contains("  partnerKey?: string;\n  tenantKey?: string;"));
+    assert!(fetch.contains("headers['X-Partner-Key'] = auth.partnerKey;"));
+    assert!(fetch.contains("query['tenant_key'] = auth.tenantKey;"));
+    // `security: []` sends nothing; other operations list their schemes
+    assert!(fetch.contains("return request<void>(`/health`, { method: 'GET' });"));
+    assert!(fetch.contains("request<void>(`/items`, { method: 'GET' }, ['bearerToken']);"));
+    assert!(
+        fetch
+            .contains("request<void>(`/reports`, { method: 'GET' }, ['partnerKey', 'tenantKey']);")
+    );
+
+    let axios = auth_client(GenerateTarget::TypescriptAxios).await;
+    assert!(!axios.contains("interceptors"));
+    assert!(axios.contains("await api.get<void>(`/health`);"));
+    assert!(axios.contains(
+        "api.get<void>(`/admin`, { headers: { ...authHeaders(['basic']) }, params: authQuery(['basic']) });"
+    ));
+
+    let rust = auth_client(GenerateTarget::RustReqwest).await;
+    assert!(
+        rust.contains("    pub partner_key: Option<String>,\n    pub tenant_key: Option<String>,")
+    );
+    assert!(rust.contains(
+        "if let (true, Some(key)) = (schemes.contains(&\"tenant_key\"), &self.auth.tenant_key) {\n            request = request.query(&[(\"tenant_key\", key)]);"
+    ));
+    assert!(rust.contains("        self.client.get(&url).send()"));
+    assert!(rust.contains("self.authorize(self.client.get(&url), &[\"bearer_token\"])"));
+}
+
+#[tokio::test]
+async fn test_python_client_sends_credentials_per_operation() {
+    let files = generate("auth.json", GenerateTarget::PythonHttpx).await;
+    // httpx is replaced by a stub recording the arguments of every GET
+    run_python(
+        &files,
+        r#"
+import asyncio, sys, types
+
+calls = {}
+
+class Response:
+    headers = {}
+    def raise_for_status(self):
+        pass
+
+class BasicAuth:
+    def __init__(self, username, password):
+        self.credentials = (username, password)
+
+class AsyncClient:
+    def __init__(self, **kwargs):
+        assert not kwargs, kwargs
+    async def get(self, url, **kwargs):
+        calls[url] = kwargs
+        return Response()
+
+sys.modules["httpx"] = types.SimpleNamespace(AsyncClient=AsyncClient, BasicAuth=BasicAuth)
+
+from api.client import ApiClient
+
+client = ApiClient(
+    "http://test", bearer_token="t", username="u", password="p",
+    partner_key="partner", tenant_key="tenant",
+)
+
+async def main():
+    await client.get_health()
+    await client.list_items()
+    await client.get_reports()
+    await client.get_admin()
+
+asyncio.run(main())
+
+assert calls["http://test/health"] == {}, calls
+assert calls["http://test/items"]["headers"] == {"Authorization": "Bearer t"}
+reports = calls["http://test/reports"]
+assert reports["headers"] == {"X-Partner-Key": "partner"}, reports
+assert reports["params"] == {"tenant_key": "tenant"}, reports
+admin = calls["http://test/admin"]
+assert admin["headers"] == {} and admin["auth"].credentials == ("u", "p"), admin
+"#,
+    );
+}
+
+#[tokio::test]
+async fn test_python_maps_validate_values() {
+    let files = generate("maps.json", GenerateTarget::PythonPydantic).await;
+    run_python(
+        &files,
+        r#"
+from pydantic import ValidationError
+from api.types import Inventory, Pet
+
+inventory = Inventory.model_validate({"counts": {"a": 1}, "pets": {"tom": {"name": "Tom"}}})
+assert inventory.counts == {"a": 1}, inventory
+assert isinstance(inventory.pets["tom"], Pet), inventory
+try:
+    Inventory.model_validate({"counts": {"a": "many"}})
+    raise AssertionError("map values not validated")
+except ValidationError:
+    pass
+"#,
+    );
+}
+
+#[tokio::test]
+async fn test_python_webhook_handlers_resolve_types() {
+    let files = generate("webhooks.json", GenerateTarget::PythonPydantic).await;
+    run_python(
+        &files,
+        r#"
+import typing
+from api.types import Ack, Delivery, Pet
+from api.webhooks import WebhookHandlers
+
+hints = typing.get_type_hints(WebhookHandlers.on_delivery_post)
+assert hints == {"payload": Delivery, "return": Ack}, hints
+hints = typing.get_type_hints(WebhookHandlers.pet_added)
+assert hints == {"payload": Pet, "return": type(None)}, hints
+"#,
+    );
+}
+
+#[tokio::test]
+async fn test_python_client_returns_typed_headers() {
+    let files = generate("headers.json", GenerateTarget::PythonHttpx).await;
+    // httpx is replaced by a stub answering with a page of pets
+    run_python(
+        &files,
+        r#"
+import asyncio, sys, types
+
+calls = {}
+
+class Response:
+    headers = {"X-Rate-Limit": "100"}
+    def raise_for_status(self):
+        pass
+    def json(self):
+        return [{"name": "Tom"}]
+
+class AsyncClient:
+    def __init__(self, **kwargs):
+        pass
+    async def get(self, url, **kwargs):
+        calls[url] = kwargs
+        return Response()
+
+sys.modules["httpx"] = types.SimpleNamespace(AsyncClient=AsyncClient)
+
+from api.client import ApiClient
+from api.types import Pet
+
+pets, headers = asyncio.run(ApiClient("http://test").list_pets())
+assert [type(p) for p in pets] == [Pet], pets
+assert headers == {"x_rate_limit": 100}, headers
+assert calls["http://test/pets"]["headers"] == {"Accept": "application/vnd.pets+json"}, calls
+"#,
+    );
+}